@@ -0,0 +1,429 @@
+//! Functions for level payments over a number of periods, i.e. loans and annuities.
+//!
+//! Amounts follow the sign convention of a spreadsheet, i.e. money paid out is negative and money received is positive, so the present value, the payments and the future value balance to zero at `rate`.
+//! `due` is `true` for payments at the start of each period and `false` for payments at the end.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::cash_flows::scale;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::root_finder::solve_from_guess;
+use crate::power::powi;
+
+/// The level payment over `nper` periods at `rate` per period that takes a present value of `pv` to a future value of `fv`.
+///
+/// # Comments
+/// With `g = (1 + rate)^nper`, the payment is `-(pv * g + fv) * rate / ((g - 1) * (1 + rate * due))`, or `-(pv + fv) / nper` at a zero rate.
+/// It is infinite or NaN if `nper` is zero.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::pmt;
+///
+/// // borrowing 200,000 over 30 years at 6% a year, paid monthly in arrears
+/// let payment: f64 = pmt(0.06 / 12.0, 360, 200_000.0, 0.0, false);
+/// assert!((payment - -1_199.10).abs() < 0.005);
+///
+/// // saving 10,000 over 10 years at 5% a year, paid at the start of each year
+/// let payment: f64 = pmt(0.05, 10, 0.0, 10_000.0, true);
+/// assert!((payment - -757.19).abs() < 0.005);
+/// ```
+pub fn pmt<T>(rate: T, nper: u32, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    pmt_with_growth(
+        rate,
+        T::from(nper).unwrap(),
+        growth(rate, nper),
+        pv,
+        fv,
+        due,
+    )
+}
+
+/// The growth `(1 + rate)^nper` over a whole number of periods.
+fn growth<T>(rate: T, nper: u32) -> T
+where
+    T: Float,
+{
+    powi(T::one() + rate, nper as i32)
+}
+
+/// The factor `(1 + rate * due) * (growth - 1) / rate` by which the payment is multiplied to value the payments at the end of `nper` periods, where `growth = (1 + rate)^nper`; it is `nper` at a zero rate.
+///
+/// Near a zero rate `growth - 1` cancels, so the factor is instead taken from the first terms of its series in the rate, `nper * (1 + (nper - 1) * rate / 2 + (nper - 1) * (nper - 2) * rate^2 / 6)`.
+fn annuity_factor<T>(rate: T, nper: T, growth: T, due: bool) -> T
+where
+    T: Float,
+{
+    let timing: T = if due { T::one() + rate } else { T::one() };
+    if (rate * nper).abs() < T::epsilon().sqrt().sqrt() {
+        let two: T = T::from(2.0).unwrap();
+        let three: T = T::from(3.0).unwrap();
+        let series: T =
+            T::one() + rate * (nper - T::one()) / two * (T::one() + rate * (nper - two) / three);
+        return timing * nper * series;
+    }
+    timing * (growth - T::one()) / rate
+}
+
+/// `pmt` with the growth `(1 + rate)^nper` calculated by the caller, so that `nper` need not be a whole number e.g. for `spreadsheet::pmt`.
+pub(crate) fn pmt_with_growth<T>(rate: T, nper: T, growth: T, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    -(pv * growth + fv) / annuity_factor(rate, nper, growth, due)
+}
+
+/// The present value that `nper` payments of `pmt` take to a future value of `fv`, given the growth `(1 + rate)^nper`.
+pub(crate) fn pv_with_growth<T>(rate: T, nper: T, growth: T, pmt: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    -(fv + pmt * annuity_factor(rate, nper, growth, due)) / growth
+}
+
+/// The future value to which `nper` payments of `pmt` take a present value of `pv`, given the growth `(1 + rate)^nper`.
+pub(crate) fn fv_with_growth<T>(rate: T, nper: T, growth: T, pmt: T, pv: T, due: bool) -> T
+where
+    T: Float,
+{
+    -(pv * growth + pmt * annuity_factor(rate, nper, growth, due))
+}
+
+/// `rate` with the growth `(1 + rate)^nper` calculated by the caller as a function of the rate, so that `nper` need not be a whole number e.g. for `spreadsheet::rate`.
+///
+/// The rate is the root of the value at period 0 of `pv`, the payments and `fv`, found with `root_finder::solve_from_guess` to within a tolerance relative to the largest of them.
+pub(crate) fn rate_with_growth<T, G>(
+    nper: T,
+    growth: G,
+    pmt: T,
+    pv: T,
+    fv: T,
+    due: bool,
+    guess: T,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    G: Fn(T) -> T,
+{
+    let npv_at = |rate: T| {
+        let growth: T = growth(rate);
+        pv + (pmt * annuity_factor(rate, nper, growth, due) + fv) / growth
+    };
+    solve_from_guess(npv_at, guess, 100, scale([pv, pmt, fv].iter()))
+}
+
+/// The amount owed after `payments` payments of `pmt` on a present value of `pv`, with the sign of `pv`.
+///
+/// In arrears the last of them is at the end of period `payments`, and in advance at the start of it, so with no interest since.
+fn owed<T>(rate: T, payments: u32, pmt: T, pv: T, due: bool) -> T
+where
+    T: Float,
+{
+    let (periods, pv): (u32, T) = match (due, payments) {
+        (_, 0) => return pv,
+        (true, _) => (payments - 1, pv + pmt),
+        (false, _) => (payments, pv),
+    };
+    if rate.is_zero() {
+        return pv + pmt * T::from(periods).unwrap();
+    }
+    let growth: T = powi(T::one() + rate, periods as i32);
+    pv * growth + pmt * (growth - T::one()) / rate
+}
+
+/// The interest part of payment `per`, from 1 to `nper`, of the level payment `pmt(rate, nper, pv, fv, due)`.
+///
+/// # Comments
+/// The interest is `rate` times the amount owed over the period the payment ends, with the opposite sign, so the interest on a loan received is negative like the payment.
+/// A payment in advance ends no period when it is paid, so the first has no interest.
+/// It is NaN if `per` is not from 1 to `nper`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::{ipmt, pmt, ppmt};
+///
+/// // the first payment on 200,000 over 30 years at 6% a year, paid monthly
+/// let interest: f64 = ipmt(0.005, 1, 360, 200_000.0, 0.0, false);
+/// let principal: f64 = ppmt(0.005, 1, 360, 200_000.0, 0.0, false);
+/// assert!((interest - -1_000.0).abs() < 0.000_001);
+/// assert!((principal - -199.10).abs() < 0.005);
+/// assert!((interest + principal - pmt(0.005, 360, 200_000.0, 0.0, false)).abs() < 0.000_001);
+/// ```
+pub fn ipmt<T>(rate: T, per: u32, nper: u32, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    if per < 1 || nper < per {
+        return T::nan();
+    }
+    if due && per == 1 {
+        return T::zero();
+    }
+    -rate * owed(rate, per - 1, pmt(rate, nper, pv, fv, due), pv, due)
+}
+
+/// The principal part of payment `per`, from 1 to `nper`, of the level payment `pmt(rate, nper, pv, fv, due)`, i.e. the payment less `ipmt`.
+///
+/// # Comments
+/// It is NaN if `per` is not from 1 to `nper`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::ppmt;
+///
+/// // the principal repaid by each of 4 annual payments on 1,000 at 10% a year
+/// let principal: Vec<f64> = (1..=4).map(|per| ppmt(0.10, per, 4, 1_000.0, 0.0, false)).collect();
+/// assert!((principal[0] - -215.47).abs() < 0.005);
+/// assert!((principal[3] - -286.79).abs() < 0.005);
+/// assert!((principal.iter().sum::<f64>() - -1_000.0).abs() < 0.000_001);
+/// ```
+pub fn ppmt<T>(rate: T, per: u32, nper: u32, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    pmt(rate, nper, pv, fv, due) - ipmt(rate, per, nper, pv, fv, due)
+}
+
+/// The interest paid by payments `start_period` to `end_period` inclusive, from 1 to `nper`, of the level payment `pmt(rate, nper, pv, 0, due)`, i.e. the sum of `ipmt` over them.
+///
+/// # Comments
+/// It is calculated in closed form as the payments less `cumprinc`, so it takes the same time for any number of periods.
+/// It is NaN if the periods are not in order from 1 to `nper`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::{cumipmt, cumprinc};
+///
+/// // the interest and principal paid in the second year of a 30-year mortgage of 125,000 at 9% a year
+/// let interest: f64 = cumipmt(0.09 / 12.0, 360, 125_000.0, 13, 24, false);
+/// let principal: f64 = cumprinc(0.09 / 12.0, 360, 125_000.0, 13, 24, false);
+/// assert!((interest - -11_135.23).abs() < 0.005);
+/// assert!((principal - -934.11).abs() < 0.005);
+/// ```
+pub fn cumipmt<T>(rate: T, nper: u32, pv: T, start_period: u32, end_period: u32, due: bool) -> T
+where
+    T: Float,
+{
+    let payments: T = T::from(end_period.saturating_sub(start_period) + 1).unwrap();
+    pmt(rate, nper, pv, T::zero(), due) * payments
+        - cumprinc(rate, nper, pv, start_period, end_period, due)
+}
+
+/// The principal repaid by payments `start_period` to `end_period` inclusive, from 1 to `nper`, of the level payment `pmt(rate, nper, pv, 0, due)`, i.e. the sum of `ppmt` over them.
+///
+/// # Comments
+/// It is calculated in closed form as the change in the amount owed over the periods.
+/// It is NaN if the periods are not in order from 1 to `nper`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::cumprinc;
+///
+/// // all the principal of a loan is repaid over its term
+/// let principal: f64 = cumprinc(0.01, 12, 1_000.0, 1, 12, true);
+/// assert!((principal - -1_000.0).abs() < 0.000_001);
+/// ```
+pub fn cumprinc<T>(rate: T, nper: u32, pv: T, start_period: u32, end_period: u32, due: bool) -> T
+where
+    T: Float,
+{
+    if start_period < 1 || end_period < start_period || nper < end_period {
+        return T::nan();
+    }
+    let payment: T = pmt(rate, nper, pv, T::zero(), due);
+    owed(rate, end_period, payment, pv, due) - owed(rate, start_period - 1, payment, pv, due)
+}
+
+/// The rate per period at which `nper` payments of `pmt` take a present value of `pv` to a future value of `fv`, searching outwards from `guess`.
+///
+/// # Comments
+/// The rate is the IRR of `pv`, the payments and `fv` as a series of cash flows, found with Brent's method to within an NPV tolerance relative to the largest of them; `spreadsheet::rate` solves in the same way.
+/// The NPVs it reports are those of that series, e.g. the value at period 0 of the amounts outstanding.
+///
+/// # Example with f64
+/// ```
+/// use time_value::annuity::rate;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // a loan of 8,000 repaid by 48 monthly payments of 200
+/// let monthly: Irr<f64> = rate(48, -200.0, 8_000.0, 0.0, false, 0.01);
+/// assert!(monthly.is_valid());
+/// assert!((monthly.irr() - 0.007_701_472).abs() < 0.000_000_001);
+/// ```
+pub fn rate<T>(nper: u32, pmt: T, pv: T, fv: T, due: bool, guess: T) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    rate_with_growth(
+        T::from(nper).unwrap(),
+        |rate| growth(rate, nper),
+        pmt,
+        pv,
+        fv,
+        due,
+        guess,
+    )
+}
+
+#[cfg(test)]
+mod pmt_tests {
+    use crate::annuity::pmt;
+    use crate::power::powi;
+
+    #[test]
+    fn it_balances_the_cash_flows() {
+        for due in [false, true].iter() {
+            let rate: f64 = 0.01;
+            let payment: f64 = pmt(rate, 24, 5_000.0, -1_000.0, *due);
+            let first: i32 = if *due { 0 } else { 1 };
+            let payments: f64 = (first..first + 24)
+                .map(|period| payment * powi(1.0 + rate, -period))
+                .sum();
+            assert!((5_000.0 + payments - 1_000.0 * powi(1.0 + rate, -24)).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn it_works_at_a_zero_rate() {
+        assert_eq!(pmt(0.0, 10, 1_000.0, 0.0, false), -100.0);
+        assert_eq!(pmt(0.0_f32, 4, 0.0, 100.0, true), -25.0);
+    }
+
+    #[test]
+    fn it_is_continuous_at_a_zero_rate() {
+        let at_zero: f64 = pmt(0.0, 360, 100_000.0, 0.0, false);
+        for rate in [1.0e-15, 1.0e-12, -1.0e-10].iter() {
+            let payment: f64 = pmt(*rate, 360, 100_000.0, 0.0, false);
+            assert!((payment - at_zero).abs() < 0.000_01);
+        }
+    }
+
+    #[test]
+    fn it_is_not_finite_without_periods() {
+        assert!(!pmt(0.05_f64, 0, 1_000.0, 0.0, false).is_finite());
+        assert!(!pmt(0.0_f64, 0, 1_000.0, 0.0, false).is_finite());
+    }
+}
+
+#[cfg(test)]
+mod rate_tests {
+    use crate::annuity::{pmt, rate};
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::spreadsheet;
+
+    #[test]
+    fn it_inverts_pmt() {
+        for due in [false, true].iter() {
+            for periodic_rate in [0.0, 0.004, 0.05, 0.25].iter() {
+                let payment: f64 = pmt(*periodic_rate, 36, 20_000.0, -5_000.0, *due);
+                let solved: Irr<f64> = rate(36, payment, 20_000.0, -5_000.0, *due, 0.10);
+                assert!(solved.is_valid());
+                assert!((solved.irr() - *periodic_rate).abs() < 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn it_agrees_with_the_spreadsheet() {
+        for due in [false, true].iter() {
+            let solved: Irr<f64> = rate(48, -200.0, 8_000.0, 0.0, *due, 0.10);
+            let spreadsheet: f64 = spreadsheet::rate(48.0, -200.0, 8_000.0, 0.0, *due, 0.10);
+            assert!((solved.irr() - spreadsheet).abs() < 0.000_000_000_001);
+        }
+    }
+
+    #[test]
+    fn it_accepts_a_guess_that_is_already_the_rate() {
+        let solved: Irr<f64> = rate(1, 0.0, -100.0, 110.0001, false, 0.10);
+        assert!(solved.is_valid());
+        assert!((solved.irr() - 0.100_001).abs() < 0.000_000_001);
+    }
+
+    #[test]
+    fn it_is_invalid_without_a_solution() {
+        // payments that never repay the loan
+        let solved: Irr<f32> = rate(12, 100.0, 1_000.0, 0.0, false, 0.10);
+        assert!(!solved.is_valid());
+    }
+}
+
+#[cfg(test)]
+mod ipmt_tests {
+    use crate::annuity::{ipmt, pmt, ppmt};
+    use crate::loan::{amortize, Schedule};
+
+    #[test]
+    fn it_matches_an_amortization_schedule() {
+        let schedule: Schedule<f64> = amortize(1_000.0, 0.01, 12);
+        for row in schedule.rows() {
+            let per: u32 = row.period() as u32;
+            assert!((ipmt(0.01, per, 12, 1_000.0, 0.0, false) + row.interest()).abs() < 1e-9);
+            assert!((ppmt(0.01, per, 12, 1_000.0, 0.0, false) + row.principal()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_charges_interest_between_payments_in_advance() {
+        let payment: f64 = pmt(0.01, 12, 1_000.0, 0.0, true);
+        assert_eq!(ipmt(0.01, 1, 12, 1_000.0, 0.0, true), 0.0);
+        let owed: f64 = 1_000.0 + payment;
+        assert!((ipmt(0.01, 2, 12, 1_000.0, 0.0, true) - -0.01 * owed).abs() < 1e-9);
+        let principal: f64 = (1..=12)
+            .map(|per| ppmt(0.01, per, 12, 1_000.0, 0.0, true))
+            .sum();
+        assert!((principal - -1_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_splits_a_balloon() {
+        let principal: f32 = (1..=5)
+            .map(|per| ppmt(0.0, per, 5, 1_000.0, -500.0, false))
+            .sum();
+        assert_eq!(principal, -500.0);
+        assert_eq!(ipmt(0.0_f32, 3, 5, 1_000.0, -500.0, false), 0.0);
+    }
+
+    #[test]
+    fn it_is_nan_outside_the_term() {
+        assert!(ipmt(0.01_f64, 0, 12, 1_000.0, 0.0, false).is_nan());
+        assert!(ppmt(0.01_f64, 13, 12, 1_000.0, 0.0, true).is_nan());
+    }
+}
+
+#[cfg(test)]
+mod cumipmt_tests {
+    use crate::annuity::{cumipmt, cumprinc, ipmt, ppmt};
+
+    #[test]
+    fn it_sums_ipmt_and_ppmt() {
+        for due in [false, true].iter() {
+            for rate in [0.0, 0.004, 0.05].iter() {
+                let interest: f64 = (7..=30)
+                    .map(|per| ipmt(*rate, per, 48, 20_000.0, 0.0, *due))
+                    .sum();
+                let principal: f64 = (7..=30)
+                    .map(|per| ppmt(*rate, per, 48, 20_000.0, 0.0, *due))
+                    .sum();
+                assert!((cumipmt(*rate, 48, 20_000.0, 7, 30, *due) - interest).abs() < 1e-9);
+                assert!((cumprinc(*rate, 48, 20_000.0, 7, 30, *due) - principal).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn it_matches_single_periods() {
+        assert!(cumipmt(0.01_f32, 12, 1_000.0, 1, 1, true).abs() < 1e-4);
+        assert!((cumipmt(0.01_f64, 12, 1_000.0, 1, 1, false) - -10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_is_nan_for_periods_out_of_order_or_outside_the_term() {
+        assert!(cumipmt(0.01_f64, 12, 1_000.0, 0, 3, false).is_nan());
+        assert!(cumprinc(0.01_f64, 12, 1_000.0, 5, 4, false).is_nan());
+        assert!(cumprinc(0.01_f64, 12, 1_000.0, 5, 13, true).is_nan());
+    }
+}
@@ -0,0 +1,620 @@
+//! Standard reporting windows (MTD, QTD, YTD and ITD), and the returns within them.
+//!
+//! Period `p` is the end of the `p`-th period after inception, so the valuation of period 0 is the value at inception.
+//! Periods are calendar months unless other month, quarter and year boundaries are supplied, e.g. weeks in a 4-4-5 retail calendar.
+
+use num::{Float, Signed};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Error, Formatter};
+use std::iter::{Product, Sum};
+
+use crate::cash_flow_series::CashFlowSeries;
+use crate::irr::bisection::functions::irr::bisection_with_config;
+use crate::irr::bisection::structs::config::BisectionConfig;
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::irr::Irr;
+
+/// The reason reporting windows cannot be determined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarError {
+    /// The inception month is not a calendar month from 1 to 12.
+    InvalidMonth(u32),
+}
+
+impl Display for CalendarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            CalendarError::InvalidMonth(month) => {
+                write!(f, "{} is not a calendar month from 1 to 12", month)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+/// The periods from the valuation at `start` to the valuation at `end`; the cash flows in periods `start + 1` to `end` fall within it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Window {
+    start: usize,
+    end: usize,
+}
+
+impl Window {
+    pub fn new(start: usize, end: usize) -> Window {
+        Window { start, end }
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `start` instead")]
+    pub fn get_start(&self) -> usize {
+        self.start()
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `end` instead")]
+    pub fn get_end(&self) -> usize {
+        self.end()
+    }
+
+    /// The number of periods in the window.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The month-, quarter-, year- and inception-to-date windows ending at the same period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Windows {
+    mtd: Window,
+    qtd: Window,
+    ytd: Window,
+    itd: Window,
+}
+
+impl Windows {
+    pub fn mtd(&self) -> Window {
+        self.mtd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `mtd` instead")]
+    pub fn get_mtd(&self) -> Window {
+        self.mtd()
+    }
+
+    pub fn qtd(&self) -> Window {
+        self.qtd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `qtd` instead")]
+    pub fn get_qtd(&self) -> Window {
+        self.qtd()
+    }
+
+    pub fn ytd(&self) -> Window {
+        self.ytd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `ytd` instead")]
+    pub fn get_ytd(&self) -> Window {
+        self.ytd()
+    }
+
+    pub fn itd(&self) -> Window {
+        self.itd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `itd` instead")]
+    pub fn get_itd(&self) -> Window {
+        self.itd()
+    }
+}
+
+/// The periods at which months, quarters and years end.
+///
+/// # Example
+/// ```
+/// use time_value::calendar::Boundaries;
+///
+/// // fiscal quarters ending in January, April, July and October, for a history starting at the end of December
+/// let boundaries: Boundaries = Boundaries::new(0..24, (1..24).step_by(3), vec![1, 13]);
+/// assert!(boundaries.is_quarter_end(4));
+/// assert!(!boundaries.is_year_end(12));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Boundaries {
+    month_ends: BTreeSet<usize>,
+    quarter_ends: BTreeSet<usize>,
+    year_ends: BTreeSet<usize>,
+}
+
+impl Boundaries {
+    pub fn new<M, Q, Y>(month_ends: M, quarter_ends: Q, year_ends: Y) -> Boundaries
+    where
+        M: IntoIterator<Item = usize>,
+        Q: IntoIterator<Item = usize>,
+        Y: IntoIterator<Item = usize>,
+    {
+        Boundaries {
+            month_ends: month_ends.into_iter().collect(),
+            quarter_ends: quarter_ends.into_iter().collect(),
+            year_ends: year_ends.into_iter().collect(),
+        }
+    }
+
+    /// The boundaries of calendar months, for `periods` monthly periods from the end of calendar month `inception_month` (1 to 12).
+    ///
+    /// # Example
+    /// ```
+    /// use time_value::calendar::{Boundaries, CalendarError};
+    ///
+    /// let boundaries: Boundaries = Boundaries::calendar_months(11, 24).unwrap();
+    /// assert!(boundaries.is_year_end(1));
+    /// assert_eq!(Boundaries::calendar_months(0, 24), Err(CalendarError::InvalidMonth(0)));
+    /// ```
+    pub fn calendar_months(
+        inception_month: u32,
+        periods: usize,
+    ) -> Result<Boundaries, CalendarError> {
+        if !(1..=12).contains(&inception_month) {
+            return Err(CalendarError::InvalidMonth(inception_month));
+        }
+        let month = |period: &usize| (inception_month as usize - 1 + period) % 12 + 1;
+        Ok(Boundaries::new(
+            0..=periods,
+            (0..=periods).filter(|period| month(period) % 3 == 0),
+            (0..=periods).filter(|period| month(period) == 12),
+        ))
+    }
+
+    /// The boundaries of a 4-4-5 retail calendar, for `periods` weekly periods from the start of a 52-week fiscal year.
+    ///
+    /// # Comments
+    /// Each quarter is months of 4, 4 and 5 weeks; 53-week years can be handled with `new`.
+    pub fn four_four_five(periods: usize) -> Boundaries {
+        let week = |period: &usize| period % 52;
+        Boundaries::new(
+            (0..=periods).filter(|period| [0, 4, 8].contains(&(week(period) % 13))),
+            (0..=periods).filter(|period| week(period) % 13 == 0),
+            (0..=periods).filter(|period| week(period) == 0),
+        )
+    }
+
+    pub fn is_month_end(&self, period: usize) -> bool {
+        self.month_ends.contains(&period)
+    }
+
+    pub fn is_quarter_end(&self, period: usize) -> bool {
+        self.quarter_ends.contains(&period)
+    }
+
+    pub fn is_year_end(&self, period: usize) -> bool {
+        self.year_ends.contains(&period)
+    }
+}
+
+/// The reporting windows ending at period `as_of`, for a history of monthly periods that starts at the end of calendar month `inception_month` (1 to 12).
+///
+/// # Comments
+/// A window starts at the last month end before `as_of` that closes a month, quarter or year, or at inception if that is later.
+/// At a quarter end the QTD window is the quarter just ended, and likewise for years; at period 0 every window is empty.
+/// The result is an error if `inception_month` is not from 1 to 12.
+///
+/// # Example
+/// ```
+/// use time_value::calendar::{windows, Window, Windows};
+///
+/// // inception at the end of November, reporting at the end of the following May
+/// let reporting: Windows = windows(6, 11).unwrap();
+/// assert_eq!(reporting.mtd(), Window::new(5, 6));
+/// assert_eq!(reporting.qtd(), Window::new(4, 6));
+/// assert_eq!(reporting.ytd(), Window::new(1, 6));
+/// assert_eq!(reporting.itd(), Window::new(0, 6));
+/// ```
+pub fn windows(as_of: usize, inception_month: u32) -> Result<Windows, CalendarError> {
+    Boundaries::calendar_months(inception_month, as_of)
+        .map(|boundaries| windows_with_boundaries(as_of, &boundaries))
+}
+
+/// The reporting windows ending at period `as_of`, with the month, quarter and year ends in `boundaries`.
+///
+/// # Comments
+/// A window starts at the last boundary before `as_of`, or at inception if there is none.
+///
+/// # Example
+/// ```
+/// use time_value::calendar::{windows_with_boundaries, Boundaries, Window, Windows};
+///
+/// // weekly periods in a 4-4-5 calendar, reporting at the end of week 20
+/// let reporting: Windows = windows_with_boundaries(20, &Boundaries::four_four_five(20));
+/// assert_eq!(reporting.mtd(), Window::new(17, 20));
+/// assert_eq!(reporting.qtd(), Window::new(13, 20));
+/// assert_eq!(reporting.ytd(), Window::new(0, 20));
+/// ```
+pub fn windows_with_boundaries(as_of: usize, boundaries: &Boundaries) -> Windows {
+    let last_start = |ends: &BTreeSet<usize>| ends.range(..as_of).next_back().copied().unwrap_or(0);
+    Windows {
+        mtd: Window::new(last_start(&boundaries.month_ends), as_of),
+        qtd: Window::new(last_start(&boundaries.quarter_ends), as_of),
+        ytd: Window::new(last_start(&boundaries.year_ends), as_of),
+        itd: Window::new(0, as_of),
+    }
+}
+
+/// The returns over a window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    window: Window,
+    mwr: Irr<T>,
+    twr: T,
+}
+
+impl<T> WindowReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `window` instead")]
+    pub fn get_window(&self) -> Window {
+        self.window()
+    }
+
+    /// The money-weighted return per period, i.e. the IRR of the window's opening value, cash flows and closing value.
+    pub fn mwr(&self) -> &Irr<T> {
+        &self.mwr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `mwr` instead")]
+    pub fn get_mwr(&self) -> &Irr<T> {
+        self.mwr()
+    }
+
+    /// The time-weighted return over the whole window, linking the return of each period.
+    pub fn twr(&self) -> T {
+        self.twr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `twr` instead")]
+    pub fn get_twr(&self) -> T {
+        self.twr()
+    }
+}
+
+/// Calculates the money- and time-weighted returns over a window, from the valuation at the end of each period and the cash flows.
+///
+/// # Assumptions
+/// The cash flows are from the investor's point of view, so contributions are negative and withdrawals positive, and occur at the end of their period, so that they are included in its valuation.
+///
+/// # Comments
+/// The return of period `p` is `(valuations[p] + net cash flow in p) / valuations[p - 1] - 1`, and the TWR links these geometrically.
+/// The MWR is found with `bisection_with_config` and the default `BisectionConfig`.
+/// An empty window has a TWR of zero, and a window that ends at or after `valuations.len()` a NaN TWR; both have an invalid MWR with a NaN IRR and the failure reason `FailureReason::InvalidInput`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::calendar::{window_return, Window, WindowReturn};
+/// use time_value::cash_flow_series::{CashFlow, CashFlowSeries};
+///
+/// // 10% in the first period, then 500 is contributed, then 0% in the second period
+/// let valuations: Vec<f64> = vec![1_000.0, 1_600.0, 1_600.0];
+/// let cash_flows: CashFlowSeries<f64> = vec![CashFlow::new(1, -500.0)].into_iter().collect();
+///
+/// let returns: WindowReturn<f64> = window_return(&valuations, &cash_flows, Window::new(0, 2));
+/// assert!((returns.twr() - 0.10).abs() < 0.000_001);
+/// assert!(returns.mwr().irr() < 0.05);
+/// ```
+pub fn window_return<T>(
+    valuations: &[T],
+    cash_flows: &CashFlowSeries<T>,
+    window: Window,
+) -> WindowReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let is_within: bool = window.end() < valuations.len();
+    if window.is_empty() || !is_within {
+        let nan: T = T::nan();
+        return WindowReturn {
+            window,
+            mwr: Irr::new(nan, nan, nan, nan, 0, 0, nan, nan, false)
+                .with_failure_reason(FailureReason::InvalidInput),
+            twr: if is_within { T::zero() } else { nan },
+        };
+    }
+
+    let net: Vec<T> = cash_flows.by_period();
+    let net_at = |period: usize| net.get(period).copied().unwrap_or_else(T::zero);
+    let (start, end): (usize, usize) = (window.start(), window.end());
+
+    let twr: T = ((start + 1)..=end)
+        .map(|period| (valuations[period] + net_at(period)) / valuations[period - 1])
+        .product::<T>()
+        - T::one();
+
+    let mut mwr_cash_flows: Vec<T> = vec![-valuations[start]];
+    mwr_cash_flows.extend(((start + 1)..=end).map(net_at));
+    mwr_cash_flows[window.len()] = mwr_cash_flows[window.len()] + valuations[end];
+    let mwr: Irr<T> = bisection_with_config(mwr_cash_flows.iter(), &BisectionConfig::new());
+
+    WindowReturn { window, mwr, twr }
+}
+
+/// The returns over the standard reporting windows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnsReport<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    mtd: WindowReturn<T>,
+    qtd: WindowReturn<T>,
+    ytd: WindowReturn<T>,
+    itd: WindowReturn<T>,
+}
+
+impl<T> ReturnsReport<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn mtd(&self) -> &WindowReturn<T> {
+        &self.mtd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `mtd` instead")]
+    pub fn get_mtd(&self) -> &WindowReturn<T> {
+        self.mtd()
+    }
+
+    pub fn qtd(&self) -> &WindowReturn<T> {
+        &self.qtd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `qtd` instead")]
+    pub fn get_qtd(&self) -> &WindowReturn<T> {
+        self.qtd()
+    }
+
+    pub fn ytd(&self) -> &WindowReturn<T> {
+        &self.ytd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `ytd` instead")]
+    pub fn get_ytd(&self) -> &WindowReturn<T> {
+        self.ytd()
+    }
+
+    pub fn itd(&self) -> &WindowReturn<T> {
+        &self.itd
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `itd` instead")]
+    pub fn get_itd(&self) -> &WindowReturn<T> {
+        self.itd()
+    }
+}
+
+/// Calculates the returns over the reporting windows ending at the last valuation, for monthly periods; see `windows` and `window_return`.
+///
+/// # Comments
+/// The result is an error if `inception_month` is not from 1 to 12.
+///
+/// # Example with f64
+/// ```
+/// use time_value::calendar::{report, ReturnsReport};
+/// use time_value::cash_flow_series::CashFlowSeries;
+///
+/// // 1% a month from the end of June to the end of August
+/// let valuations: Vec<f64> = vec![100.0, 101.0, 102.01];
+/// let returns: ReturnsReport<f64> = report(&valuations, &CashFlowSeries::new(), 6).unwrap();
+///
+/// assert!((returns.mtd().twr() - 0.01).abs() < 0.000_001);
+/// assert!((returns.qtd().twr() - 0.0201).abs() < 0.000_001);
+/// assert!((returns.qtd().mwr().irr() - 0.01).abs() < 0.000_01);
+/// assert_eq!(returns.ytd().window(), returns.itd().window());
+/// ```
+pub fn report<T>(
+    valuations: &[T],
+    cash_flows: &CashFlowSeries<T>,
+    inception_month: u32,
+) -> Result<ReturnsReport<T>, CalendarError>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let as_of: usize = valuations.len().saturating_sub(1);
+    Boundaries::calendar_months(inception_month, as_of)
+        .map(|boundaries| report_with_boundaries(valuations, cash_flows, &boundaries))
+}
+
+/// Calculates the returns over the reporting windows ending at the last valuation, with the month, quarter and year ends in `boundaries`; see `windows_with_boundaries` and `window_return`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::calendar::{report_with_boundaries, Boundaries, ReturnsReport, Window};
+/// use time_value::cash_flow_series::CashFlowSeries;
+///
+/// // 0.1% a week for the first 15 weeks of a 4-4-5 calendar
+/// let valuations: Vec<f64> = (0..=15).map(|week| 100.0 * 1.001_f64.powi(week)).collect();
+/// let returns: ReturnsReport<f64> =
+///     report_with_boundaries(&valuations, &CashFlowSeries::new(), &Boundaries::four_four_five(15));
+///
+/// assert_eq!(returns.mtd().window(), Window::new(13, 15));
+/// assert!((returns.mtd().twr() - (1.001_f64.powi(2) - 1.0)).abs() < 0.000_001);
+/// ```
+pub fn report_with_boundaries<T>(
+    valuations: &[T],
+    cash_flows: &CashFlowSeries<T>,
+    boundaries: &Boundaries,
+) -> ReturnsReport<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let reporting: Windows =
+        windows_with_boundaries(valuations.len().saturating_sub(1), boundaries);
+    ReturnsReport {
+        mtd: window_return(valuations, cash_flows, reporting.mtd()),
+        qtd: window_return(valuations, cash_flows, reporting.qtd()),
+        ytd: window_return(valuations, cash_flows, reporting.ytd()),
+        itd: window_return(valuations, cash_flows, reporting.itd()),
+    }
+}
+
+#[cfg(test)]
+mod windows_tests {
+    use crate::calendar::{windows, Window, Windows};
+
+    #[test]
+    fn it_works_at_inception() {
+        let reporting: Windows = windows(0, 3).unwrap();
+        assert!(reporting.mtd().is_empty());
+        assert!(reporting.qtd().is_empty());
+        assert!(reporting.ytd().is_empty());
+        assert!(reporting.itd().is_empty());
+    }
+
+    #[test]
+    fn it_reports_the_quarter_just_ended() {
+        // inception at the end of December, reporting at the end of March
+        let reporting: Windows = windows(3, 12).unwrap();
+        assert_eq!(reporting.qtd(), Window::new(0, 3));
+        assert_eq!(reporting.ytd(), Window::new(0, 3));
+
+        // and at the end of the following December
+        let reporting: Windows = windows(12, 12).unwrap();
+        assert_eq!(reporting.qtd(), Window::new(9, 12));
+        assert_eq!(reporting.ytd(), Window::new(0, 12));
+    }
+
+    #[test]
+    fn it_starts_the_year_at_the_last_december() {
+        let reporting: Windows = windows(20, 1).unwrap();
+        assert_eq!(reporting.mtd(), Window::new(19, 20));
+        assert_eq!(reporting.qtd(), Window::new(20 - 3, 20));
+        assert_eq!(reporting.ytd(), Window::new(11, 20));
+    }
+}
+
+#[cfg(test)]
+mod windows_with_boundaries_tests {
+    use crate::calendar::{windows, windows_with_boundaries, Boundaries, Window, Windows};
+
+    #[test]
+    fn it_matches_windows_with_calendar_months() {
+        for inception_month in 1..=12 {
+            for as_of in 0..30 {
+                let boundaries: Boundaries =
+                    Boundaries::calendar_months(inception_month, as_of).unwrap();
+                assert_eq!(
+                    Ok(windows_with_boundaries(as_of, &boundaries)),
+                    windows(as_of, inception_month)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_follows_a_four_four_five_calendar() {
+        // week 60 is in the second month of the second year
+        let reporting: Windows = windows_with_boundaries(60, &Boundaries::four_four_five(60));
+        assert_eq!(reporting.mtd(), Window::new(56, 60));
+        assert_eq!(reporting.qtd(), Window::new(52, 60));
+        assert_eq!(reporting.ytd(), Window::new(52, 60));
+    }
+
+    #[test]
+    fn it_starts_at_inception_without_boundaries() {
+        let reporting: Windows = windows_with_boundaries(7, &Boundaries::default());
+        assert_eq!(reporting.mtd(), Window::new(0, 7));
+        assert_eq!(reporting.ytd(), reporting.itd());
+    }
+}
+
+#[cfg(test)]
+mod window_return_tests {
+    use crate::calendar::{window_return, Window, WindowReturn};
+    use crate::cash_flow_series::{CashFlow, CashFlowSeries};
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+
+    #[test]
+    fn it_ignores_cash_flows_outside_the_window() {
+        let valuations: Vec<f64> = vec![100.0, 210.0, 231.0];
+        let cash_flows: CashFlowSeries<f64> = vec![CashFlow::new(1, -100.0)].into_iter().collect();
+        let returns: WindowReturn<f64> = window_return(&valuations, &cash_flows, Window::new(1, 2));
+
+        assert!((returns.twr() - 0.10).abs() < 0.000_001);
+        assert!((returns.mwr().irr() - 0.10).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_works_with_an_empty_window() {
+        let valuations: Vec<f64> = vec![100.0];
+        let returns: WindowReturn<f64> =
+            window_return(&valuations, &CashFlowSeries::new(), Window::new(0, 0));
+
+        assert_eq!(returns.twr(), 0.0);
+        assert!(!returns.mwr().is_valid());
+        assert_eq!(
+            returns.mwr().failure_reason(),
+            Some(FailureReason::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_window_past_the_valuations() {
+        let valuations: Vec<f64> = vec![100.0, 101.0];
+        for window in [Window::new(0, 2), Window::new(1, 5), Window::new(3, 3)].iter() {
+            let returns: WindowReturn<f64> =
+                window_return(&valuations, &CashFlowSeries::new(), *window);
+
+            assert!(returns.twr().is_nan());
+            assert!(!returns.mwr().is_valid());
+            assert_eq!(
+                returns.mwr().failure_reason(),
+                Some(FailureReason::InvalidInput)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use crate::calendar::{report, windows, Boundaries, CalendarError, ReturnsReport};
+    use crate::cash_flow_series::CashFlowSeries;
+
+    #[test]
+    fn it_rejects_months_outside_the_calendar() {
+        let valuations: Vec<f64> = vec![100.0, 101.0];
+        for month in [0, 13].iter() {
+            assert_eq!(
+                Boundaries::calendar_months(*month, 12),
+                Err(CalendarError::InvalidMonth(*month))
+            );
+            assert_eq!(windows(1, *month), Err(CalendarError::InvalidMonth(*month)));
+            assert!(report(&valuations, &CashFlowSeries::new(), *month).is_err());
+        }
+    }
+
+    #[test]
+    fn it_works_without_valuations() {
+        let returns: ReturnsReport<f64> = report(&[], &CashFlowSeries::new(), 1).unwrap();
+        assert!(!returns.itd().mwr().is_valid());
+        assert!(returns.itd().twr().is_nan());
+    }
+}
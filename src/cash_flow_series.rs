@@ -0,0 +1,632 @@
+//! A struct for a series of cash flows that carry a period and an optional tag.
+
+use num::Float;
+use std::collections::BTreeMap;
+
+use crate::cash_flows::scale;
+use crate::irr::bisection::functions::are_equal_enough;
+
+/// The version of the encoding hashed by `CashFlowSeries::fingerprint`.
+const FINGERPRINT_VERSION: u8 = 1;
+
+/// The bits to which `CashFlowSeries::fingerprint` maps every NaN.
+const CANONICAL_NAN_BITS: u64 = 0x7ff8_0000_0000_0000;
+
+/// The offset basis of the 128-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+
+/// The prime of the 128-bit FNV-1a hash.
+const FNV_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// What a cash flow represents, so that it can be grouped or excluded in an analysis.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tag {
+    Contribution,
+    Distribution,
+    Fee,
+    Income,
+    Principal,
+    Tax,
+    /// A transfer between accounts of the same owner, which is internal to a portfolio of those accounts.
+    Transfer,
+    /// Any other classification, named by the user.
+    Custom(String),
+}
+
+/// A cash flow at the end of a period, optionally tagged.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashFlow<T>
+where
+    T: Float,
+{
+    period: usize,
+    amount: T,
+    tag: Option<Tag>,
+}
+
+impl<T> CashFlow<T>
+where
+    T: Float,
+{
+    /// An untagged cash flow.
+    pub fn new(period: usize, amount: T) -> CashFlow<T> {
+        CashFlow {
+            period,
+            amount,
+            tag: None,
+        }
+    }
+
+    pub fn with_tag(mut self, tag: Tag) -> CashFlow<T> {
+        self.tag = Some(tag);
+        self
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `period` instead")]
+    pub fn get_period(&self) -> usize {
+        self.period()
+    }
+
+    pub fn amount(&self) -> T {
+        self.amount
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `amount` instead")]
+    pub fn get_amount(&self) -> T {
+        self.amount()
+    }
+
+    pub fn tag(&self) -> Option<&Tag> {
+        self.tag.as_ref()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `tag` instead")]
+    pub fn get_tag(&self) -> Option<&Tag> {
+        self.tag()
+    }
+}
+
+/// A series of cash flows divided by a scale, e.g. to express large notionals in millions before solving; see `CashFlowSeries::normalized`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledSeries<T>
+where
+    T: Float,
+{
+    series: CashFlowSeries<T>,
+    scale: T,
+}
+
+impl<T> ScaledSeries<T>
+where
+    T: Float,
+{
+    pub fn series(&self) -> &CashFlowSeries<T> {
+        &self.series
+    }
+
+    pub fn scale(&self) -> T {
+        self.scale
+    }
+
+    /// Converts an amount in the units of the rescaled series, e.g. an NPV, back into the currency of the original series.
+    pub fn unscale(&self, amount: T) -> T {
+        amount * self.scale
+    }
+}
+
+/// Cash flows in the order they were added, any number of which may fall in the same period.
+///
+/// # Comments
+/// The solvers take one amount per period; see `by_period`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+///
+/// let mut series: CashFlowSeries<f64> = CashFlowSeries::new();
+/// series.push(CashFlow::new(0, -100.0).with_tag(Tag::Contribution));
+/// series.push(CashFlow::new(0, -2.0).with_tag(Tag::Fee));
+/// series.push(CashFlow::new(2, 130.0).with_tag(Tag::Distribution));
+///
+/// assert_eq!(series.by_period(), vec![-102.0, 0.0, 130.0]);
+/// assert_eq!(series.total_for(&Tag::Fee), -2.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashFlowSeries<T>
+where
+    T: Float,
+{
+    cash_flows: Vec<CashFlow<T>>,
+}
+
+impl<T> CashFlowSeries<T>
+where
+    T: Float,
+{
+    pub fn new() -> CashFlowSeries<T> {
+        CashFlowSeries { cash_flows: vec![] }
+    }
+
+    pub fn push(&mut self, cash_flow: CashFlow<T>) {
+        self.cash_flows.push(cash_flow);
+    }
+
+    pub fn cash_flows(&self) -> &[CashFlow<T>] {
+        &self.cash_flows
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `cash_flows` instead")]
+    pub fn get_cash_flows(&self) -> &[CashFlow<T>] {
+        self.cash_flows()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cash_flows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cash_flows.is_empty()
+    }
+
+    /// Sums the cash flows in each period from 0 to the last period with a cash flow, for use with the solvers.
+    pub fn by_period(&self) -> Vec<T> {
+        self.net_by_period().collect()
+    }
+
+    /// The net cash flow in each period from 0 to the last period with a cash flow, without copying the cash flows.
+    pub fn net_by_period(&self) -> impl Iterator<Item = T> + '_ {
+        let mut ordered: Vec<&CashFlow<T>> = self.cash_flows.iter().collect();
+        ordered.sort_by_key(|cash_flow| cash_flow.period);
+        let periods: usize = ordered.last().map_or(0, |cash_flow| cash_flow.period + 1);
+
+        let mut next: usize = 0;
+        (0..periods).map(move |period| {
+            let mut net: T = T::zero();
+            while next < ordered.len() && ordered[next].period == period {
+                net = net + ordered[next].amount;
+                next += 1;
+            }
+            net
+        })
+    }
+
+    /// The running total of the net cash flows from period 0 e.g. for a J-curve or the payback period.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::cash_flow_series::{CashFlow, CashFlowSeries};
+    ///
+    /// let series: CashFlowSeries<f64> = vec![
+    ///     CashFlow::new(0, -100.0),
+    ///     CashFlow::new(1, -20.0),
+    ///     CashFlow::new(2, 50.0),
+    ///     CashFlow::new(3, 90.0),
+    ///     CashFlow::new(1, 10.0),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let cumulative: Vec<f64> = series.cumulative().collect();
+    /// assert_eq!(cumulative, vec![-100.0, -110.0, -60.0, 30.0]);
+    ///
+    /// // the cash flows pay back in period 3
+    /// assert_eq!(series.cumulative().position(|total| 0.0 <= total), Some(3));
+    /// ```
+    pub fn cumulative(&self) -> impl Iterator<Item = T> + '_ {
+        self.net_by_period().scan(T::zero(), |total, net| {
+            *total = *total + net;
+            Some(*total)
+        })
+    }
+
+    /// Sums the cash flows with a tag.
+    pub fn total_for(&self, tag: &Tag) -> T {
+        self.cash_flows
+            .iter()
+            .filter(|cash_flow| cash_flow.tag.as_ref() == Some(tag))
+            .fold(T::zero(), |total, cash_flow| total + cash_flow.amount)
+    }
+
+    /// Whether two series have the same cash flows in the same order, with the same periods and tags and amounts within `tolerance`.
+    ///
+    /// # Comments
+    /// To compare series that may split the same net cash flows differently, compare their `by_period` amounts instead.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+    ///
+    /// let ours: CashFlowSeries<f64> =
+    ///     vec![CashFlow::new(0, -100.0), CashFlow::new(1, 110.0).with_tag(Tag::Distribution)]
+    ///         .into_iter()
+    ///         .collect();
+    /// let legacy: CashFlowSeries<f64> =
+    ///     vec![CashFlow::new(0, -100.0), CashFlow::new(1, 110.004).with_tag(Tag::Distribution)]
+    ///         .into_iter()
+    ///         .collect();
+    ///
+    /// assert!(ours.approx_eq(&legacy, 0.005));
+    /// assert!(!ours.approx_eq(&legacy, 0.001));
+    /// ```
+    pub fn approx_eq(&self, other: &CashFlowSeries<T>, tolerance: T) -> bool {
+        self.cash_flows.len() == other.cash_flows.len()
+            && self
+                .cash_flows
+                .iter()
+                .zip(other.cash_flows.iter())
+                .all(|(ours, theirs)| {
+                    ours.period == theirs.period
+                        && ours.tag == theirs.tag
+                        && are_equal_enough::is_true_within(ours.amount, theirs.amount, tolerance)
+                })
+    }
+
+    /// A series of the cash flows that satisfy `predicate`, in the same order.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+    /// use time_value::irr::bisection::functions::irr::bisection;
+    ///
+    /// let series: CashFlowSeries<f64> = vec![
+    ///     CashFlow::new(0, -100.0).with_tag(Tag::Contribution),
+    ///     CashFlow::new(1, -5.0).with_tag(Tag::Fee),
+    ///     CashFlow::new(2, 125.0).with_tag(Tag::Distribution),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// // the IRR excluding fees
+    /// let gross: Vec<f64> = series
+    ///     .filter(|cash_flow| cash_flow.tag() != Some(&Tag::Fee))
+    ///     .by_period();
+    /// assert!((bisection(gross.iter(), 0.0, 0.5, 100).irr() - 0.1180).abs() < 0.000_1);
+    /// ```
+    pub fn filter<P>(&self, mut predicate: P) -> CashFlowSeries<T>
+    where
+        P: FnMut(&CashFlow<T>) -> bool,
+    {
+        self.cash_flows
+            .iter()
+            .filter(|cash_flow| predicate(cash_flow))
+            .cloned()
+            .collect()
+    }
+
+    /// The series divided by its scale, so that its largest amount is between one and ten in absolute value; see `cash_flows::scale`.
+    ///
+    /// # Comments
+    /// Rates, such as the IRR, are the same for the rescaled series, while amounts, such as NPVs, are converted back with `ScaledSeries::unscale`.
+    ///
+    /// # Example with f32
+    /// ```
+    /// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, ScaledSeries};
+    /// use time_value::present_value::from_cash_flows_and_discount_rate as pv;
+    ///
+    /// let series: CashFlowSeries<f32> =
+    ///     vec![CashFlow::new(0, -2.5e9), CashFlow::new(1, 3.0e9)].into_iter().collect();
+    /// let normalized: ScaledSeries<f32> = series.normalized();
+    ///
+    /// assert_eq!(normalized.scale(), 1.0e9);
+    /// assert_eq!(normalized.series().by_period(), vec![-2.5, 3.0]);
+    /// let npv: f32 = pv(normalized.series().by_period().iter(), 0.05);
+    /// assert!((normalized.unscale(npv) - 357_142_857.0).abs() < 100.0);
+    /// ```
+    pub fn normalized(&self) -> ScaledSeries<T> {
+        let amounts: Vec<T> = self
+            .cash_flows
+            .iter()
+            .map(|cash_flow| cash_flow.amount)
+            .collect();
+        let scale: T = scale(amounts.iter());
+        ScaledSeries {
+            series: self
+                .cash_flows
+                .iter()
+                .map(|cash_flow| CashFlow {
+                    amount: cash_flow.amount / scale,
+                    ..cash_flow.clone()
+                })
+                .collect(),
+            scale,
+        }
+    }
+
+    /// A hash of the cash flows that is the same on every platform and in every release, e.g. for cache keys or to find duplicate series across services.
+    ///
+    /// # Comments
+    /// It depends on the cash flows but not their order, so series that are equal up to order have the same fingerprint, and amounts are compared as `f64`, with `-0.0` the same as `0.0` and every NaN the same as every other.
+    ///
+    /// Each cash flow is encoded as 17 bytes or more, with integers big-endian:
+    /// - the period as a `u64`
+    /// - the tag as a byte: 0 for none, then 1 to 7 for `Contribution`, `Distribution`, `Fee`, `Income`, `Principal`, `Tax` and `Transfer`, or 8 for `Custom` followed by the length of its name as a `u64` and the name in UTF-8
+    /// - the amount as the bits of an `f64`, with `-0.0` encoded as `0.0` and every NaN as `0x7ff8000000000000`
+    ///
+    /// The fingerprint is the 128-bit FNV-1a hash of the byte 1, the version of this encoding, followed by the encoded cash flows sorted in lexicographic byte order.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+    ///
+    /// let series: CashFlowSeries<f64> =
+    ///     vec![CashFlow::new(0, -100.0), CashFlow::new(1, 110.0).with_tag(Tag::Distribution)]
+    ///         .into_iter()
+    ///         .collect();
+    /// let reordered: CashFlowSeries<f64> =
+    ///     vec![CashFlow::new(1, 110.0).with_tag(Tag::Distribution), CashFlow::new(0, -100.0)]
+    ///         .into_iter()
+    ///         .collect();
+    ///
+    /// assert_eq!(series.fingerprint(), reordered.fingerprint());
+    /// assert_ne!(series.fingerprint(), series.filter(|cash_flow| cash_flow.period() == 0).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u128 {
+        let mut records: Vec<Vec<u8>> = self
+            .cash_flows
+            .iter()
+            .map(|cash_flow| {
+                let mut record: Vec<u8> = (cash_flow.period as u64).to_be_bytes().to_vec();
+                match &cash_flow.tag {
+                    None => record.push(0),
+                    Some(Tag::Contribution) => record.push(1),
+                    Some(Tag::Distribution) => record.push(2),
+                    Some(Tag::Fee) => record.push(3),
+                    Some(Tag::Income) => record.push(4),
+                    Some(Tag::Principal) => record.push(5),
+                    Some(Tag::Tax) => record.push(6),
+                    Some(Tag::Transfer) => record.push(7),
+                    Some(Tag::Custom(name)) => {
+                        record.push(8);
+                        record.extend_from_slice(&(name.len() as u64).to_be_bytes());
+                        record.extend_from_slice(name.as_bytes());
+                    }
+                }
+                let amount: f64 = cash_flow.amount.to_f64().unwrap_or(f64::NAN);
+                let bits: u64 = if amount.is_nan() {
+                    CANONICAL_NAN_BITS
+                } else if amount == 0.0 {
+                    0
+                } else {
+                    amount.to_bits()
+                };
+                record.extend_from_slice(&bits.to_be_bytes());
+                record
+            })
+            .collect();
+        records.sort();
+
+        std::iter::once(&FINGERPRINT_VERSION)
+            .chain(records.iter().flatten())
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u128::from(*byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Splits the series into one series per tag, with untagged cash flows under `None`, each in the same order.
+    pub fn split_by_tag(&self) -> BTreeMap<Option<Tag>, CashFlowSeries<T>> {
+        let mut split: BTreeMap<Option<Tag>, CashFlowSeries<T>> = BTreeMap::new();
+        for cash_flow in self.cash_flows.iter() {
+            split
+                .entry(cash_flow.tag.clone())
+                .or_insert_with(CashFlowSeries::new)
+                .push(cash_flow.clone());
+        }
+        split
+    }
+}
+
+impl<T> std::iter::FromIterator<CashFlow<T>> for CashFlowSeries<T>
+where
+    T: Float,
+{
+    fn from_iter<I: IntoIterator<Item = CashFlow<T>>>(cash_flows: I) -> CashFlowSeries<T> {
+        CashFlowSeries {
+            cash_flows: cash_flows.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod cash_flow_series_tests {
+    use crate::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+    use crate::irr::bisection::functions::irr::bisection;
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let series: CashFlowSeries<f32> = CashFlowSeries::new();
+        assert!(series.is_empty());
+        assert!(series.by_period().is_empty());
+        assert_eq!(series.total_for(&Tag::Fee), 0.0);
+    }
+
+    #[test]
+    fn it_keeps_untagged_cash_flows_out_of_tag_totals() {
+        let series: CashFlowSeries<f64> = vec![
+            CashFlow::new(1, 10.0),
+            CashFlow::new(1, 5.0).with_tag(Tag::Custom(String::from("carry"))),
+            CashFlow::new(3, 7.0).with_tag(Tag::Custom(String::from("carry"))),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(series.by_period(), vec![0.0, 15.0, 0.0, 7.0]);
+        assert_eq!(series.total_for(&Tag::Custom(String::from("carry"))), 12.0);
+        assert_eq!(series.cash_flows()[0].tag(), None);
+    }
+
+    #[test]
+    fn it_splits_by_tag_in_order() {
+        let series: CashFlowSeries<f64> = vec![
+            CashFlow::new(3, 30.0).with_tag(Tag::Distribution),
+            CashFlow::new(0, -100.0),
+            CashFlow::new(1, 10.0).with_tag(Tag::Distribution),
+            CashFlow::new(1, -1.0).with_tag(Tag::Fee),
+        ]
+        .into_iter()
+        .collect();
+        let split = series.split_by_tag();
+
+        assert_eq!(split.len(), 3);
+        let periods: Vec<usize> = split[&Some(Tag::Distribution)]
+            .cash_flows()
+            .iter()
+            .map(|cash_flow| cash_flow.period())
+            .collect();
+        assert_eq!(periods, vec![3, 1]);
+        assert_eq!(split[&None].by_period(), vec![-100.0]);
+        assert_eq!(split[&Some(Tag::Fee)].by_period(), vec![0.0, -1.0]);
+    }
+
+    #[test]
+    fn it_filters_by_predicate() {
+        let series: CashFlowSeries<f32> = vec![
+            CashFlow::new(0, -100.0).with_tag(Tag::Contribution),
+            CashFlow::new(1, 20.0).with_tag(Tag::Distribution),
+            CashFlow::new(2, 90.0).with_tag(Tag::Distribution),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(series.filter(|_| true), series);
+        assert!(series.filter(|_| false).is_empty());
+        assert_eq!(
+            series
+                .filter(|cash_flow| 0.0 < cash_flow.amount())
+                .by_period(),
+            vec![0.0, 20.0, 90.0]
+        );
+    }
+
+    #[test]
+    fn it_nets_and_accumulates_lazily() {
+        let series: CashFlowSeries<f32> = vec![
+            CashFlow::new(2, 5.0),
+            CashFlow::new(0, -10.0),
+            CashFlow::new(2, -1.0),
+            CashFlow::new(4, 8.0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            series.net_by_period().collect::<Vec<f32>>(),
+            vec![-10.0, 0.0, 4.0, 0.0, 8.0]
+        );
+        assert_eq!(
+            series.cumulative().collect::<Vec<f32>>(),
+            vec![-10.0, -10.0, -6.0, -6.0, 2.0]
+        );
+        assert_eq!(series.cumulative().take(2).count(), 2);
+        assert_eq!(CashFlowSeries::<f32>::new().cumulative().count(), 0);
+    }
+
+    #[test]
+    fn it_compares_periods_and_tags_exactly() {
+        let series: CashFlowSeries<f64> = vec![CashFlow::new(1, 10.0).with_tag(Tag::Fee)]
+            .into_iter()
+            .collect();
+        let other_period: CashFlowSeries<f64> = vec![CashFlow::new(2, 10.0).with_tag(Tag::Fee)]
+            .into_iter()
+            .collect();
+        let untagged: CashFlowSeries<f64> = vec![CashFlow::new(1, 10.0)].into_iter().collect();
+
+        assert!(series.approx_eq(&series, 0.0));
+        assert!(!series.approx_eq(&other_period, 1.0));
+        assert!(!series.approx_eq(&untagged, 1.0));
+        assert!(!series.approx_eq(&CashFlowSeries::new(), 1.0));
+    }
+
+    #[test]
+    fn it_solves_by_period() {
+        let series: CashFlowSeries<f64> = vec![
+            CashFlow::new(2, 60.0).with_tag(Tag::Distribution),
+            CashFlow::new(0, -100.0).with_tag(Tag::Contribution),
+            CashFlow::new(1, 60.0).with_tag(Tag::Distribution),
+        ]
+        .into_iter()
+        .collect();
+        let cash_flows: Vec<f64> = series.by_period();
+
+        assert!(bisection(cash_flows.iter(), 0.0, 0.5, 100).is_valid());
+    }
+}
+
+#[cfg(test)]
+mod fingerprint_tests {
+    use crate::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+
+    fn series<T: num::Float>(amounts: &[T]) -> CashFlowSeries<T> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(period, amount)| CashFlow::new(period, *amount))
+            .collect()
+    }
+
+    #[test]
+    fn it_is_stable() {
+        let series: CashFlowSeries<f64> = vec![
+            CashFlow::new(0, -100.0),
+            CashFlow::new(1, 110.0).with_tag(Tag::Distribution),
+            CashFlow::new(2, 5.5).with_tag(Tag::Custom(String::from("carry"))),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            series.fingerprint(),
+            0x58ef_e81e_7dcf_053b_1d69_2a48_b985_84c9
+        );
+        assert_eq!(
+            CashFlowSeries::<f32>::new().fingerprint(),
+            0xd228_cb69_0f1a_8caf_7891_2b70_4e4a_1344
+        );
+    }
+
+    #[test]
+    fn it_treats_signed_zeros_and_nans_as_equal() {
+        assert_eq!(
+            series(&[0.0, 1.0]).fingerprint(),
+            series(&[-0.0, 1.0]).fingerprint()
+        );
+        assert_eq!(
+            series(&[f64::NAN]).fingerprint(),
+            series(&[-f64::NAN]).fingerprint()
+        );
+        assert_eq!(
+            series(&[f32::NAN, 1.0]).fingerprint(),
+            series(&[f64::NAN, 1.0]).fingerprint()
+        );
+    }
+
+    #[test]
+    fn it_distinguishes_periods_tags_and_amounts() {
+        let base: CashFlowSeries<f64> = series(&[-100.0, 110.0]);
+        let moved: CashFlowSeries<f64> = vec![CashFlow::new(0, -100.0), CashFlow::new(2, 110.0)]
+            .into_iter()
+            .collect();
+        let tagged: CashFlowSeries<f64> = vec![
+            CashFlow::new(0, -100.0),
+            CashFlow::new(1, 110.0).with_tag(Tag::Income),
+        ]
+        .into_iter()
+        .collect();
+        let fingerprints: Vec<u128> = vec![
+            base.fingerprint(),
+            moved.fingerprint(),
+            tagged.fingerprint(),
+            series(&[-100.0, 110.000_000_1]).fingerprint(),
+            series(&[-100.0, 110.0, 0.0]).fingerprint(),
+        ];
+        for (index, fingerprint) in fingerprints.iter().enumerate() {
+            assert!(!fingerprints[index + 1..].contains(fingerprint));
+        }
+    }
+}
@@ -0,0 +1,460 @@
+//! Functions for inspecting series of cash flows before valuing them.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::power::powi;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+use crate::rate::{Frequency, FrequencyError};
+
+/// The IRR above which a series with a single sign change is flagged as implausible: 10,000%.
+pub const IMPLAUSIBLE_IRR: f32 = 100.0;
+
+/// A warning about a series of cash flows that is likely to be a data error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lint {
+    /// There are no outflows, so there is no IRR.
+    NoNegativeCashFlows,
+    /// There are no inflows, so there is no IRR.
+    NoPositiveCashFlows,
+    /// The series changes sign once, so it has a single IRR, but that IRR exceeds `IMPLAUSIBLE_IRR`.
+    ImplausibleIrr,
+    /// More than the allowed number of consecutive periods, starting at `start`, have no cash flow.
+    Gap { start: usize, periods: usize },
+}
+
+/// Checks a series of cash flows for signs of data errors.
+///
+/// # Comments
+/// Gaps are runs of zero cash flows between the first and last non-zero cash flows that are longer than `max_gap` periods.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flows::{lint, Lint};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 0.0, 0.0, 0.0, 150.0];
+/// assert_eq!(lint(cash_flows.iter(), 2), vec![Lint::Gap { start: 1, periods: 3 }]);
+///
+/// let cash_flows: Vec<f64> = vec![-1.0, 1_000.0];
+/// assert_eq!(lint(cash_flows.iter(), 2), vec![Lint::ImplausibleIrr]);
+/// ```
+pub fn lint<T>(cash_flows: Iter<T>, max_gap: usize) -> Vec<Lint>
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let mut lints: Vec<Lint> = vec![];
+
+    let non_zero: Vec<(usize, T)> = cash_flows
+        .clone()
+        .enumerate()
+        .filter(|(_, cash_flow)| !cash_flow.is_zero())
+        .map(|(period, cash_flow)| (period, *cash_flow))
+        .collect();
+
+    if !non_zero.iter().any(|(_, cash_flow)| *cash_flow < T::zero()) {
+        lints.push(Lint::NoNegativeCashFlows);
+    }
+    if !non_zero.iter().any(|(_, cash_flow)| T::zero() < *cash_flow) {
+        lints.push(Lint::NoPositiveCashFlows);
+    }
+
+    if sign_changes(cash_flows.clone()) == 1 {
+        // with a single sign change the NPV keeps the sign of the first cash flow above the IRR
+        let npv: T = pv(cash_flows, T::from(IMPLAUSIBLE_IRR).unwrap());
+        if npv.signum() != non_zero[0].1.signum() {
+            lints.push(Lint::ImplausibleIrr);
+        }
+    }
+
+    for pair in non_zero.windows(2) {
+        let periods: usize = pair[1].0 - pair[0].0 - 1;
+        if max_gap < periods {
+            lints.push(Lint::Gap {
+                start: pair[0].0 + 1,
+                periods,
+            });
+        }
+    }
+
+    lints
+}
+
+/// Counts the sign changes in a series of cash flows, ignoring zero cash flows.
+///
+/// # Comments
+/// By Descartes' rule of signs the series has at most this many IRRs.
+///
+/// # Example with f32
+/// ```
+/// use time_value::cash_flows::sign_changes;
+///
+/// let cash_flows: Vec<f32> = vec![-100.0, 230.0, 0.0, -132.0];
+/// assert_eq!(sign_changes(cash_flows.iter()), 2);
+/// ```
+pub fn sign_changes<T>(cash_flows: Iter<T>) -> usize
+where
+    T: Float,
+{
+    let non_zero: Vec<T> = cash_flows
+        .filter(|cash_flow| !cash_flow.is_zero())
+        .copied()
+        .collect();
+    non_zero
+        .windows(2)
+        .filter(|pair| pair[0].signum() != pair[1].signum())
+        .count()
+}
+
+/// The power of ten nearest below the largest cash flow in absolute value, by which a series can be divided so that its amounts are of order one.
+///
+/// # Comments
+/// Rescaling a series does not change its IRR, but with `f32` the NPVs of large notionals cannot be calculated to within a tolerance of cents, so a solver may not converge without it.
+/// The scale is one if there are no non-zero finite cash flows.
+/// The scale is an integer power of ten, so dividing by it only changes the exponents of the cash flows.
+///
+/// # Example with f32
+/// ```
+/// use time_value::cash_flows::scale;
+///
+/// let cash_flows: Vec<f32> = vec![-250_000_000.0, 60_000_000.0, 300_000_000.0];
+/// assert_eq!(scale(cash_flows.iter()), 100_000_000.0);
+/// ```
+pub fn scale<T>(cash_flows: Iter<T>) -> T
+where
+    T: Float,
+{
+    let largest: T = cash_flows
+        .map(|cash_flow| cash_flow.abs())
+        .filter(|amount| amount.is_finite())
+        .fold(T::zero(), T::max);
+    if largest.is_zero() {
+        return T::one();
+    }
+    let ten: T = T::from(10.0).unwrap();
+    let exponent: i32 = largest.log10().floor().to_i32().unwrap();
+    // the logarithm may round across a power of ten
+    let scale: T = powi(ten, exponent);
+    if largest < scale {
+        powi(ten, exponent - 1)
+    } else if ten * scale <= largest {
+        powi(ten, exponent + 1)
+    } else {
+        scale
+    }
+}
+
+/// Whether `normalize` keeps or trims the zero cash flows at the end of a series.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingZeros {
+    Keep,
+    Trim,
+}
+
+/// A series of cash flows after `normalize`, and what was removed from it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normalized<'a, T>
+where
+    T: Float,
+{
+    cash_flows: &'a [T],
+    trimmed: usize,
+}
+
+impl<'a, T> Normalized<'a, T>
+where
+    T: Float,
+{
+    pub fn cash_flows(&self) -> &'a [T] {
+        self.cash_flows
+    }
+
+    /// The number of zero cash flows removed from the end of the series.
+    pub fn trimmed(&self) -> usize {
+        self.trimmed
+    }
+
+    /// Whether the series was changed.
+    pub fn is_changed(&self) -> bool {
+        0 < self.trimmed
+    }
+}
+
+/// Normalizes a series of cash flows before valuing it, reporting what was changed.
+///
+/// # Comments
+/// Trailing zeros do not change the NPV or the IRR, but they add terms to every NPV evaluation and periods to breakdowns by period.
+/// Trimming them keeps the period of every other cash flow, and a series of only zeros is trimmed to an empty series.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flows::{normalize, Normalized, TrailingZeros};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0, 0.0, 0.0];
+/// let normalized: Normalized<f64> = normalize(&cash_flows, TrailingZeros::Trim);
+/// assert_eq!(normalized.cash_flows(), &[-100.0, 60.0, 60.0]);
+/// assert_eq!(normalized.trimmed(), 2);
+///
+/// assert!(!normalize(&cash_flows, TrailingZeros::Keep).is_changed());
+/// ```
+pub fn normalize<T>(cash_flows: &[T], trailing_zeros: TrailingZeros) -> Normalized<'_, T>
+where
+    T: Float,
+{
+    let kept: usize = match trailing_zeros {
+        TrailingZeros::Keep => cash_flows.len(),
+        TrailingZeros::Trim => cash_flows
+            .iter()
+            .rposition(|cash_flow| !cash_flow.is_zero())
+            .map_or(0, |last| last + 1),
+    };
+    Normalized {
+        cash_flows: &cash_flows[..kept],
+        trimmed: cash_flows.len() - kept,
+    }
+}
+
+/// A series of cash flows after `aggregate`, and how much of its last period was covered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aggregated<T>
+where
+    T: Float,
+{
+    cash_flows: Vec<T>,
+    partial_periods: usize,
+}
+
+impl<T> Aggregated<T>
+where
+    T: Float,
+{
+    pub fn cash_flows(&self) -> &[T] {
+        &self.cash_flows
+    }
+
+    /// The number of periods of the original series in the last aggregated period, if they are fewer than a whole period, and otherwise 0.
+    pub fn partial_periods(&self) -> usize {
+        self.partial_periods
+    }
+
+    /// Whether every aggregated period is made up of whole periods of the original series.
+    pub fn is_exact(&self) -> bool {
+        self.partial_periods == 0
+    }
+
+    /// The aggregated cash flows if every period is whole, and otherwise an error with the number of periods in the partial last one.
+    pub fn into_exact(self) -> Result<Vec<T>, FrequencyError> {
+        if self.is_exact() {
+            Ok(self.cash_flows)
+        } else {
+            Err(FrequencyError::PartialPeriod(self.partial_periods))
+        }
+    }
+}
+
+/// Aggregates a series of cash flows at periods of frequency `from` into periods of the coarser frequency `to`, e.g. monthly into quarterly.
+///
+/// # Comments
+/// The cash flow at period 0 stays at period 0 and every other cash flow is added to the period of `to` in which its period ends, i.e. moved to the end of it without interest.
+/// A period of `to` must be a whole number of periods of `from`; see `Frequency::periods_in`.
+/// If the series ends part of the way through a period of `to`, the last aggregated cash flow covers fewer periods, which `partial_periods` reports and `into_exact` rejects.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flows::{aggregate, Aggregated};
+/// use time_value::rate::{Frequency, FrequencyError};
+///
+/// let monthly: Vec<f64> = vec![-1_000.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 1_010.0];
+/// let quarterly: Aggregated<f64> = aggregate(&monthly, Frequency::Monthly, Frequency::Quarterly).unwrap();
+/// assert_eq!(quarterly.cash_flows(), &[-1_000.0, 30.0, 30.0, 1_010.0]);
+/// assert_eq!(quarterly.partial_periods(), 1);
+/// assert_eq!(quarterly.into_exact(), Err(FrequencyError::PartialPeriod(1)));
+/// ```
+pub fn aggregate<T>(
+    cash_flows: &[T],
+    from: Frequency,
+    to: Frequency,
+) -> Result<Aggregated<T>, FrequencyError>
+where
+    T: Float,
+{
+    let periods: usize = from.periods_in(to)? as usize;
+    let mut aggregated: Vec<T> = vec![];
+    for (period, cash_flow) in cash_flows.iter().enumerate() {
+        let index: usize = period.div_ceil(periods);
+        if aggregated.len() <= index {
+            aggregated.push(T::zero());
+        }
+        aggregated[index] = aggregated[index] + *cash_flow;
+    }
+    Ok(Aggregated {
+        cash_flows: aggregated,
+        partial_periods: cash_flows.len().saturating_sub(1) % periods,
+    })
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use crate::cash_flows::{lint, Lint};
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f32> = vec![];
+        assert_eq!(
+            lint(cash_flows.iter(), 0),
+            vec![Lint::NoNegativeCashFlows, Lint::NoPositiveCashFlows]
+        );
+    }
+
+    #[test]
+    fn it_finds_no_lints_in_a_clean_series() {
+        let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+        assert!(lint(cash_flows.iter(), 0).is_empty());
+    }
+
+    #[test]
+    fn it_flags_one_sided_series() {
+        let inflows: Vec<f64> = vec![0.0, 10.0, 20.0];
+        let outflows: Vec<f64> = vec![-10.0, -20.0];
+        assert_eq!(lint(inflows.iter(), 5), vec![Lint::NoNegativeCashFlows]);
+        assert_eq!(lint(outflows.iter(), 5), vec![Lint::NoPositiveCashFlows]);
+    }
+
+    #[test]
+    fn it_flags_implausible_irrs_in_either_direction() {
+        let borrowing: Vec<f64> = vec![1.0, -1_000.0];
+        let plausible: Vec<f64> = vec![-100.0, 500.0];
+        assert_eq!(lint(borrowing.iter(), 0), vec![Lint::ImplausibleIrr]);
+        assert!(lint(plausible.iter(), 0).is_empty());
+    }
+
+    #[test]
+    fn it_ignores_leading_and_trailing_zeros() {
+        let cash_flows: Vec<f64> = vec![0.0, 0.0, 0.0, -100.0, 60.0, 60.0, 0.0, 0.0, 0.0];
+        assert!(lint(cash_flows.iter(), 1).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod sign_changes_tests {
+    use crate::cash_flows::sign_changes;
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f64> = vec![];
+        assert_eq!(sign_changes(cash_flows.iter()), 0);
+    }
+
+    #[test]
+    fn it_ignores_zeros() {
+        let cash_flows: Vec<f64> = vec![0.0, -10.0, 0.0, 0.0, 5.0, 5.0, 0.0];
+        assert_eq!(sign_changes(cash_flows.iter()), 1);
+    }
+}
+
+#[cfg(test)]
+mod normalize_tests {
+    use crate::cash_flows::{normalize, Normalized, TrailingZeros};
+    use crate::irr::bisection::functions::irr::bisection;
+
+    #[test]
+    fn it_does_not_change_the_irr() {
+        let cash_flows: Vec<f64> = vec![-100.0, 0.0, 60.0, 60.0, 0.0, 0.0, 0.0];
+        let normalized: Normalized<f64> = normalize(&cash_flows, TrailingZeros::Trim);
+
+        assert_eq!(normalized.cash_flows().len(), 4);
+        assert!(bisection(normalized.cash_flows().iter(), 0.0, 0.5, 100)
+            .approx_eq(&bisection(cash_flows.iter(), 0.0, 0.5, 100), 0.000_001));
+    }
+
+    #[test]
+    fn it_trims_a_series_of_zeros_to_nothing() {
+        let cash_flows: Vec<f32> = vec![0.0, 0.0];
+        let normalized: Normalized<f32> = normalize(&cash_flows, TrailingZeros::Trim);
+        assert!(normalized.cash_flows().is_empty());
+        assert_eq!(normalized.trimmed(), 2);
+    }
+
+    #[test]
+    fn it_leaves_a_series_without_trailing_zeros_unchanged() {
+        let cash_flows: Vec<f64> = vec![0.0, -100.0, 110.0];
+        assert!(!normalize(&cash_flows, TrailingZeros::Trim).is_changed());
+        assert!(!normalize(&Vec::<f64>::new(), TrailingZeros::Trim).is_changed());
+    }
+}
+
+#[cfg(test)]
+mod scale_tests {
+    use crate::cash_flows::scale;
+
+    #[test]
+    fn it_is_one_without_cash_flows() {
+        let cash_flows: Vec<f64> = vec![0.0, f64::NAN];
+        assert_eq!(scale(cash_flows.iter()), 1.0);
+        assert_eq!(scale(Vec::<f32>::new().iter()), 1.0);
+    }
+
+    #[test]
+    fn it_works_with_small_amounts() {
+        let cash_flows: Vec<f64> = vec![-0.004, 0.003];
+        assert!((scale(cash_flows.iter()) - 0.001).abs() < 1e-15);
+    }
+
+    #[test]
+    fn it_is_the_power_of_ten_at_or_below_the_largest_amount() {
+        for exponent in -20..20 {
+            let power: f64 = 10.0_f64.powi(exponent);
+            assert_eq!(scale([power].iter()), power);
+            assert_eq!(scale([-9.99 * power].iter()), power);
+        }
+        assert_eq!(scale([1_000.0_f32, 999.0].iter()), 1_000.0);
+        assert_eq!(scale([999.0_f32].iter()), 100.0);
+    }
+}
+
+#[cfg(test)]
+mod aggregate_tests {
+    use crate::cash_flows::aggregate;
+    use crate::rate::{Frequency, FrequencyError};
+
+    #[test]
+    fn it_aggregates_whole_periods_exactly() {
+        let monthly: Vec<f64> = (0..25).map(|month| month as f64).collect();
+        let annual: Vec<f64> = aggregate(&monthly, Frequency::Monthly, Frequency::Annual)
+            .unwrap()
+            .into_exact()
+            .unwrap();
+        assert_eq!(annual, vec![0.0, 78.0, 222.0]);
+        assert_eq!(annual.iter().sum::<f64>(), monthly.iter().sum::<f64>());
+    }
+
+    #[test]
+    fn it_keeps_a_series_at_the_same_frequency() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let aggregated = aggregate(&cash_flows, Frequency::Weekly, Frequency::Weekly).unwrap();
+        assert_eq!(aggregated.cash_flows(), &cash_flows[..]);
+        assert!(aggregated.is_exact());
+        assert!(
+            aggregate(&[] as &[f32], Frequency::Daily, Frequency::Annual)
+                .unwrap()
+                .cash_flows()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn it_rejects_frequencies_that_do_not_divide() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        assert_eq!(
+            aggregate(&cash_flows, Frequency::Weekly, Frequency::Monthly),
+            Err(FrequencyError::NotAMultiple(
+                Frequency::Weekly,
+                Frequency::Monthly
+            ))
+        );
+        assert_eq!(
+            aggregate(&cash_flows, Frequency::Continuous, Frequency::Annual),
+            Err(FrequencyError::Continuous)
+        );
+    }
+}
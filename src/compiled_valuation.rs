@@ -0,0 +1,152 @@
+//! A struct for evaluating the same series of cash flows at many discount rates.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::functions::irr::bisection_with_npv;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::power::powi;
+
+/// A series of cash flows prepared once for cheap repeated valuation.
+///
+/// # Comments
+/// Compiling drops zero cash flows, sorts the remaining ones by period and merges cash flows sharing a period.
+/// Each valuation then only discounts the non-zero cash flows, stepping the discount factor from one period to the next instead of raising the discount to every period from scratch.
+///
+/// # Example with f64
+/// ```
+/// use time_value::compiled_valuation::CompiledValuation;
+/// use time_value::present_value::from_cash_flows_and_discount_rate as npv;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 0.0, 0.0, 0.0, 0.0, 150.0];
+/// let compiled: CompiledValuation<f64> = CompiledValuation::from_cash_flows(cash_flows.iter());
+/// assert_eq!(compiled.len(), 2);
+///
+/// let rates: Vec<f64> = vec![0.0, 0.05, 0.10];
+/// for (rate, value) in rates.iter().zip(compiled.npvs(rates.iter())) {
+///     assert!((value - npv(cash_flows.iter(), *rate)).abs() < 0.000_000_001);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledValuation<T>
+where
+    T: Float,
+{
+    cash_flows: Vec<(u32, T)>,
+}
+
+impl<T> CompiledValuation<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn from_cash_flows(cash_flows: Iter<T>) -> CompiledValuation<T> {
+        let sparse: Vec<(u32, T)> = cash_flows
+            .enumerate()
+            .map(|(period, cash_flow)| (period as u32, *cash_flow))
+            .collect();
+        CompiledValuation::from_sparse_cash_flows(sparse.iter())
+    }
+
+    pub fn from_sparse_cash_flows(cash_flows: Iter<(u32, T)>) -> CompiledValuation<T> {
+        let mut sorted: Vec<(u32, T)> = cash_flows.copied().collect();
+        sorted.sort_by_key(|(period, _)| *period);
+
+        let mut compiled: Vec<(u32, T)> = Vec::with_capacity(sorted.len());
+        for (period, cash_flow) in sorted {
+            match compiled.last_mut() {
+                Some((last_period, last_cash_flow)) if *last_period == period => {
+                    *last_cash_flow = *last_cash_flow + cash_flow;
+                }
+                _ => compiled.push((period, cash_flow)),
+            }
+        }
+        compiled.retain(|(_, cash_flow)| !cash_flow.is_zero());
+
+        CompiledValuation {
+            cash_flows: compiled,
+        }
+    }
+
+    /// The number of non-zero cash flows that are discounted per valuation.
+    pub fn len(&self) -> usize {
+        self.cash_flows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cash_flows.is_empty()
+    }
+
+    pub fn npv(&self, discount_rate: T) -> T {
+        let discount: T = T::one() + discount_rate;
+        let mut discount_factor: T = T::one();
+        let mut previous_period: u32 = 0;
+        let mut npv: T = T::zero();
+        for (period, cash_flow) in self.cash_flows.iter() {
+            discount_factor =
+                discount_factor * powi(discount, -((period - previous_period) as i32));
+            previous_period = *period;
+            npv = npv + *cash_flow * discount_factor;
+        }
+        npv
+    }
+
+    pub fn npvs(&self, discount_rates: Iter<T>) -> Vec<T> {
+        discount_rates.map(|rate| self.npv(*rate)).collect()
+    }
+
+    /// Calculates the IRR with the bisection method; see `irr::bisection::functions::irr::bisection`.
+    pub fn bisection(&self, rate_low_guess: T, rate_high_guess: T, iteration_limit: u32) -> Irr<T> {
+        bisection_with_npv(
+            |rate| self.npv(rate),
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod compiled_valuation_tests {
+    use crate::compiled_valuation::CompiledValuation;
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::present_value::from_sparse_cash_flows_and_discount_rate as sparse_npv;
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f32> = vec![0.0, 0.0];
+        let compiled: CompiledValuation<f32> =
+            CompiledValuation::from_cash_flows(cash_flows.iter());
+        assert!(compiled.is_empty());
+        assert_eq!(compiled.npv(0.10), 0.0);
+    }
+
+    #[test]
+    fn it_merges_and_sorts_sparse_cash_flows() {
+        let cash_flows: Vec<(u32, f64)> =
+            vec![(7, 400.0), (0, -500.0), (3, 60.0), (3, 40.0), (5, 0.0)];
+        let compiled: CompiledValuation<f64> =
+            CompiledValuation::from_sparse_cash_flows(cash_flows.iter());
+        assert_eq!(compiled.len(), 3);
+        for rate in [-0.5, 0.0, 0.07, 1.0].iter() {
+            assert!(
+                (compiled.npv(*rate) - sparse_npv(cash_flows.iter(), *rate)).abs() < 0.000_000_001
+            );
+        }
+    }
+
+    #[test]
+    fn it_solves_like_bisection() {
+        let cash_flows: Vec<f64> = vec![
+            -100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,
+        ];
+        let compiled: CompiledValuation<f64> =
+            CompiledValuation::from_cash_flows(cash_flows.iter());
+        let compiled_irr: Irr<f64> = compiled.bisection(0.05, 0.18, 100);
+        let calculated_irr: Irr<f64> = bisection(cash_flows.iter(), 0.05, 0.18, 100);
+
+        assert!(compiled_irr.is_valid());
+        assert!((compiled_irr.irr() - calculated_irr.irr()).abs() < 0.000_000_001);
+    }
+}
@@ -0,0 +1,249 @@
+//! Scenario shocks to a curve of spot rates, where the rate at index `t` applies to period `t`, and the revaluation of cash flows under them, e.g. for IRRBB-style interest rate risk.
+//!
+//! The curve is the slice of spot rates taken by `present_value::discount_factors_from_spot_rates` and `duration::fisher_weil`.
+
+use num::Float;
+use std::iter::{Product, Sum};
+
+use crate::present_value::discount_factors_from_spot_rates;
+
+/// A change to a curve of spot rates.
+///
+/// # Comments
+/// The steepener and flattener combine a short shock, which decays as `exp(-t / decay)` with the period `t`, and a long shock, which grows as `1 - exp(-t / decay)`; `short` and `long` are their sizes at the short and long ends of the curve.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShockKind<T> {
+    /// Every rate moves by the same amount.
+    Parallel(T),
+    /// Short rates fall by up to `short` and long rates rise by up to `long`.
+    Steepener { short: T, long: T, decay: T },
+    /// Short rates rise by up to `short` and long rates fall by up to `long`.
+    Flattener { short: T, long: T, decay: T },
+    /// The rate of each period moves by the amount at its index; rates beyond the end of the amounts do not move.
+    Custom(Vec<T>),
+}
+
+impl<T> ShockKind<T>
+where
+    T: Float,
+{
+    /// The change in the spot rate of `period`.
+    pub fn shift(&self, period: usize) -> T {
+        let short_weight = |decay: T| (-T::from(period).unwrap() / decay).exp();
+        match self {
+            ShockKind::Parallel(shift) => *shift,
+            ShockKind::Steepener { short, long, decay } => {
+                let weight: T = short_weight(*decay);
+                -*short * weight + *long * (T::one() - weight)
+            }
+            ShockKind::Flattener { short, long, decay } => {
+                let weight: T = short_weight(*decay);
+                *short * weight - *long * (T::one() - weight)
+            }
+            ShockKind::Custom(shifts) => shifts.get(period).copied().unwrap_or_else(T::zero),
+        }
+    }
+}
+
+/// Applies a shock to a curve of spot rates.
+///
+/// # Example with f64
+/// ```
+/// use time_value::curve::{shock, ShockKind};
+///
+/// let spot_rates: Vec<f64> = vec![0.02, 0.025, 0.03, 0.035];
+/// let shocked: Vec<f64> = shock(&spot_rates, &ShockKind::Parallel(0.01));
+/// assert!((shocked[2] - 0.04).abs() < 1e-12);
+///
+/// // a steepener lowers the short end and raises the long end
+/// let steepened: Vec<f64> =
+///     shock(&spot_rates, &ShockKind::Steepener { short: 0.01, long: 0.01, decay: 1.0 });
+/// assert!(steepened[0] < spot_rates[0]);
+/// assert!(steepened[3] > spot_rates[3]);
+/// ```
+pub fn shock<T>(spot_rates: &[T], shock: &ShockKind<T>) -> Vec<T>
+where
+    T: Float,
+{
+    spot_rates
+        .iter()
+        .enumerate()
+        .map(|(period, spot_rate)| *spot_rate + shock.shift(period))
+        .collect()
+}
+
+/// The standard set of shocks of the Basel IRRBB framework that move the whole curve: parallel up and down by `parallel`, and the steepener and flattener.
+///
+/// # Comments
+/// As in the framework, the steepener combines 65% of the short shock `short` with 90% of the long shock `long`, and the flattener 80% of the short shock with 60% of the long shock.
+/// `decay` is the number of periods over which the short shock decays by a factor of `e`, e.g. 4 for annual periods or 16 for quarterly periods.
+///
+/// # Example with f64
+/// ```
+/// use time_value::curve::{standard_shocks, ShockKind};
+///
+/// let shocks: Vec<ShockKind<f64>> = standard_shocks(0.02, 0.025, 0.015, 4.0);
+/// assert_eq!(shocks.len(), 4);
+/// assert_eq!(shocks[1], ShockKind::Parallel(-0.02));
+/// ```
+pub fn standard_shocks<T>(parallel: T, short: T, long: T, decay: T) -> Vec<ShockKind<T>>
+where
+    T: Float,
+{
+    let share = |share: f64| T::from(share).unwrap();
+    vec![
+        ShockKind::Parallel(parallel),
+        ShockKind::Parallel(-parallel),
+        ShockKind::Steepener {
+            short: share(0.65) * short,
+            long: share(0.9) * long,
+            decay,
+        },
+        ShockKind::Flattener {
+            short: share(0.8) * short,
+            long: share(0.6) * long,
+            decay,
+        },
+    ]
+}
+
+/// The present value of cash flows under a shocked curve, and its change from the present value under the unshocked curve.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Revaluation<T> {
+    shock: ShockKind<T>,
+    present_value: T,
+    change: T,
+}
+
+impl<T> Revaluation<T>
+where
+    T: Float,
+{
+    pub fn shock(&self) -> &ShockKind<T> {
+        &self.shock
+    }
+
+    pub fn present_value(&self) -> T {
+        self.present_value
+    }
+
+    pub fn change(&self) -> T {
+        self.change
+    }
+}
+
+/// Revalues cash flows under each of `shocks` to a curve of spot rates, e.g. those of `standard_shocks`.
+///
+/// # Assumptions
+/// There is a spot rate for every cash flow; cash flows beyond the end of the curve are ignored, as by `duration::fisher_weil`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::curve::{revalue, standard_shocks, Revaluation};
+///
+/// let cash_flows: Vec<f64> = vec![0.0, 5.0, 5.0, 5.0, 5.0, 105.0];
+/// let spot_rates: Vec<f64> = vec![0.03, 0.03, 0.032, 0.034, 0.036, 0.038];
+/// let revaluations: Vec<Revaluation<f64>> =
+///     revalue(&cash_flows, &spot_rates, &standard_shocks(0.02, 0.025, 0.015, 4.0));
+///
+/// // a bond loses value when rates rise and gains when they fall
+/// assert!(revaluations[0].change() < 0.0);
+/// assert!(revaluations[1].change() > 0.0);
+/// let worst: f64 = revaluations.iter().map(|revaluation| revaluation.change()).fold(0.0, f64::min);
+/// assert_eq!(worst, revaluations[0].change());
+/// ```
+pub fn revalue<T>(
+    cash_flows: &[T],
+    spot_rates: &[T],
+    shocks: &[ShockKind<T>],
+) -> Vec<Revaluation<T>>
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let present_value = |spot_rates: &[T]| -> T {
+        cash_flows
+            .iter()
+            .zip(discount_factors_from_spot_rates(spot_rates.iter()))
+            .map(|(cash_flow, discount_factor)| *cash_flow * discount_factor)
+            .sum()
+    };
+    let unshocked: T = present_value(spot_rates);
+    shocks
+        .iter()
+        .map(|kind| {
+            let shocked: T = present_value(&shock(spot_rates, kind));
+            Revaluation {
+                shock: kind.clone(),
+                present_value: shocked,
+                change: shocked - unshocked,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod shock_tests {
+    use crate::curve::{shock, ShockKind};
+
+    #[test]
+    fn it_pivots_the_steepener_and_flattener() {
+        let spot_rates: Vec<f64> = vec![0.03; 40];
+        let steepener: ShockKind<f64> = ShockKind::Steepener {
+            short: 0.01,
+            long: 0.01,
+            decay: 4.0,
+        };
+        let flattener: ShockKind<f64> = ShockKind::Flattener {
+            short: 0.01,
+            long: 0.01,
+            decay: 4.0,
+        };
+        let steepened: Vec<f64> = shock(&spot_rates, &steepener);
+        let flattened: Vec<f64> = shock(&spot_rates, &flattener);
+
+        assert!((steepened[0] - 0.02).abs() < 1e-12);
+        assert!((flattened[0] - 0.04).abs() < 1e-12);
+        assert!((steepened[39] - 0.04).abs() < 0.000_1);
+        assert!((flattened[39] - 0.02).abs() < 0.000_1);
+        for period in 0..40 {
+            assert!((steepened[period] + flattened[period] - 0.06).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_leaves_rates_beyond_a_custom_shock() {
+        let spot_rates: Vec<f32> = vec![0.01, 0.02, 0.03];
+        let shocked: Vec<f32> = shock(&spot_rates, &ShockKind::Custom(vec![0.005, -0.005]));
+
+        assert_eq!(shocked, vec![0.015, 0.015, 0.03]);
+    }
+}
+
+#[cfg(test)]
+mod revalue_tests {
+    use crate::curve::{revalue, ShockKind};
+    use crate::duration::fisher_weil;
+
+    #[test]
+    fn it_approximates_the_change_with_duration() {
+        let cash_flows: Vec<f64> = vec![0.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 104.0];
+        let spot_rates: Vec<f64> = vec![0.04; 10];
+        let revaluations = revalue(&cash_flows, &spot_rates, &[ShockKind::Parallel(0.000_1)]);
+        let unshocked: f64 = revaluations[0].present_value() - revaluations[0].change();
+        let duration: f64 = fisher_weil(cash_flows.iter(), spot_rates.iter());
+
+        let approximation: f64 = -duration / 1.04 * unshocked * 0.000_1;
+        assert!((revaluations[0].change() - approximation).abs() < 0.001);
+    }
+
+    #[test]
+    fn it_does_not_change_the_value_without_a_shock() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let spot_rates: Vec<f64> = vec![0.01, 0.02, 0.03];
+        let revaluations = revalue(&cash_flows, &spot_rates, &[ShockKind::Custom(vec![])]);
+
+        assert_eq!(revaluations[0].change(), 0.0);
+    }
+}
@@ -0,0 +1,828 @@
+//! Cash flows on dates, and their bucketing into periodic series for the functions that take one amount per period.
+//!
+//! Dates are day numbers counted from the first of January of year 1 of the proleptic Gregorian calendar, which is day 1, as by `day_number` and chrono's `Datelike::num_days_from_ce`.
+//! They are naive civil dates: a day number names a day on the calendar with no time of day or time zone, so a cash flow falls on the same day wherever it is valued.
+//! A timestamp names an instant, whose date depends on where it is observed, so it is only converted with an explicit UTC offset or time zone; see `from_unix_seconds` and, with the `chrono` feature, `from_timestamp`.
+//! Periods are months, quarters, half years or years, each aligned to the start of a calendar year, e.g. the second quarter is April to June, or of a fiscal year; see `FiscalYear`.
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone};
+use num::Float;
+use std::convert::TryFrom;
+use std::fmt::{Display, Error, Formatter};
+
+use crate::calendar::CalendarError;
+use crate::rate::Frequency;
+
+/// The day number of 1970-01-01.
+const UNIX_EPOCH_DAY_NUMBER: i64 = 719_163;
+
+/// The number of seconds in a day, ignoring leap seconds as Unix time does.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The day number of a date in the proleptic Gregorian calendar, or `None` if there is no such date or its day number does not fit in an `i32`.
+///
+/// # Example
+/// ```
+/// use time_value::dated::{civil_date, day_number};
+///
+/// assert_eq!(day_number(1, 1, 1), Some(1));
+/// assert_eq!(day_number(2024, 2, 29), Some(738_945));
+/// assert_eq!(day_number(2023, 2, 29), None);
+/// assert_eq!(civil_date(738_945), (2024, 2, 29));
+/// ```
+pub fn day_number(year: i32, month: u32, day: u32) -> Option<i32> {
+    if !(1..=12).contains(&month) || day == 0 || days_in_month(year, month) < day {
+        return None;
+    }
+    // days from 1970-01-01, counting years from March so that the leap day ends a year
+    let year: i64 = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year.rem_euclid(400);
+    let month_from_march: i64 = (month as i64 + 9) % 12;
+    let day_of_year: i64 = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days: i64 = era * 146_097 + day_of_era - 719_468;
+    i32::try_from(days + UNIX_EPOCH_DAY_NUMBER).ok()
+}
+
+/// The year, month and day of a day number; the inverse of `day_number`.
+pub fn civil_date(day_number: i32) -> (i32, u32, u32) {
+    let days: i64 = day_number as i64 - UNIX_EPOCH_DAY_NUMBER + 719_468;
+    let era: i64 = days.div_euclid(146_097);
+    let day_of_era: i64 = days.rem_euclid(146_097);
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march: i64 = (5 * day_of_year + 2) / 153;
+    let day: i64 = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month: i64 = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year: i64 = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month as u32, day as u32)
+}
+
+/// The day number of the date at a Unix timestamp where the local time is `utc_offset_seconds` ahead of UTC, or `None` if it does not fit in an `i32`.
+///
+/// # Comments
+/// The offset is that of where the cash flow is booked, e.g. `-5 * 3_600` for New York in winter; an offset of zero gives the date in UTC.
+///
+/// # Example
+/// ```
+/// use time_value::dated::{day_number, from_unix_seconds};
+///
+/// // 2024-03-31 23:30 in UTC is already 2024-04-01 in Berlin, two hours ahead
+/// let booked: i64 = 1_711_927_800;
+/// assert_eq!(from_unix_seconds(booked, 0), day_number(2024, 3, 31));
+/// assert_eq!(from_unix_seconds(booked, 2 * 3_600), day_number(2024, 4, 1));
+/// ```
+pub fn from_unix_seconds(seconds: i64, utc_offset_seconds: i32) -> Option<i32> {
+    let local_seconds: i64 = seconds.checked_add(utc_offset_seconds as i64)?;
+    i32::try_from(local_seconds.div_euclid(SECONDS_PER_DAY) + UNIX_EPOCH_DAY_NUMBER).ok()
+}
+
+/// The day number of a date, behind the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub fn from_naive_date(date: NaiveDate) -> i32 {
+    date.num_days_from_ce()
+}
+
+/// The date of a day number, or `None` if it is outside chrono's range, behind the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub fn to_naive_date(day_number: i32) -> Option<NaiveDate> {
+    NaiveDate::from_num_days_from_ce_opt(day_number)
+}
+
+/// The day number of the date of a timestamp in its own time zone, behind the `chrono` feature.
+///
+/// # Comments
+/// A timestamp in UTC gives its date in UTC; convert it with `DateTime::with_timezone` to the zone where the cash flow is booked first.
+///
+/// # Example
+/// ```
+/// use chrono::{FixedOffset, TimeZone, Utc};
+/// use time_value::dated::{day_number, from_timestamp};
+///
+/// let booked = Utc.with_ymd_and_hms(2024, 3, 31, 23, 30, 0).unwrap();
+/// let berlin = FixedOffset::east_opt(2 * 3_600).unwrap();
+/// assert_eq!(Some(from_timestamp(&booked)), day_number(2024, 3, 31));
+/// assert_eq!(Some(from_timestamp(&booked.with_timezone(&berlin))), day_number(2024, 4, 1));
+/// ```
+#[cfg(feature = "chrono")]
+pub fn from_timestamp<Tz>(timestamp: &DateTime<Tz>) -> i32
+where
+    Tz: TimeZone,
+{
+    from_naive_date(timestamp.date_naive())
+}
+
+/// Converts `(date, amount)` pairs into the `(day number, amount)` pairs taken by `bucket` and `xirr`, behind the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub fn day_numbers<T>(cash_flows: &[(NaiveDate, T)]) -> Vec<(i32, T)>
+where
+    T: Copy,
+{
+    cash_flows
+        .iter()
+        .map(|(date, amount)| (from_naive_date(*date), *amount))
+        .collect()
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// What to put in a period with no cash flows between the first and last periods that have them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillPolicy {
+    /// An amount of zero.
+    ZeroFill,
+    /// The amount of the period before, e.g. for valuations rather than flows.
+    CarryForward,
+    /// No series; the first such period is reported as `BucketError::MissingPeriod`.
+    Error,
+}
+
+/// The reason dated cash flows cannot be bucketed into a periodic series.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BucketError {
+    /// There are no cash flows to fix the first period.
+    NoCashFlows,
+    /// Periods of this frequency are not a whole number of months.
+    UnsupportedFrequency(Frequency),
+    /// The period at this index has no cash flows, under `FillPolicy::Error`.
+    MissingPeriod(usize),
+    /// A period of the first frequency falls in more than one period of the second, as aligned to their fiscal years.
+    NotNested(Frequency, Frequency),
+}
+
+impl Display for BucketError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            BucketError::NoCashFlows => write!(f, "there are no cash flows to bucket"),
+            BucketError::UnsupportedFrequency(frequency) => {
+                write!(f, "a {} period is not a whole number of months", frequency)
+            }
+            BucketError::MissingPeriod(index) => {
+                write!(f, "the period at index {} has no cash flows", index)
+            }
+            BucketError::NotNested(finer, coarser) => write!(
+                f,
+                "a {} period falls in more than one {} period",
+                finer, coarser
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BucketError {}
+
+/// A fiscal year of twelve months starting in `start_month` (1 to 12), named by the calendar year in which it ends, e.g. FY2024 from October 2023 to September 2024.
+///
+/// # Example
+/// ```
+/// use time_value::calendar::CalendarError;
+/// use time_value::dated::FiscalYear;
+///
+/// assert_eq!(FiscalYear::new(10).unwrap().start_month(), 10);
+/// assert_eq!(FiscalYear::new(13), Err(CalendarError::InvalidMonth(13)));
+/// assert_eq!(FiscalYear::calendar(), FiscalYear::new(1).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiscalYear {
+    start_month: u32,
+}
+
+impl FiscalYear {
+    pub fn new(start_month: u32) -> Result<FiscalYear, CalendarError> {
+        if !(1..=12).contains(&start_month) {
+            return Err(CalendarError::InvalidMonth(start_month));
+        }
+        Ok(FiscalYear { start_month })
+    }
+
+    /// The calendar year, starting in January.
+    pub fn calendar() -> FiscalYear {
+        FiscalYear { start_month: 1 }
+    }
+
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+
+    pub fn is_calendar(&self) -> bool {
+        self.start_month == 1
+    }
+
+    /// The months from the start of the fiscal year named 0 to the start of the calendar month `month_index` months from January of year 0.
+    fn months_into(&self, month_index: i64) -> i64 {
+        let offset: i64 = self.start_month as i64 - 1;
+        month_index - offset + if self.is_calendar() { 0 } else { 12 }
+    }
+}
+
+impl Default for FiscalYear {
+    fn default() -> FiscalYear {
+        FiscalYear::calendar()
+    }
+}
+
+/// The year of a period and its number within the year, from 1, e.g. the second quarter of 2024 or of fiscal 2024.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeriodLabel {
+    year: i32,
+    period: u32,
+    frequency: Frequency,
+    fiscal_year: FiscalYear,
+}
+
+impl PeriodLabel {
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    pub fn frequency(&self) -> Frequency {
+        self.frequency
+    }
+
+    pub fn fiscal_year(&self) -> FiscalYear {
+        self.fiscal_year
+    }
+}
+
+impl Display for PeriodLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if !self.fiscal_year.is_calendar() {
+            write!(f, "FY")?;
+        }
+        match self.frequency {
+            Frequency::Annual => write!(f, "{}", self.year),
+            Frequency::SemiAnnual => write!(f, "{} H{}", self.year, self.period),
+            Frequency::Quarterly => write!(f, "{} Q{}", self.year, self.period),
+            _ => write!(f, "{} M{:02}", self.year, self.period),
+        }
+    }
+}
+
+/// One amount per period of `frequency` in years of `fiscal_year`, starting at the period labelled `first`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::dated::{bucket, day_number, FillPolicy, PeriodicSeries};
+/// use time_value::rate::Frequency;
+///
+/// let cash_flows: Vec<(i32, f64)> = vec![
+///     (day_number(2024, 2, 10).unwrap(), -100.0),
+///     (day_number(2024, 9, 30).unwrap(), 60.0),
+///     (day_number(2024, 8, 1).unwrap(), 5.0),
+/// ];
+/// let series: PeriodicSeries<f64> = bucket(&cash_flows, Frequency::Quarterly, FillPolicy::ZeroFill).unwrap();
+/// assert_eq!(series.amounts(), &[-100.0, 0.0, 65.0]);
+/// assert_eq!(series.label(2).to_string(), "2024 Q3");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeriodicSeries<T>
+where
+    T: Float,
+{
+    first: PeriodLabel,
+    amounts: Vec<T>,
+}
+
+impl<T> PeriodicSeries<T>
+where
+    T: Float,
+{
+    pub fn frequency(&self) -> Frequency {
+        self.first.frequency
+    }
+
+    pub fn fiscal_year(&self) -> FiscalYear {
+        self.first.fiscal_year
+    }
+
+    pub fn first(&self) -> PeriodLabel {
+        self.first
+    }
+
+    pub fn amounts(&self) -> &[T] {
+        &self.amounts
+    }
+
+    /// The label of the period at `index`, which may be past the end of the series.
+    pub fn label(&self, index: usize) -> PeriodLabel {
+        label(
+            period_key(&self.first) + index as i64,
+            self.frequency(),
+            self.fiscal_year(),
+        )
+    }
+
+    /// Sums the periods of this series into periods of `frequency` in years of `fiscal_year`, e.g. calendar months into fiscal quarters.
+    ///
+    /// # Comments
+    /// Each period of this series must fall within one period of the result, i.e. `frequency` is no finer and its periods start at the start of a period of this series.
+    /// Periods at either end of the result only sum the periods of this series that fall in them.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::dated::{bucket, day_number, FiscalYear, FillPolicy, PeriodicSeries};
+    /// use time_value::rate::Frequency;
+    ///
+    /// let cash_flows: Vec<(i32, f64)> = vec![
+    ///     (day_number(2023, 9, 30).unwrap(), 10.0),
+    ///     (day_number(2023, 10, 31).unwrap(), 20.0),
+    ///     (day_number(2024, 1, 31).unwrap(), 30.0),
+    /// ];
+    /// let monthly: PeriodicSeries<f64> = bucket(&cash_flows, Frequency::Monthly, FillPolicy::ZeroFill).unwrap();
+    /// let fiscal: PeriodicSeries<f64> = monthly.rebucket(Frequency::Quarterly, FiscalYear::new(10).unwrap()).unwrap();
+    /// assert_eq!(fiscal.amounts(), &[10.0, 20.0, 30.0]);
+    /// assert_eq!(fiscal.first().to_string(), "FY2023 Q4");
+    /// assert_eq!(fiscal.label(1).to_string(), "FY2024 Q1");
+    /// ```
+    pub fn rebucket(
+        &self,
+        frequency: Frequency,
+        fiscal_year: FiscalYear,
+    ) -> Result<PeriodicSeries<T>, BucketError> {
+        let months: i64 = months_per_period(frequency)?;
+        let own_months: i64 = months_per_period(self.frequency())?;
+        let own_start: i64 = fiscal_year.months_into(first_month(&self.first));
+        if months % own_months != 0 || own_start.rem_euclid(own_months) != 0 {
+            return Err(BucketError::NotNested(self.frequency(), frequency));
+        }
+        let keys: Vec<i64> = (0..self.amounts.len() as i64)
+            .map(|index| (own_start + index * own_months).div_euclid(months))
+            .collect();
+        let first: i64 = match keys.first() {
+            Some(first) => *first,
+            None => return Err(BucketError::NoCashFlows),
+        };
+        let mut amounts: Vec<T> = vec![T::zero(); (keys[keys.len() - 1] - first + 1) as usize];
+        for (key, amount) in keys.iter().zip(&self.amounts) {
+            amounts[(key - first) as usize] = amounts[(key - first) as usize] + *amount;
+        }
+        Ok(PeriodicSeries {
+            first: label(first, frequency, fiscal_year),
+            amounts,
+        })
+    }
+}
+
+fn months_per_period(frequency: Frequency) -> Result<i64, BucketError> {
+    match frequency {
+        Frequency::Annual => Ok(12),
+        Frequency::SemiAnnual => Ok(6),
+        Frequency::Quarterly => Ok(3),
+        Frequency::Monthly => Ok(1),
+        _ => Err(BucketError::UnsupportedFrequency(frequency)),
+    }
+}
+
+/// The number of the period of `label`, counting periods of its frequency from the start of the fiscal year named 0.
+fn period_key(label: &PeriodLabel) -> i64 {
+    let months: i64 = months_per_period(label.frequency).unwrap();
+    (label.year as i64 * 12 + (label.period as i64 - 1) * months) / months
+}
+
+/// The calendar month in which the period of `label` starts, counting months from January of year 0.
+fn first_month(label: &PeriodLabel) -> i64 {
+    let months: i64 = months_per_period(label.frequency).unwrap();
+    let offset: i64 = label.fiscal_year.start_month as i64 - 1;
+    period_key(label) * months + offset
+        - if label.fiscal_year.is_calendar() {
+            0
+        } else {
+            12
+        }
+}
+
+fn label(period_key: i64, frequency: Frequency, fiscal_year: FiscalYear) -> PeriodLabel {
+    let months: i64 = months_per_period(frequency).unwrap();
+    let month: i64 = period_key * months;
+    PeriodLabel {
+        year: month.div_euclid(12) as i32,
+        period: (month.rem_euclid(12) / months + 1) as u32,
+        frequency,
+        fiscal_year,
+    }
+}
+
+/// Sums `(day number, amount)` pairs into one amount per period of `frequency` in calendar years, from the period of the earliest cash flow to that of the latest.
+///
+/// # Comments
+/// A period whose cash flows sum to zero has an amount of zero whatever the policy; `policy` only decides the periods with no cash flows at all.
+/// The frequency must be annual, semi-annual, quarterly or monthly.
+///
+/// # Example with f64
+/// ```
+/// use time_value::dated::{bucket, day_number, BucketError, FillPolicy, PeriodicSeries};
+/// use time_value::rate::Frequency;
+///
+/// let valuations: Vec<(i32, f64)> = vec![
+///     (day_number(2024, 1, 31).unwrap(), 100.0),
+///     (day_number(2024, 3, 31).unwrap(), 104.0),
+/// ];
+/// let series: PeriodicSeries<f64> = bucket(&valuations, Frequency::Monthly, FillPolicy::CarryForward).unwrap();
+/// assert_eq!(series.amounts(), &[100.0, 100.0, 104.0]);
+/// assert_eq!(
+///     bucket(&valuations, Frequency::Monthly, FillPolicy::Error),
+///     Err(BucketError::MissingPeriod(1))
+/// );
+/// ```
+pub fn bucket<T>(
+    cash_flows: &[(i32, T)],
+    frequency: Frequency,
+    policy: FillPolicy,
+) -> Result<PeriodicSeries<T>, BucketError>
+where
+    T: Float,
+{
+    bucket_fiscal(cash_flows, frequency, FiscalYear::calendar(), policy)
+}
+
+/// `bucket` for periods of fiscal years, e.g. quarters of years starting in April.
+///
+/// # Example with f64
+/// ```
+/// use time_value::dated::{bucket_fiscal, day_number, FiscalYear, FillPolicy, PeriodicSeries};
+/// use time_value::rate::Frequency;
+///
+/// let cash_flows: Vec<(i32, f64)> = vec![
+///     (day_number(2024, 3, 31).unwrap(), -100.0),
+///     (day_number(2024, 4, 1).unwrap(), 40.0),
+///     (day_number(2025, 3, 31).unwrap(), 80.0),
+/// ];
+/// let fiscal_year: FiscalYear = FiscalYear::new(4).unwrap();
+/// let series: PeriodicSeries<f64> =
+///     bucket_fiscal(&cash_flows, Frequency::Annual, fiscal_year, FillPolicy::Error).unwrap();
+/// assert_eq!(series.amounts(), &[-100.0, 120.0]);
+/// assert_eq!(series.first().to_string(), "FY2024");
+/// assert_eq!(series.label(1).to_string(), "FY2025");
+/// ```
+pub fn bucket_fiscal<T>(
+    cash_flows: &[(i32, T)],
+    frequency: Frequency,
+    fiscal_year: FiscalYear,
+    policy: FillPolicy,
+) -> Result<PeriodicSeries<T>, BucketError>
+where
+    T: Float,
+{
+    let months: i64 = months_per_period(frequency)?;
+    let keys: Vec<i64> = cash_flows
+        .iter()
+        .map(|(day, _)| {
+            let (year, month, _) = civil_date(*day);
+            fiscal_year
+                .months_into(year as i64 * 12 + month as i64 - 1)
+                .div_euclid(months)
+        })
+        .collect();
+    let (first, last) = match (keys.iter().min(), keys.iter().max()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Err(BucketError::NoCashFlows),
+    };
+    let mut amounts: Vec<Option<T>> = vec![None; (last - first + 1) as usize];
+    for (key, (_, amount)) in keys.iter().zip(cash_flows) {
+        let slot: &mut Option<T> = &mut amounts[(key - first) as usize];
+        *slot = Some(slot.unwrap_or_else(T::zero) + *amount);
+    }
+    let mut filled: Vec<T> = Vec::with_capacity(amounts.len());
+    for (index, amount) in amounts.into_iter().enumerate() {
+        let amount: T = match (amount, policy) {
+            (Some(amount), _) => amount,
+            (None, FillPolicy::ZeroFill) => T::zero(),
+            // the first period always has a cash flow
+            (None, FillPolicy::CarryForward) => filled[index - 1],
+            (None, FillPolicy::Error) => return Err(BucketError::MissingPeriod(index)),
+        };
+        filled.push(amount);
+    }
+    Ok(PeriodicSeries {
+        first: label(first, frequency, fiscal_year),
+        amounts: filled,
+    })
+}
+
+#[cfg(test)]
+mod day_number_tests {
+    use crate::dated::{civil_date, day_number};
+
+    #[test]
+    fn it_matches_known_dates() {
+        assert_eq!(day_number(1970, 1, 1), Some(719_163));
+        assert_eq!(day_number(2000, 3, 1), Some(730_180));
+        assert_eq!(day_number(1900, 2, 29), None);
+        assert_eq!(day_number(2000, 2, 29), Some(730_179));
+        assert_eq!(day_number(2024, 13, 1), None);
+        assert_eq!(day_number(2024, 4, 0), None);
+    }
+
+    #[test]
+    fn it_round_trips() {
+        for day in (-800_000..=800_000).step_by(97) {
+            let (year, month, date) = civil_date(day);
+            assert_eq!(day_number(year, month, date), Some(day));
+        }
+    }
+
+    #[test]
+    fn it_counts_consecutive_days() {
+        // a spreadsheet's serial day numbers are offset by a constant after 1900-03-01
+        let serial =
+            |year: i32, month: u32, day: u32| day_number(year, month, day).unwrap() - 693_594;
+        assert_eq!(serial(2008, 1, 1), 39_448);
+        assert_eq!(serial(2009, 4, 1), 39_904);
+    }
+}
+
+#[cfg(test)]
+mod from_unix_seconds_tests {
+    use crate::dated::{day_number, from_unix_seconds};
+
+    #[test]
+    fn it_works_before_the_epoch() {
+        assert_eq!(from_unix_seconds(-1, 0), day_number(1969, 12, 31));
+        assert_eq!(from_unix_seconds(0, 0), day_number(1970, 1, 1));
+        assert_eq!(from_unix_seconds(0, -1), day_number(1969, 12, 31));
+    }
+
+    #[test]
+    fn it_shifts_late_bookings_by_the_offset() {
+        // 2024-01-15 03:00 in UTC is still 2024-01-14 in New York, five hours behind
+        let booked: i64 = 1_705_287_600;
+        assert_eq!(from_unix_seconds(booked, 0), day_number(2024, 1, 15));
+        assert_eq!(
+            from_unix_seconds(booked, -5 * 3_600),
+            day_number(2024, 1, 14)
+        );
+    }
+
+    #[test]
+    fn it_is_none_out_of_range() {
+        assert_eq!(from_unix_seconds(i64::MAX, 1), None);
+        assert_eq!(from_unix_seconds(i64::MAX, 0), None);
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod chrono_tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    use crate::dated::{
+        civil_date, day_number, day_numbers, from_naive_date, from_timestamp, from_unix_seconds,
+        to_naive_date,
+    };
+
+    #[test]
+    fn it_agrees_with_day_number() {
+        for day in (-700_000..=700_000).step_by(101) {
+            let date: NaiveDate = to_naive_date(day).unwrap();
+            let (year, month, day_of_month) = civil_date(day);
+            assert_eq!(from_naive_date(date), day);
+            assert_eq!(
+                NaiveDate::from_ymd_opt(year, month, day_of_month),
+                Some(date)
+            );
+        }
+        let date: NaiveDate = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(Some(from_naive_date(date)), day_number(2024, 2, 29));
+        assert_eq!(day_numbers(&[(date, 1.0)]), vec![(738_945, 1.0)]);
+    }
+
+    #[test]
+    fn it_agrees_with_from_unix_seconds() {
+        let offset: FixedOffset = FixedOffset::west_opt(8 * 3_600).unwrap();
+        for seconds in (-2_000_000_000_i64..=2_000_000_000).step_by(9_999_991) {
+            let timestamp = Utc.timestamp_opt(seconds, 0).unwrap();
+            assert_eq!(
+                Some(from_timestamp(&timestamp)),
+                from_unix_seconds(seconds, 0)
+            );
+            assert_eq!(
+                Some(from_timestamp(&timestamp.with_timezone(&offset))),
+                from_unix_seconds(seconds, -8 * 3_600)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod bucket_tests {
+    use crate::dated::{bucket, day_number, BucketError, FillPolicy, PeriodicSeries};
+    use crate::rate::Frequency;
+
+    fn cash_flows() -> Vec<(i32, f64)> {
+        vec![
+            (day_number(2023, 11, 30).unwrap(), -100.0),
+            (day_number(2024, 2, 1).unwrap(), 10.0),
+            (day_number(2024, 2, 29).unwrap(), -10.0),
+            (day_number(2024, 4, 15).unwrap(), 120.0),
+        ]
+    }
+
+    #[test]
+    fn it_fills_gaps_by_policy() {
+        let zero_filled: PeriodicSeries<f64> =
+            bucket(&cash_flows(), Frequency::Monthly, FillPolicy::ZeroFill).unwrap();
+        let carried: PeriodicSeries<f64> =
+            bucket(&cash_flows(), Frequency::Monthly, FillPolicy::CarryForward).unwrap();
+
+        assert_eq!(zero_filled.amounts(), &[-100.0, 0.0, 0.0, 0.0, 0.0, 120.0]);
+        // February has cash flows that sum to zero, so is not a gap
+        assert_eq!(
+            carried.amounts(),
+            &[-100.0, -100.0, -100.0, 0.0, 0.0, 120.0]
+        );
+        assert_eq!(
+            bucket(&cash_flows(), Frequency::Monthly, FillPolicy::Error),
+            Err(BucketError::MissingPeriod(1))
+        );
+    }
+
+    #[test]
+    fn it_aligns_periods_to_the_calendar_year() {
+        let series: PeriodicSeries<f64> =
+            bucket(&cash_flows(), Frequency::SemiAnnual, FillPolicy::Error).unwrap();
+
+        assert_eq!(series.amounts(), &[-100.0, 120.0]);
+        assert_eq!(series.first().year(), 2023);
+        assert_eq!(series.first().period(), 2);
+        assert_eq!(series.label(1).to_string(), "2024 H1");
+        assert_eq!(series.label(3).to_string(), "2025 H1");
+    }
+
+    #[test]
+    fn it_needs_cash_flows_and_whole_months() {
+        assert_eq!(
+            bucket(
+                &[] as &[(i32, f64)],
+                Frequency::Annual,
+                FillPolicy::ZeroFill
+            ),
+            Err(BucketError::NoCashFlows)
+        );
+        assert_eq!(
+            bucket(&cash_flows(), Frequency::Weekly, FillPolicy::ZeroFill),
+            Err(BucketError::UnsupportedFrequency(Frequency::Weekly))
+        );
+    }
+
+    #[test]
+    fn it_labels_months() {
+        let series: PeriodicSeries<f64> =
+            bucket(&cash_flows(), Frequency::Monthly, FillPolicy::ZeroFill).unwrap();
+        assert_eq!(series.first().to_string(), "2023 M11");
+        assert_eq!(series.label(5).to_string(), "2024 M04");
+    }
+}
+
+#[cfg(test)]
+mod bucket_fiscal_tests {
+    use crate::dated::{bucket, bucket_fiscal, day_number, FillPolicy, FiscalYear, PeriodicSeries};
+    use crate::rate::Frequency;
+
+    #[test]
+    fn it_matches_bucket_in_calendar_years() {
+        let cash_flows: Vec<(i32, f64)> = vec![
+            (day_number(2022, 12, 31).unwrap(), -50.0),
+            (day_number(2024, 5, 1).unwrap(), 70.0),
+        ];
+        assert_eq!(
+            bucket_fiscal(
+                &cash_flows,
+                Frequency::Quarterly,
+                FiscalYear::calendar(),
+                FillPolicy::ZeroFill
+            ),
+            bucket(&cash_flows, Frequency::Quarterly, FillPolicy::ZeroFill)
+        );
+    }
+
+    #[test]
+    fn it_names_fiscal_years_by_the_year_they_end() {
+        let fiscal_year: FiscalYear = FiscalYear::new(10).unwrap();
+        let cash_flows: Vec<(i32, f64)> = vec![
+            (day_number(2023, 9, 30).unwrap(), -100.0),
+            (day_number(2023, 10, 1).unwrap(), 10.0),
+            (day_number(2024, 7, 4).unwrap(), 20.0),
+        ];
+        let series: PeriodicSeries<f64> = bucket_fiscal(
+            &cash_flows,
+            Frequency::Quarterly,
+            fiscal_year,
+            FillPolicy::ZeroFill,
+        )
+        .unwrap();
+
+        assert_eq!(series.amounts(), &[-100.0, 10.0, 0.0, 0.0, 20.0]);
+        assert_eq!(series.fiscal_year(), fiscal_year);
+        assert_eq!(series.first().year(), 2023);
+        assert_eq!(series.first().period(), 4);
+        assert_eq!(series.label(1).to_string(), "FY2024 Q1");
+        assert_eq!(series.label(4).to_string(), "FY2024 Q4");
+    }
+
+    #[test]
+    fn it_works_before_year_1() {
+        let fiscal_year: FiscalYear = FiscalYear::new(7).unwrap();
+        let cash_flows: Vec<(i32, f64)> = vec![
+            (day_number(-1, 6, 30).unwrap(), 1.0),
+            (day_number(-1, 7, 1).unwrap(), 2.0),
+        ];
+        let series: PeriodicSeries<f64> = bucket_fiscal(
+            &cash_flows,
+            Frequency::Monthly,
+            fiscal_year,
+            FillPolicy::Error,
+        )
+        .unwrap();
+
+        assert_eq!(series.first().year(), -1);
+        assert_eq!(series.first().period(), 12);
+        assert_eq!(series.label(1).year(), 0);
+        assert_eq!(series.label(1).period(), 1);
+    }
+}
+
+#[cfg(test)]
+mod rebucket_tests {
+    use crate::dated::{bucket, bucket_fiscal, day_number, BucketError, FillPolicy, FiscalYear};
+    use crate::rate::Frequency;
+
+    fn cash_flows() -> Vec<(i32, f64)> {
+        (1..=30)
+            .map(|month: u32| {
+                let year: i32 = 2022 + (month as i32 - 1) / 12;
+                let day: i32 = day_number(year, (month - 1) % 12 + 1, 15).unwrap();
+                (day, month as f64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_matches_bucketing_the_dated_cash_flows() {
+        for start_month in 1..=12 {
+            let fiscal_year: FiscalYear = FiscalYear::new(start_month).unwrap();
+            let monthly = bucket(&cash_flows(), Frequency::Monthly, FillPolicy::Error).unwrap();
+            for frequency in [
+                Frequency::Quarterly,
+                Frequency::SemiAnnual,
+                Frequency::Annual,
+            ]
+            .iter()
+            {
+                assert_eq!(
+                    monthly.rebucket(*frequency, fiscal_year),
+                    bucket_fiscal(&cash_flows(), *frequency, fiscal_year, FillPolicy::Error)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_needs_nested_periods() {
+        let quarterly = bucket(&cash_flows(), Frequency::Quarterly, FillPolicy::Error).unwrap();
+
+        assert_eq!(
+            quarterly.rebucket(Frequency::Annual, FiscalYear::new(2).unwrap()),
+            Err(BucketError::NotNested(
+                Frequency::Quarterly,
+                Frequency::Annual
+            ))
+        );
+        assert_eq!(
+            quarterly.rebucket(Frequency::Monthly, FiscalYear::calendar()),
+            Err(BucketError::NotNested(
+                Frequency::Quarterly,
+                Frequency::Monthly
+            ))
+        );
+        assert!(quarterly
+            .rebucket(Frequency::Annual, FiscalYear::new(4).unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn it_rejects_months_outside_the_calendar() {
+        assert!(FiscalYear::new(0).is_err());
+        assert!(FiscalYear::new(13).is_err());
+    }
+}
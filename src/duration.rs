@@ -0,0 +1,117 @@
+//! Functions for measuring the sensitivity of the present value of a series of cash flows to interest rates.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::power::powi;
+
+/// Calculates the Fisher-Weil duration of a series of cash flows, discounted with a curve of spot rates where the rate at index `t` applies to period `t`.
+///
+/// # Assumptions
+/// There is a spot rate for every cash flow; cash flows beyond the end of the curve are ignored.
+///
+/// # Comments
+/// The duration is the present value weighted average period `sum(t * pv_t) / sum(pv_t)`, measured in periods.
+/// With a flat curve it equals the Macaulay duration.
+///
+/// # Example with f64
+/// ```
+/// use time_value::duration::fisher_weil;
+///
+/// // a zero coupon cash flow has a duration equal to its maturity, whatever the curve
+/// let cash_flows: Vec<f64> = vec![0.0, 0.0, 0.0, 100.0];
+/// let spot_rates: Vec<f64> = vec![0.01, 0.02, 0.03, 0.04];
+/// assert!((fisher_weil(cash_flows.iter(), spot_rates.iter()) - 3.0).abs() < 0.000_000_001);
+/// ```
+pub fn fisher_weil<T>(cash_flows: Iter<T>, spot_rates: Iter<T>) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let (weighted, present_value): (T, T) = cash_flows.zip(spot_rates).enumerate().fold(
+        (T::zero(), T::zero()),
+        |(weighted, present_value), (period, (cash_flow, spot_rate))| {
+            let pv: T = *cash_flow * powi(T::one() + *spot_rate, -(period as i32));
+            (weighted + T::from(period).unwrap() * pv, present_value + pv)
+        },
+    );
+    weighted / present_value
+}
+
+/// Calculates the Fisher-Weil convexity of a series of cash flows, discounted with a curve of spot rates where the rate at index `t` applies to period `t`.
+///
+/// # Assumptions
+/// There is a spot rate for every cash flow; cash flows beyond the end of the curve are ignored.
+///
+/// # Comments
+/// The convexity is `sum(t * (t + 1) * cash_flow_t * (1 + spot_rate_t)^(-t - 2)) / sum(pv_t)`, the second derivative of the present value with respect to a parallel shift of the curve relative to the present value, measured in periods squared.
+///
+/// # Example with f64
+/// ```
+/// use time_value::duration::fisher_weil_convexity;
+///
+/// let cash_flows: Vec<f64> = vec![0.0, 0.0, 121.0];
+/// let spot_rates: Vec<f64> = vec![0.10, 0.10, 0.10];
+/// assert!((fisher_weil_convexity(cash_flows.iter(), spot_rates.iter()) - 6.0 / 1.21).abs() < 0.000_000_001);
+/// ```
+pub fn fisher_weil_convexity<T>(cash_flows: Iter<T>, spot_rates: Iter<T>) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let (weighted, present_value): (T, T) = cash_flows.zip(spot_rates).enumerate().fold(
+        (T::zero(), T::zero()),
+        |(weighted, present_value), (period, (cash_flow, spot_rate))| {
+            let discount: T = T::one() + *spot_rate;
+            let pv: T = *cash_flow * powi(discount, -(period as i32));
+            let t: T = T::from(period).unwrap();
+            (
+                weighted + t * (t + T::one()) * pv / (discount * discount),
+                present_value + pv,
+            )
+        },
+    );
+    weighted / present_value
+}
+
+#[cfg(test)]
+mod fisher_weil_tests {
+    use crate::duration::fisher_weil;
+
+    #[test]
+    fn it_is_nan_without_value() {
+        let cash_flows: Vec<f64> = vec![];
+        let spot_rates: Vec<f64> = vec![0.05];
+        assert!(fisher_weil(cash_flows.iter(), spot_rates.iter()).is_nan());
+    }
+
+    #[test]
+    fn it_shortens_as_the_long_end_rises() {
+        let cash_flows: Vec<f64> = vec![0.0, 5.0, 5.0, 5.0, 105.0];
+        let flat: Vec<f64> = vec![0.05; 5];
+        let steep: Vec<f64> = vec![0.01, 0.02, 0.04, 0.06, 0.08];
+        assert!(
+            fisher_weil(cash_flows.iter(), steep.iter())
+                < fisher_weil(cash_flows.iter(), flat.iter())
+        );
+    }
+}
+
+#[cfg(test)]
+mod fisher_weil_convexity_tests {
+    use crate::duration::fisher_weil_convexity;
+    use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+    #[test]
+    fn it_matches_a_finite_difference_with_a_flat_curve() {
+        let cash_flows: Vec<f64> = vec![0.0, 5.0, 5.0, 5.0, 105.0];
+        let rate: f64 = 0.05;
+        let bump: f64 = 0.000_1;
+        let second_difference: f64 = (pv(cash_flows.iter(), rate + bump)
+            - 2.0 * pv(cash_flows.iter(), rate)
+            + pv(cash_flows.iter(), rate - bump))
+            / (bump * bump);
+        let spot_rates: Vec<f64> = vec![rate; 5];
+        let convexity: f64 = fisher_weil_convexity(cash_flows.iter(), spot_rates.iter());
+        assert!((convexity - second_difference / pv(cash_flows.iter(), rate)).abs() < 0.001);
+    }
+}
@@ -0,0 +1,83 @@
+//! Functions for exporting calculations as plot data.
+//!
+//! Each function returns `(x, y)` arrays of equal length, ready to be handed to a charting library.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// Produces the points of an NPV profile: the discount rates and the NPV of the cash flows at each rate.
+///
+/// # Example with f64
+/// ```
+/// use time_value::export::npv_profile_plot_points;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// let rates: Vec<f64> = vec![0.0, 0.10, 0.20];
+/// let (x, y): (Vec<f64>, Vec<f64>) = npv_profile_plot_points(cash_flows.iter(), rates.iter());
+/// assert_eq!(x, rates);
+/// assert_eq!(y[0], 20.0);
+/// assert!(y[1] > y[2]);
+/// ```
+pub fn npv_profile_plot_points<T>(cash_flows: Iter<T>, rates: Iter<T>) -> (Vec<T>, Vec<T>)
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    rates
+        .map(|rate| (*rate, pv(cash_flows.clone(), *rate)))
+        .unzip()
+}
+
+/// Produces the points of a future value growth path: the period and the value at the end of it, starting from the present value in period 0.
+///
+/// # Example with f32
+/// ```
+/// use time_value::export::fv_growth_plot_points;
+///
+/// let present_value: f32 = 10.0;
+/// let rates: Vec<f32> = vec![1.0, 2.0, 3.0];
+/// let (x, y): (Vec<f32>, Vec<f32>) = fv_growth_plot_points(present_value, rates.iter());
+/// assert_eq!(x, vec![0.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(y, vec![10.0, 20.0, 60.0, 240.0]);
+/// ```
+pub fn fv_growth_plot_points<T>(present_value: T, expected_rates: Iter<T>) -> (Vec<T>, Vec<T>)
+where
+    T: Float + Product<T>,
+{
+    let mut periods: Vec<T> = vec![T::zero()];
+    let mut values: Vec<T> = vec![present_value];
+    let mut value: T = present_value;
+    for (period, rate) in expected_rates.enumerate() {
+        value = value * (T::one() + *rate);
+        periods.push(T::from(period + 1).unwrap());
+        values.push(value);
+    }
+    (periods, values)
+}
+
+#[cfg(test)]
+mod fv_growth_plot_points_tests {
+    use crate::export::fv_growth_plot_points;
+    use crate::future_value::from_pv_and_expected_rates as fv;
+
+    #[test]
+    fn it_works_with_no_rates() {
+        let present_value: f64 = 10.0;
+        let rates: Vec<f64> = vec![];
+        let (x, y): (Vec<f64>, Vec<f64>) = fv_growth_plot_points(present_value, rates.iter());
+        assert_eq!(x, vec![0.0]);
+        assert_eq!(y, vec![present_value]);
+    }
+
+    #[test]
+    fn it_ends_at_the_future_value() {
+        let present_value: f64 = 10.0;
+        let rates: Vec<f64> = vec![0.02, 0.04, -0.20, 0.00, -0.08, 0.20, 0.03, -0.02];
+        let (x, y): (Vec<f64>, Vec<f64>) = fv_growth_plot_points(present_value, rates.iter());
+        assert_eq!(x.len(), rates.len() + 1);
+        assert_eq!(y.len(), rates.len() + 1);
+        assert_eq!(y[rates.len()], fv(present_value, rates.iter()));
+    }
+}
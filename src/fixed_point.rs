@@ -0,0 +1,135 @@
+//! Functions for calculating present and future values in fixed-point arithmetic.
+//!
+//! Amounts and rates are `i64` counts of `10^-scale` units e.g. with a scale of 4, `12_3400` is 12.34 and `500` is a rate of 5%.
+//! Every product and quotient is computed in `i128` and rounded half away from zero, so results are identical on every architecture.
+//!
+//! # Panics
+//! The functions panic if a result does not fit in an `i64`.
+
+use std::convert::TryFrom;
+use std::slice::Iter;
+
+/// The representation of 1 at `scale`.
+///
+/// # Example
+/// ```
+/// use time_value::fixed_point::one;
+///
+/// assert_eq!(one(4), 10_000);
+/// ```
+pub fn one(scale: u32) -> i64 {
+    10_i64.pow(scale)
+}
+
+/// Multiplies two fixed-point numbers of the same scale.
+///
+/// # Example
+/// ```
+/// use time_value::fixed_point::mul;
+///
+/// // 1.5 * 0.25 = 0.375, rounded to 0.38 at a scale of 2
+/// assert_eq!(mul(150, 25, 2), 38);
+/// ```
+pub fn mul(a: i64, b: i64, scale: u32) -> i64 {
+    round_div(i128::from(a) * i128::from(b), i128::from(one(scale)))
+}
+
+/// Divides two fixed-point numbers of the same scale.
+///
+/// # Example
+/// ```
+/// use time_value::fixed_point::div;
+///
+/// // 1 / 3 = 0.33 and -2 / 3 = -0.67 at a scale of 2
+/// assert_eq!(div(100, 300, 2), 33);
+/// assert_eq!(div(-200, 300, 2), -67);
+/// ```
+pub fn div(a: i64, b: i64, scale: u32) -> i64 {
+    round_div(i128::from(a) * i128::from(one(scale)), i128::from(b))
+}
+
+/// Calculates the present value of a series of cash flows, the first of which is undiscounted.
+///
+/// # Comments
+/// The cash flows are discounted backwards one period at a time, `pv_t = cash_flow_t + pv_(t+1) / (1 + discount_rate)`, so there is one rounding per period and no compounding factor whose rounding error grows with the period.
+///
+/// # Example
+/// ```
+/// use time_value::fixed_point::present_value;
+///
+/// // -1,000 now and 1,210 in two periods at 10%, with a scale of 4
+/// let cash_flows: Vec<i64> = vec![-1_000_0000, 0, 1_210_0000];
+/// assert_eq!(present_value(cash_flows.iter(), 1000, 4), 0);
+/// ```
+pub fn present_value(cash_flows: Iter<i64>, discount_rate: i64, scale: u32) -> i64 {
+    let growth: i64 = one(scale) + discount_rate;
+    cash_flows
+        .rev()
+        .fold(0, |acc, cash_flow| *cash_flow + div(acc, growth, scale))
+}
+
+/// Converts a present value and expected rates into a future value; see `future_value::from_pv_and_expected_rates`.
+///
+/// # Example
+/// ```
+/// use time_value::fixed_point::future_value;
+///
+/// // 10 growing at 10% for three periods is 13.31, with a scale of 2
+/// let rates: Vec<i64> = vec![10, 10, 10];
+/// assert_eq!(future_value(10_00, rates.iter(), 2), 13_31);
+/// ```
+pub fn future_value(present_value: i64, expected_rates: Iter<i64>, scale: u32) -> i64 {
+    expected_rates.fold(present_value, |acc, rate| {
+        mul(acc, one(scale) + *rate, scale)
+    })
+}
+
+fn round_div(numerator: i128, denominator: i128) -> i64 {
+    let quotient: i128 = numerator / denominator;
+    let remainder: i128 = numerator % denominator;
+    let rounded: i128 = if denominator.abs() <= 2 * remainder.abs() {
+        quotient + numerator.signum() * denominator.signum()
+    } else {
+        quotient
+    };
+    i64::try_from(rounded).expect("fixed-point result does not fit in an i64")
+}
+
+#[cfg(test)]
+mod fixed_point_tests {
+    use crate::fixed_point::{div, future_value, mul, present_value};
+
+    #[test]
+    fn it_rounds_half_away_from_zero() {
+        assert_eq!(mul(5, 1, 1), 1);
+        assert_eq!(mul(-5, 1, 1), -1);
+        assert_eq!(div(1, 20, 1), 1);
+        assert_eq!(div(1, -20, 1), -1);
+        assert_eq!(div(1, 30, 1), 0);
+    }
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<i64> = vec![];
+        assert_eq!(present_value(cash_flows.iter(), 500, 4), 0);
+    }
+
+    #[test]
+    fn it_matches_floating_point_to_the_scale() {
+        let cash_flows: Vec<i64> =
+            vec![-100_000_000, 50_000_000, 10_000_000, 10_000_000, 60_000_000];
+        let expected: f64 = crate::present_value::from_cash_flows_and_discount_rate(
+            [-100.0, 50.0, 10.0, 10.0, 60.0].iter(),
+            0.0725,
+        );
+        let value: i64 = present_value(cash_flows.iter(), 72_500, 6);
+        assert!((value as f64 / 1_000_000.0 - expected).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_round_trips_with_future_value() {
+        let rates: Vec<i64> = vec![500, 500, 500];
+        let cash_flows: Vec<i64> = vec![0, 0, 0, future_value(10_000_000, rates.iter(), 4)];
+        assert_eq!(present_value(cash_flows.iter(), 500, 4), 10_000_000);
+    }
+}
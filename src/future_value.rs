@@ -23,7 +23,7 @@ use std::slice::Iter;
 /// let present_value: f32 = 10.0;
 /// let rates: Vec<f32> = vec![1.0, 2.0, 3.0];
 /// let expected_value: f32 = 240.0;
-/// let value: f32 = from_pv_and_expected_rates(&present_value, rates.iter());
+/// let value: f32 = from_pv_and_expected_rates(present_value, rates.iter());
 /// assert_eq!(value, expected_value);
 /// ```
 ///
@@ -45,14 +45,14 @@ use std::slice::Iter;
 /// let present_value: f64 = 10.0;
 /// let rates: Vec<f64> = vec![0.1, 0.1, 0.1];
 /// let expected_value: f64 = 13.31;
-/// let value: f64 = from_pv_and_expected_rates(&present_value, rates.iter());
+/// let value: f64 = from_pv_and_expected_rates(present_value, rates.iter());
 /// assert!(abs(value - expected_value) < 0.001)
 /// ```
-pub fn from_pv_and_expected_rates<T>(present_value: &T, expected_rates: Iter<T>) -> T
+pub fn from_pv_and_expected_rates<T>(present_value: T, expected_rates: Iter<T>) -> T
 where
     T: Float + Product<T>,
 {
-    expected_rates.fold(*present_value, |acc, x| acc * (T::one() + *x))
+    expected_rates.fold(present_value, |acc, x| acc * (T::one() + *x))
 }
 
 #[cfg(test)]
@@ -64,7 +64,7 @@ mod from_pv_and_expected_rates_tests {
     fn it_works_with_no_rates() {
         let present_value: f64 = 10.0;
         let expected_rates: Vec<f64> = vec![];
-        let future_value: f64 = fv(&present_value, expected_rates.iter());
+        let future_value: f64 = fv(present_value, expected_rates.iter());
         assert_eq!(future_value, present_value);
         assert_eq!(expected_rates.len(), 0);
     }
@@ -74,7 +74,7 @@ mod from_pv_and_expected_rates_tests {
         let present_value: f32 = 10.0;
         let rates: Vec<f32> = vec![0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1];
         let expected_value: f32 = 19.48;
-        let value: f32 = fv(&present_value, rates.iter());
+        let value: f32 = fv(present_value, rates.iter());
         assert!(abs(value - expected_value) < 0.01);
     }
 
@@ -83,7 +83,7 @@ mod from_pv_and_expected_rates_tests {
         let present_value: f32 = 10.0;
         let rates: Vec<f32> = vec![-0.02, -0.02, -0.02, -0.02, -20.0, -0.02, -0.02, -0.02];
         let expected_value: f32 = -164.94;
-        let value: f32 = fv(&present_value, rates.iter());
+        let value: f32 = fv(present_value, rates.iter());
         assert!(abs(value - expected_value) < 0.01);
     }
 
@@ -92,7 +92,7 @@ mod from_pv_and_expected_rates_tests {
         let present_value: f64 = 10.0;
         let rates: Vec<f64> = vec![0.02, 0.04, -0.20, 0.00, -0.08, 0.20, 0.03, -0.02];
         let expected_value: f64 = 9.46;
-        let value: f64 = fv(&present_value, rates.iter());
+        let value: f64 = fv(present_value, rates.iter());
         assert!(abs(value - expected_value) < 0.01);
     }
 }
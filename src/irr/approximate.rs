@@ -0,0 +1,195 @@
+//! Non-iterative approximations of the IRR of a series of cash flows, e.g. to screen large data sets before running an exact solver on the candidates.
+
+use num::Float;
+use std::slice::Iter;
+
+use crate::power::powf;
+
+/// Approximates the IRR of a series of cash flows from its multiple of money and the average time of its cash flows.
+///
+/// # Comments
+/// With `M` the inflows divided by the outflows and `D` the average time of the inflows less that of the outflows, each weighted by amount, the approximation is `M^(1 / D) - 1`.
+/// It is exact for a single outflow followed by a single inflow, and otherwise underestimates a positive IRR, by more the longer the series and the higher the IRR.
+/// It is NaN if the series has no inflows or no outflows, or they have the same average time.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::approximate::duration;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 0.0, 121.0];
+/// assert!((duration(cash_flows.iter()) - 0.10).abs() < 0.000_000_1);
+/// ```
+pub fn duration<T>(cash_flows: Iter<T>) -> T
+where
+    T: Float,
+{
+    let (outflows, outflow_time, inflows, inflow_time): (T, T, T, T) = cash_flows.enumerate().fold(
+        (T::zero(), T::zero(), T::zero(), T::zero()),
+        |(outflows, outflow_time, inflows, inflow_time), (period, cash_flow)| {
+            let time: T = T::from(period).unwrap();
+            if *cash_flow < T::zero() {
+                (
+                    outflows - *cash_flow,
+                    outflow_time - *cash_flow * time,
+                    inflows,
+                    inflow_time,
+                )
+            } else {
+                (
+                    outflows,
+                    outflow_time,
+                    inflows + *cash_flow,
+                    inflow_time + *cash_flow * time,
+                )
+            }
+        },
+    );
+
+    if outflows.is_zero() || inflows.is_zero() {
+        return T::nan();
+    }
+    let average_time: T = inflow_time / inflows - outflow_time / outflows;
+    if average_time.is_zero() {
+        return T::nan();
+    }
+    powf(inflows / outflows, T::one() / average_time) - T::one()
+}
+
+/// Approximates the IRR of a series of cash flows by Hazen's average-capital method, i.e. as the return on the capital invested in it.
+///
+/// # Comments
+/// With `k` the `duration` approximation, `c_t` the capital invested after the cash flow of period `t` when it earns `k`, and `PV` the present value at `k`, the approximation is `k + NPV(k) * (1 + k) / PV(c_0, ..., c_(n-1))`.
+/// This is exact if `k` is the IRR, and corrects most of the error of `duration` for short series and moderate rates, e.g. to within half a percentage point for level payments over up to 10 periods at up to 20% per period.
+/// For long series at high rates the capital can be far from that invested at the IRR, and the approximation can be worse than `duration`; see the tests for a comparison with the exact IRR.
+/// It is NaN if `duration` is, or the capital has no value.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::approximate::hazen;
+/// use time_value::irr::bisection::functions::irr::bisection;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// let exact: f64 = bisection(cash_flows.iter(), 0.0, 0.5, 100).irr();
+/// assert!((hazen(cash_flows.iter()) - exact).abs() < 0.000_5);
+/// ```
+pub fn hazen<T>(cash_flows: Iter<T>) -> T
+where
+    T: Float,
+{
+    let reference_rate: T = duration(cash_flows.clone());
+    if reference_rate.is_nan() {
+        return reference_rate;
+    }
+    let growth: T = T::one() + reference_rate;
+    let last: usize = cash_flows.len() - 1;
+
+    let mut npv: T = T::zero();
+    let mut capital: T = T::zero();
+    let mut capital_value: T = T::zero();
+    let mut discount: T = T::one();
+    for (period, cash_flow) in cash_flows.enumerate() {
+        npv = npv + *cash_flow * discount;
+        if period < last {
+            capital = capital * growth - *cash_flow;
+            capital_value = capital_value + capital * discount;
+        }
+        discount = discount / growth;
+    }
+
+    let approximation: T = reference_rate + npv * growth / capital_value;
+    if approximation.is_finite() {
+        approximation
+    } else {
+        T::nan()
+    }
+}
+
+#[cfg(test)]
+mod accuracy_tests {
+    //! Compares the approximations with the exact IRR of level payments and of bonds, over `n` periods at a rate `r` per period, in percentage points:
+    //!
+    //! | series                | `duration`  | `hazen`      |
+    //! |-----------------------|-------------|--------------|
+    //! | `n <= 10`, `r <= 20%` | within 3.1  | within 0.6   |
+    //! | `n <= 40`, `r <= 5%`  | within 0.9  | within 0.3   |
+    //! | `n <= 20`, `r <= 20%` | within 6.2  | not reliable |
+    //!
+    //! Both are exact for a single outflow followed by a single inflow.
+
+    use crate::irr::approximate::{duration, hazen};
+    use crate::irr::bisection::functions::irr::bisection_with_config;
+    use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy, Convergence};
+    use std::slice::Iter;
+
+    /// The level payment and bond series of a loan of 1 over `periods` periods at `rate`.
+    fn series(periods: usize, rate: f64) -> Vec<Vec<f64>> {
+        let payment: f64 = if rate == 0.0 {
+            1.0 / periods as f64
+        } else {
+            rate / (1.0 - (1.0 + rate).powi(-(periods as i32)))
+        };
+        let mut level: Vec<f64> = vec![-1.0];
+        level.extend(vec![payment; periods]);
+        let mut bond: Vec<f64> = vec![-1.0];
+        bond.extend(vec![rate; periods]);
+        bond[periods] += 1.0;
+        vec![level, bond]
+    }
+
+    /// The largest error of an approximation over the series for every combination of periods and rates.
+    fn worst_error(approximation: fn(Iter<f64>) -> f64, periods: &[usize], rates: &[f64]) -> f64 {
+        let config: BisectionConfig<f64> = BisectionConfig::new()
+            .with_npv_tolerance(1e-12)
+            .with_iteration_limit(200)
+            .with_convergence(Convergence::NpvOrRateInterval(1e-12))
+            .with_bracket(BracketStrategy::Bounds(-0.5, 1.0));
+        let mut worst: f64 = 0.0;
+        for periods in periods {
+            for rate in rates {
+                for cash_flows in series(*periods, *rate) {
+                    let exact: f64 = bisection_with_config(cash_flows.iter(), &config).irr();
+                    worst = worst.max((approximation(cash_flows.iter()) - exact).abs());
+                }
+            }
+        }
+        worst
+    }
+
+    #[test]
+    fn it_is_accurate_for_short_series() {
+        let periods: Vec<usize> = (1..=10).collect();
+        let rates: Vec<f64> = vec![0.0, 0.01, 0.02, 0.05, 0.10, 0.15, 0.20];
+        assert!(worst_error(duration, &periods, &rates) < 0.031);
+        assert!(worst_error(hazen, &periods, &rates) < 0.006);
+    }
+
+    #[test]
+    fn it_is_accurate_for_long_series_at_low_rates() {
+        let periods: Vec<usize> = vec![12, 20, 30, 40];
+        let rates: Vec<f64> = vec![0.0, 0.01, 0.02, 0.05];
+        assert!(worst_error(duration, &periods, &rates) < 0.009);
+        assert!(worst_error(hazen, &periods, &rates) < 0.003);
+    }
+
+    #[test]
+    fn it_bounds_duration_for_longer_series() {
+        let periods: Vec<usize> = vec![12, 15, 20];
+        let rates: Vec<f64> = vec![0.05, 0.10, 0.20];
+        assert!(worst_error(duration, &periods, &rates) < 0.062);
+    }
+
+    #[test]
+    fn it_is_exact_for_a_single_inflow() {
+        let cash_flows: Vec<f64> = vec![-100.0, 0.0, 0.0, 0.0, 1.2_f64.powi(4) * 100.0];
+        assert!((duration(cash_flows.iter()) - 0.2).abs() < 0.000_000_1);
+        assert!((hazen(cash_flows.iter()) - 0.2).abs() < 0.000_000_1);
+    }
+
+    #[test]
+    fn it_is_nan_without_a_sign_change() {
+        let cash_flows: Vec<f32> = vec![100.0, 10.0];
+        assert!(duration(cash_flows.iter()).is_nan());
+        assert!(hazen(cash_flows.iter()).is_nan());
+        assert!(hazen(Vec::<f64>::new().iter()).is_nan());
+    }
+}
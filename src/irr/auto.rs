@@ -0,0 +1,193 @@
+//! Chooses an IRR solver by inspecting the series of cash flows.
+
+use num::{Float, Signed};
+use std::fmt;
+use std::fmt::{Debug, Display};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::cash_flows::sign_changes;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::comparison::{solve_with_npv, SolverKind};
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// The number of cash flows above which a series with a single sign change is solved with Brent's method rather than the hybrid method.
+pub const LONG_SERIES: usize = 120;
+
+/// The reason a solver was chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reason {
+    /// The series changes sign more than once, so it may have several IRRs and only bisection reliably stays in the bracket.
+    MultipleSignChanges,
+    /// The series is long, so its NPV tends to be flat at high rates where Newton steps overshoot, and each evaluation is costly.
+    LongSeries,
+    /// The series is short and changes sign at most once, so Newton's method converges quickly with bisection as a fallback.
+    ShortConventionalSeries,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::MultipleSignChanges => {
+                write!(f, "more than one sign change, so bisection keeps to the bracket")
+            }
+            Reason::LongSeries => write!(
+                f,
+                "more than {} cash flows, so Brent's method minimises NPV evaluations",
+                LONG_SERIES
+            ),
+            Reason::ShortConventionalSeries => write!(
+                f,
+                "at most one sign change and at most {} cash flows, so Newton's method with a bisection fallback",
+                LONG_SERIES
+            ),
+        }
+    }
+}
+
+/// The solver chosen for a series of cash flows and the reason for choosing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Choice {
+    kind: SolverKind,
+    reason: Reason,
+}
+
+impl Choice {
+    /// The chosen solver, which is never `SolverKind::Auto`.
+    pub fn kind(&self) -> SolverKind {
+        self.kind
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `kind` instead")]
+    pub fn get_kind(&self) -> SolverKind {
+        self.kind()
+    }
+
+    pub fn reason(&self) -> Reason {
+        self.reason
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `reason` instead")]
+    pub fn get_reason(&self) -> Reason {
+        self.reason()
+    }
+}
+
+/// Chooses a solver for a series of cash flows.
+///
+/// # Comments
+/// The heuristic, in order:
+/// - more than one sign change: `SolverKind::Bisection`
+/// - more than `LONG_SERIES` cash flows: `SolverKind::Brent`
+/// - otherwise: `SolverKind::Hybrid`
+///
+/// Only the signs and the number of cash flows are inspected, not how well conditioned the IRR is: amounts spanning many orders of magnitude, or a root at which the NPV is nearly flat, e.g. close to a double root, are not detected.
+/// For those, normalize the cash flows first with `BisectionConfig::with_normalize`, or check `irr::multiplicity::inspect` before trusting a solution.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::auto::{choose, Reason};
+/// use time_value::irr::comparison::SolverKind;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// assert_eq!(choose(cash_flows.iter()).kind(), SolverKind::Bisection);
+/// assert_eq!(choose(cash_flows.iter()).reason(), Reason::MultipleSignChanges);
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// assert_eq!(choose(cash_flows.iter()).kind(), SolverKind::Hybrid);
+/// ```
+pub fn choose<T>(cash_flows: Iter<T>) -> Choice
+where
+    T: Float,
+{
+    if 1 < sign_changes(cash_flows.clone()) {
+        Choice {
+            kind: SolverKind::Bisection,
+            reason: Reason::MultipleSignChanges,
+        }
+    } else if LONG_SERIES < cash_flows.len() {
+        Choice {
+            kind: SolverKind::Brent,
+            reason: Reason::LongSeries,
+        }
+    } else {
+        Choice {
+            kind: SolverKind::Hybrid,
+            reason: Reason::ShortConventionalSeries,
+        }
+    }
+}
+
+/// Calculates the IRR of a series of cash flows with the solver chosen by `choose`, returning the choice with the IRR.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::auto::{auto, Reason};
+/// use time_value::irr::comparison::SolverKind;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let (calculated_irr, choice) = auto(cash_flows.iter(), 0.05, 0.18, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert_eq!(choice.kind(), SolverKind::Hybrid);
+/// assert_eq!(choice.reason(), Reason::ShortConventionalSeries);
+/// ```
+pub fn auto<T>(
+    cash_flows: Iter<T>,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> (Irr<T>, Choice)
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let choice: Choice = choose(cash_flows.clone());
+    let irr: Irr<T> = solve_with_npv(
+        choice.kind(),
+        cash_flows.clone(),
+        &|rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    );
+    (irr, choice)
+}
+
+#[cfg(test)]
+mod choose_tests {
+    use crate::irr::auto::{choose, Reason, LONG_SERIES};
+    use crate::irr::comparison::SolverKind;
+
+    #[test]
+    fn it_prefers_brent_for_long_series() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; LONG_SERIES]);
+        assert_eq!(choose(cash_flows.iter()).kind(), SolverKind::Brent);
+        assert_eq!(choose(cash_flows.iter()).reason(), Reason::LongSeries);
+    }
+
+    #[test]
+    fn it_prefers_bisection_for_long_series_with_multiple_sign_changes() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; LONG_SERIES]);
+        cash_flows.push(-50.0);
+        assert_eq!(choose(cash_flows.iter()).kind(), SolverKind::Bisection);
+    }
+}
+
+#[cfg(test)]
+mod auto_tests {
+    use crate::irr::auto::auto;
+    use crate::irr::comparison::SolverKind;
+
+    #[test]
+    fn it_works_with_a_long_series() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; 600]);
+        let (calculated_irr, choice) = auto(cash_flows.iter(), 0.0, 10.0, 100);
+
+        assert_eq!(choice.kind(), SolverKind::Brent);
+        assert!(calculated_irr.is_valid());
+    }
+}
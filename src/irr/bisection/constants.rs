@@ -3,4 +3,5 @@
 /// The level of precision we require for NPVs to zero.
 ///
 /// NPVs will be calculated for some currency or other similarly represented medium of exchange, suggesting two decimals of precision are sufficient for our purposes.
+/// It is the default; `bisection_with_tolerance` and `initial_bounds::determine_with_tolerance` accept a tolerance suited to the scale of the cash flows.
 pub const NPV_PRECISION: f32 = 0.001;
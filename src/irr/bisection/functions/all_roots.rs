@@ -0,0 +1,120 @@
+//! Find every IRR of a series of cash flows within an interval.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::functions::irr::bisection_with_npv;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// Scans `[rate_min, rate_max]` in increments of `step` for sign changes of the NPV and solves each sub-bracket with the bisection method.
+///
+/// # Assumptions
+/// It is assumed that `step` is positive; otherwise nothing is scanned.
+///
+/// # Comments
+/// The IRRs are returned in increasing order.
+/// Two IRRs closer together than `step` have no sign change between neighbouring grid rates and are missed, as is a root where the NPV touches zero without changing sign; `irr::multiplicity::inspect` bounds how many IRRs there are.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::all_roots;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // the IRRs are 10% and 20%
+/// let cash_flows: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// let irrs: Vec<Irr<f64>> = all_roots::find(cash_flows.iter(), -0.5, 1.0, 0.01, 100);
+/// assert_eq!(irrs.len(), 2);
+/// assert!((irrs[0].irr() - 0.10).abs() < 0.000_5);
+/// assert!((irrs[1].irr() - 0.20).abs() < 0.000_5);
+/// ```
+pub fn find<T>(
+    cash_flows: Iter<T>,
+    rate_min: T,
+    rate_max: T,
+    step: T,
+    iteration_limit: u32,
+) -> Vec<Irr<T>>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let npv_at = |rate: T| pv(cash_flows.clone(), rate);
+    let mut irrs: Vec<Irr<T>> = vec![];
+    if step <= T::zero() || rate_max < rate_min {
+        return irrs;
+    }
+
+    let mut rate_low: T = rate_min;
+    let mut npv_rate_low: T = npv_at(rate_low);
+    if npv_rate_low.is_zero() {
+        irrs.push(exact(rate_low, npv_rate_low, iteration_limit));
+    }
+
+    let mut steps: T = T::one();
+    while rate_low < rate_max {
+        // stepping from rate_min avoids accumulating rounding errors over many steps
+        let rate_high: T = (rate_min + steps * step).min(rate_max);
+        let npv_rate_high: T = npv_at(rate_high);
+        if npv_rate_high.is_zero() {
+            irrs.push(exact(rate_high, npv_rate_high, iteration_limit));
+        } else if npv_rate_low * npv_rate_high < T::zero() {
+            irrs.push(bisection_with_npv(
+                npv_at,
+                rate_low,
+                rate_high,
+                iteration_limit,
+            ));
+        }
+        rate_low = rate_high;
+        npv_rate_low = npv_rate_high;
+        steps = steps + T::one();
+    }
+
+    irrs
+}
+
+/// An IRR at which the NPV is exactly zero, so no iterations are needed.
+fn exact<T>(rate: T, npv: T, iteration_limit: u32) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    Irr::new(rate, npv, rate, npv, iteration_limit, 0, rate, npv, true)
+}
+
+#[cfg(test)]
+mod find_tests {
+    use crate::irr::bisection::functions::all_roots;
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_works_with_a_non_positive_step() {
+        let cash_flows: Vec<f64> = vec![-100.0, 110.0];
+        assert!(all_roots::find(cash_flows.iter(), 0.0, 1.0, 0.0, 100).is_empty());
+        assert!(all_roots::find(cash_flows.iter(), 0.0, 1.0, -0.1, 100).is_empty());
+    }
+
+    #[test]
+    fn it_finds_three_irrs() {
+        // the IRRs are 0%, 10% and 20%
+        let cash_flows: Vec<f64> = vec![-1_000.0, 3_300.0, -3_620.0, 1_320.0];
+        let irrs: Vec<Irr<f64>> = all_roots::find(cash_flows.iter(), -0.05, 0.5, 0.03, 100);
+
+        assert_eq!(irrs.len(), 3);
+        for (calculated_irr, expected) in irrs.iter().zip([0.0, 0.10, 0.20].iter()) {
+            assert!(calculated_irr.is_valid());
+            // the NPV is flat near these IRRs, so an NPV within NPV_PRECISION allows a wider rate
+            assert!((calculated_irr.irr() - expected).abs() < 0.000_5);
+        }
+    }
+
+    #[test]
+    fn it_finds_an_irr_on_the_grid_once() {
+        let cash_flows: Vec<f64> = vec![-100.0, 125.0];
+        let irrs: Vec<Irr<f64>> = all_roots::find(cash_flows.iter(), 0.0, 1.0, 0.25, 100);
+
+        assert_eq!(irrs.len(), 1);
+        assert_eq!(irrs[0].irr(), 0.25);
+        assert_eq!(irrs[0].iterations_run(), 0);
+    }
+}
@@ -1,7 +1,7 @@
 //! Determines if two floating point numbers are "equal enough" based on machine epsilon
 
 use num::{abs, Float, Signed};
-use std::fmt::{Debug, Display};
+use std::convert::TryFrom;
 use std::iter::{Product, Sum};
 
 /// # Comments
@@ -13,19 +13,173 @@ use std::iter::{Product, Sum};
 ///
 /// let a: f32 = 0.0010;
 /// let b: f32 = 0.0010;
-/// assert!(are_equal_enough::is_true(&a, &b));
+/// assert!(are_equal_enough::is_true(a, b));
 ///
 /// let c: f32 = 0.0011;
-/// assert!(!are_equal_enough::is_true(&a, &c));
+/// assert!(!are_equal_enough::is_true(a, c));
 /// ```
-pub fn is_true<T>(a: &T, b: &T) -> bool
+pub fn is_true<T>(a: T, b: T) -> bool
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
-    let difference: T = abs(*a - *b);
-    let a_abs: T = abs(*a);
-    let b_abs: T = abs(*b);
+    let difference: T = abs(a - b);
+    let a_abs: T = abs(a);
+    let b_abs: T = abs(b);
     let larger: T = if a_abs < b_abs { b_abs } else { a_abs };
 
     difference <= (larger * T::epsilon())
 }
+
+/// Whether `a` and `b` are within an absolute tolerance or a tolerance relative to the larger of their magnitudes, i.e. `|a - b| <= max(absolute_tolerance, relative_tolerance * max(|a|, |b|))`.
+///
+/// # Comments
+/// A purely relative comparison, like `is_true`, cannot succeed against zero, so it cannot say that an NPV has converged to zero; the absolute floor can.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::are_equal_enough;
+///
+/// let npv: f64 = 0.000_4;
+/// assert!(!are_equal_enough::is_true(npv, 0.0));
+/// assert!(are_equal_enough::is_true_with_tolerances(npv, 0.0, 0.001, f64::EPSILON));
+/// assert!(are_equal_enough::is_true_with_tolerances(1_000_000.0, 1_000_001.0, 0.001, 0.000_001));
+/// ```
+pub fn is_true_with_tolerances<T>(a: T, b: T, absolute_tolerance: T, relative_tolerance: T) -> bool
+where
+    T: Float,
+{
+    let larger: T = a.abs().max(b.abs());
+    (a - b).abs() <= absolute_tolerance.max(relative_tolerance * larger)
+}
+
+/// Whether `a` and `b` are within an absolute `tolerance`, treating two NaNs, or two infinities of the same sign, as equal.
+///
+/// # Comments
+/// This is the comparison used by the `approx_eq` methods e.g. of `Irr`, where an invalid result has a NaN IRR.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::are_equal_enough;
+///
+/// assert!(are_equal_enough::is_true_within(100.0, 100.004, 0.005));
+/// assert!(!are_equal_enough::is_true_within(100.0, 100.006, 0.005));
+/// assert!(are_equal_enough::is_true_within(f64::NAN, f64::NAN, 0.0));
+/// assert!(!are_equal_enough::is_true_within(f64::NAN, 0.0, f64::INFINITY));
+/// ```
+pub fn is_true_within<T>(a: T, b: T, tolerance: T) -> bool
+where
+    T: Float,
+{
+    a == b || (a.is_nan() && b.is_nan()) || (a - b).abs() <= tolerance
+}
+
+/// Counts the representable values of `T` between `a` and `b`, i.e. their distance in units in the last place (ULPs).
+///
+/// # Comments
+/// Unlike a comparison scaled by `epsilon`, the distance is meaningful near zero: `0.0` and `-0.0` are 0 ULPs apart, and the smallest positive and negative values are 2 ULPs apart.
+/// `f32` is measured in `f32` ULPs and every other type in `f64` ULPs; the distance involving a NaN is `u64::MAX`.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::are_equal_enough;
+///
+/// let a: f32 = 1.0;
+/// let b: f32 = 1.0 + f32::EPSILON;
+/// assert_eq!(are_equal_enough::ulps_between(a, b), 1);
+/// assert_eq!(are_equal_enough::ulps_between(0.0_f32, -0.0_f32), 0);
+/// assert_eq!(are_equal_enough::ulps_between(-1.0e-45_f32, 1.0e-45_f32), 2);
+/// ```
+pub fn ulps_between<T>(a: T, b: T) -> u64
+where
+    T: Float,
+{
+    if a.is_nan() || b.is_nan() {
+        return u64::MAX;
+    }
+    let (a, b): (i128, i128) = if std::mem::size_of::<T>() == std::mem::size_of::<f32>() {
+        (
+            ordered_f32(a.to_f32().unwrap()),
+            ordered_f32(b.to_f32().unwrap()),
+        )
+    } else {
+        (
+            ordered_f64(a.to_f64().unwrap()),
+            ordered_f64(b.to_f64().unwrap()),
+        )
+    };
+    u64::try_from((a - b).abs()).unwrap_or(u64::MAX)
+}
+
+/// Whether `a` and `b` are at most `max_ulps` representable values apart; see `ulps_between`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::are_equal_enough;
+///
+/// let sum: f64 = 0.1 + 0.2;
+/// assert!(are_equal_enough::is_true_within_ulps(sum, 0.3, 1));
+/// assert!(!are_equal_enough::is_true_within_ulps(sum, 0.3, 0));
+/// ```
+pub fn is_true_within_ulps<T>(a: T, b: T, max_ulps: u64) -> bool
+where
+    T: Float,
+{
+    ulps_between(a, b) <= max_ulps
+}
+
+/// Maps the bits of an `f32` to an integer with the same ordering as the value, with both zeros at 0.
+fn ordered_f32(value: f32) -> i128 {
+    let bits: i32 = value.to_bits() as i32;
+    if bits < 0 {
+        -i128::from(bits & i32::MAX)
+    } else {
+        i128::from(bits)
+    }
+}
+
+/// Maps the bits of an `f64` to an integer with the same ordering as the value, with both zeros at 0.
+fn ordered_f64(value: f64) -> i128 {
+    let bits: i64 = value.to_bits() as i64;
+    if bits < 0 {
+        -i128::from(bits & i64::MAX)
+    } else {
+        i128::from(bits)
+    }
+}
+
+#[cfg(test)]
+mod is_true_with_tolerances_tests {
+    use crate::irr::bisection::functions::are_equal_enough::is_true_with_tolerances;
+
+    #[test]
+    fn it_uses_the_larger_tolerance() {
+        assert!(is_true_with_tolerances(0.0, 0.5, 1.0, 0.0));
+        assert!(is_true_with_tolerances(100.0, 101.0, 0.0, 0.01));
+        assert!(!is_true_with_tolerances(100.0, 102.0, 1.0, 0.01));
+    }
+
+    #[test]
+    fn it_works_with_nan() {
+        assert!(!is_true_with_tolerances(f64::NAN, 0.0, 1.0, 1.0));
+    }
+}
+
+#[cfg(test)]
+mod ulps_between_tests {
+    use crate::irr::bisection::functions::are_equal_enough::{is_true, ulps_between};
+
+    #[test]
+    fn it_works_across_zero_where_is_true_does_not() {
+        let tiny: f64 = 1.0e-300;
+        assert!(!is_true(tiny, -tiny));
+        assert!(!is_true(tiny, 0.0));
+        assert_eq!(ulps_between(tiny, -tiny), 2 * ulps_between(tiny, 0.0));
+        assert_eq!(ulps_between(f64::MIN_POSITIVE, 0.0), 1 << 52);
+    }
+
+    #[test]
+    fn it_works_with_nan_and_infinity() {
+        assert_eq!(ulps_between(f64::NAN, 1.0), u64::MAX);
+        assert_eq!(ulps_between(f32::MAX, f32::INFINITY), 1);
+    }
+}
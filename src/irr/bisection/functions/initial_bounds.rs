@@ -1,16 +1,22 @@
 //! Calculate the initial rates to use with the bisection method.
 
 use num::{abs, Float, Signed};
-use std::fmt::{Debug, Display};
+use std::borrow::Borrow;
+use std::cell::Cell;
 use std::iter::{Product, Sum};
-use std::slice::Iter;
 
 use crate::irr::bisection::constants::NPV_PRECISION;
 use crate::irr::bisection::structs::initial_bounds::InitialBounds;
+use crate::irr::evaluations::counted;
 use crate::present_value::from_cash_flows_and_discount_rate as pv;
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+use crate::present_value::from_sparse_cash_flows_and_discount_rate as sparse_pv;
 
 /// This will find a pair of initial rates, one with a negative NPV and the other with a positive NPV, for use with the bisection method.
 ///
+/// # Comments
+/// The cash flows can be any collection or iterator that can be cloned, as each NPV evaluation iterates over them again e.g. `cash_flows.iter()`, `&cash_flows` or a `VecDeque`.
+///
 /// # Example
 /// ```
 /// use time_value::irr::bisection::functions::initial_bounds;
@@ -22,52 +28,205 @@ use crate::present_value::from_cash_flows_and_discount_rate as pv;
 ///         }
 ///
 ///         let rate_guess: f32 = 0.10;
-///         let iteration_limit: i16 = 100;
+///         let iteration_limit: u32 = 100;
 ///
 ///         let initial_bounds: InitialBounds<f32> = initial_bounds::determine(
 ///             cash_flows.iter(),
-///             &rate_guess,
-///             &iteration_limit,
+///             rate_guess,
+///             iteration_limit,
 ///         );
 ///
 ///         assert!(initial_bounds.is_valid())
 /// ```
-pub fn determine<T>(cash_flows: Iter<T>, rate_guess: &T, iteration_limit: &i16) -> InitialBounds<T>
+pub fn determine<T, I, B>(cash_flows: I, rate_guess: T, iteration_limit: u32) -> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    determine_with_npv(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_guess,
+        iteration_limit,
+    )
+}
+
+/// Finds initial rates like `determine` for a slice of cash flows, which each NPV evaluation indexes directly.
+///
+/// # Example
+/// ```
+/// use time_value::irr::bisection::functions::initial_bounds;
+/// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// let initial_bounds: InitialBounds<f64> =
+///     initial_bounds::determine_from_slice(&cash_flows, 0.50, 100);
+/// assert!(initial_bounds.is_valid());
+/// assert_eq!(initial_bounds, initial_bounds::determine(cash_flows.iter(), 0.50, 100));
+/// ```
+pub fn determine_from_slice<T>(
+    cash_flows: &[T],
+    rate_guess: T,
+    iteration_limit: u32,
+) -> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    determine_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        rate_guess,
+        iteration_limit,
+    )
+}
+
+/// Finds initial rates like `determine`, accepting a rate guess whose NPV is within `npv_tolerance` of zero rather than `NPV_PRECISION`.
+///
+/// # Example
+/// ```
+/// use time_value::irr::bisection::functions::initial_bounds;
+/// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
+///
+/// // the NPV at 10% is 0.000413, which is within NPV_PRECISION but not the tolerance
+/// let cash_flows: Vec<f64> = vec![-0.0100, 0.0060, 0.0060];
+/// let initial_bounds: InitialBounds<f64> =
+///     initial_bounds::determine_with_tolerance(cash_flows.iter(), 0.10, 100, 0.000_000_001);
+/// assert!(initial_bounds.is_valid());
+/// assert!(initial_bounds.rate_low() < initial_bounds.rate_high());
+/// ```
+pub fn determine_with_tolerance<T, I, B>(
+    cash_flows: I,
+    rate_guess: T,
+    iteration_limit: u32,
+    npv_tolerance: T,
+) -> InitialBounds<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
 {
-    let npv_rate_guess: T = pv(cash_flows.clone(), &rate_guess);
-    if abs(pv(cash_flows.clone(), &rate_guess)) < T::from(NPV_PRECISION).unwrap() {
+    determine_with_npv_and_tolerance(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_guess,
+        iteration_limit,
+        npv_tolerance,
+    )
+}
+
+/// Finds initial rates for a series of sparse cash flows, given as `(period, cash flow)` pairs.
+///
+/// # Example
+/// ```
+/// use time_value::irr::bisection::functions::initial_bounds;
+/// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
+///
+/// let cash_flows: Vec<(u32, f64)> = vec![(0, -100.0), (30, 1_000.0)];
+/// let initial_bounds: InitialBounds<f64> =
+///     initial_bounds::determine_sparse(cash_flows.iter(), 0.01, 100);
+/// assert!(initial_bounds.is_valid());
+/// ```
+pub fn determine_sparse<T, I, B>(
+    cash_flows: I,
+    rate_guess: T,
+    iteration_limit: u32,
+) -> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<(u32, T)>,
+{
+    determine_with_npv(
+        |rate| sparse_pv(cash_flows.clone(), rate),
+        rate_guess,
+        iteration_limit,
+    )
+}
+
+pub(crate) fn determine_with_npv<T, F>(
+    npv_at: F,
+    rate_guess: T,
+    iteration_limit: u32,
+) -> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    determine_with_npv_and_tolerance(
+        npv_at,
+        rate_guess,
+        iteration_limit,
+        T::from(NPV_PRECISION).unwrap(),
+    )
+}
+
+pub(crate) fn determine_with_npv_and_tolerance<T, F>(
+    npv_at: F,
+    rate_guess: T,
+    iteration_limit: u32,
+    npv_tolerance: T,
+) -> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "initial_bounds",
+        rate_guess = crate::trace::value(rate_guess),
+        iteration_limit = iteration_limit,
+        npv_tolerance = crate::trace::value(npv_tolerance),
+    )
+    .entered();
+
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let npv_rate_guess: T = npv_at(rate_guess);
+    if abs(npv_rate_guess) < npv_tolerance {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("the rate guess is a root");
         return InitialBounds::new(
-            *rate_guess,
+            rate_guess,
             npv_rate_guess,
-            *rate_guess,
+            rate_guess,
             npv_rate_guess,
-            *iteration_limit,
+            iteration_limit,
             0,
             true,
-        );
+        )
+        .with_npv_evaluations(npv_evaluations.get());
     }
 
     let mut epsilon_multiple: T = T::from(10.00).unwrap();
-    let mut rate_low: T = *rate_guess - epsilon_multiple * T::epsilon();
-    let mut rate_high: T = *rate_guess + epsilon_multiple * T::epsilon();
-    let mut npv_rate_low: T = pv(cash_flows.clone(), &rate_low);
-    let mut npv_rate_high: T = pv(cash_flows.clone(), &rate_high);
-    let mut iterations_run: i16 = 0;
+    let mut rate_low: T = rate_guess - epsilon_multiple * T::epsilon();
+    let mut rate_high: T = rate_guess + epsilon_multiple * T::epsilon();
+    let mut npv_rate_low: T = npv_at(rate_low);
+    let mut npv_rate_high: T = npv_at(rate_high);
+    let mut iterations_run: u32 = 0;
     let go_low: bool = abs(npv_rate_low) < abs(npv_rate_high);
 
-    while iterations_run < *iteration_limit {
+    while iterations_run < iteration_limit {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            iterations_run,
+            rate_low = crate::trace::value(rate_low),
+            rate_high = crate::trace::value(rate_high),
+            npv_rate_low = crate::trace::value(npv_rate_low),
+            npv_rate_high = crate::trace::value(npv_rate_high),
+            "bracket search iteration"
+        );
         if npv_rate_low * npv_rate_high <= T::zero() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(iterations_run, "bracket found");
             return InitialBounds::new(
                 rate_low,
                 npv_rate_low,
                 rate_high,
                 npv_rate_high,
-                *iteration_limit,
+                iteration_limit,
                 iterations_run,
                 true,
-            );
+            )
+            .with_npv_evaluations(npv_evaluations.get());
         }
 
         epsilon_multiple = generate_epsilon_multiple(epsilon_multiple);
@@ -80,26 +239,29 @@ where
             rate_high = rate_high + epsilon_multiple * T::epsilon();
         }
 
-        npv_rate_low = pv(cash_flows.clone(), &rate_low);
-        npv_rate_high = pv(cash_flows.clone(), &rate_high);
+        npv_rate_low = npv_at(rate_low);
+        npv_rate_high = npv_at(rate_high);
 
         iterations_run += 1;
     }
 
+    #[cfg(feature = "tracing")]
+    tracing::debug!(iterations_run, "bracket not found");
     InitialBounds::new(
         rate_low,
         npv_rate_low,
         rate_high,
         npv_rate_high,
-        *iteration_limit,
+        iteration_limit,
         iterations_run,
         false,
     )
+    .with_npv_evaluations(npv_evaluations.get())
 }
 
 fn generate_epsilon_multiple<T>(epsilon_multiple: T) -> T
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
     if epsilon_multiple < T::max_value() / T::from(2.0).unwrap() {
         epsilon_multiple * T::from(2.0).unwrap()
@@ -114,15 +276,15 @@ mod determine_test {
     use rand::distributions::uniform::SampleUniform;
     use rand::prelude::ThreadRng;
     use rand::{thread_rng, Rng};
-    use std::fmt::{Debug, Display};
+
     use std::iter::{Product, Sum};
 
     use crate::irr::bisection::functions::initial_bounds;
     use crate::irr::bisection::structs::initial_bounds::InitialBounds;
 
-    fn generate_random_cash_flows<T>(thread_range: &mut ThreadRng, vector_size: &i16) -> Vec<T>
+    fn generate_random_cash_flows<T>(thread_range: &mut ThreadRng, vector_size: usize) -> Vec<T>
     where
-        T: Float + Product<T> + Sum<T> + Signed + Display + Debug + SampleUniform,
+        T: Float + Product<T> + Sum<T> + Signed + SampleUniform,
     {
         //ensure the first element is negative
         let mut cash_flows: Vec<T> =
@@ -137,42 +299,38 @@ mod determine_test {
     #[test]
     fn it_works_with_random_inputs() {
         let mut thread_range: ThreadRng = thread_rng();
-        let vector_size: i16 = 20;
+        let vector_size: usize = 20;
         let rate_guess: f32 = 0.01;
-        let iteration_limit: i16 = 1_000;
-        let mut cash_flows: Vec<f32> = generate_random_cash_flows(&mut thread_range, &vector_size);
+        let iteration_limit: u32 = 1_000;
+        let mut cash_flows: Vec<f32> = generate_random_cash_flows(&mut thread_range, vector_size);
 
         for _ in 0..100 {
             let initial_bounds: InitialBounds<f32> =
-                initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+                initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
             if initial_bounds.is_valid() {
-                assert!(
-                    initial_bounds.get_npv_rate_low() * initial_bounds.get_npv_rate_high() <= 0.00
-                );
+                assert!(initial_bounds.npv_rate_low() * initial_bounds.npv_rate_high() <= 0.00);
             } else {
                 assert_eq!(
-                    initial_bounds.get_iteration_limit(),
-                    initial_bounds.get_iterations_run()
+                    initial_bounds.iteration_limit(),
+                    initial_bounds.iterations_run()
                 );
             }
 
-            cash_flows = generate_random_cash_flows(&mut thread_range, &vector_size)
+            cash_flows = generate_random_cash_flows(&mut thread_range, vector_size)
         }
     }
 
     #[test]
     fn it_works_with_a_good_guess() {
         let mut cash_flows: Vec<f32> = vec![-100.00];
-        for _ in 0..10 {
-            cash_flows.push(20.00);
-        }
+        cash_flows.extend(vec![20.00; 10]);
 
         let rate_guess: f32 = 0.150984;
-        let iteration_limit: i16 = 0;
+        let iteration_limit: u32 = 0;
 
         let initial_bounds: InitialBounds<f32> =
-            initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+            initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
         assert!(initial_bounds.is_valid())
     }
@@ -180,15 +338,13 @@ mod determine_test {
     #[test]
     fn it_works_with_a_bad_guess() {
         let mut cash_flows: Vec<f32> = vec![-100.00];
-        for _ in 0..10 {
-            cash_flows.push(20.00);
-        }
+        cash_flows.extend(vec![20.00; 10]);
 
         let rate_guess: f32 = 0.10;
-        let iteration_limit: i16 = 0;
+        let iteration_limit: u32 = 0;
 
         let initial_bounds: InitialBounds<f32> =
-            initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+            initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
         assert!(!initial_bounds.is_valid())
     }
@@ -196,15 +352,13 @@ mod determine_test {
     #[test]
     fn it_works_with_a_low_guess() {
         let mut cash_flows: Vec<f32> = vec![-100.00];
-        for _ in 0..10 {
-            cash_flows.push(20.00);
-        }
+        cash_flows.extend(vec![20.00; 10]);
 
         let rate_guess: f32 = 0.10;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
 
         let initial_bounds: InitialBounds<f32> =
-            initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+            initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
         assert!(initial_bounds.is_valid())
     }
@@ -212,16 +366,78 @@ mod determine_test {
     #[test]
     fn it_works_with_a_high_guess() {
         let mut cash_flows: Vec<f32> = vec![-100.00];
-        for _ in 0..10 {
-            cash_flows.push(20.00);
-        }
+        cash_flows.extend(vec![20.00; 10]);
 
         let rate_guess: f32 = 0.2;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
 
         let initial_bounds: InitialBounds<f32> =
-            initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+            initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
         assert!(initial_bounds.is_valid())
     }
 }
+
+#[cfg(test)]
+mod solve_tests {
+    use crate::irr::bisection::functions::initial_bounds::determine;
+    use crate::irr::bisection::functions::irr::bisection_with_config;
+    use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy};
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+    use crate::irr::bisection::structs::initial_bounds::InitialBounds;
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_matches_bisection_between_the_bounds() {
+        let cash_flows: Vec<f64> = vec![
+            -100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,
+        ];
+        let initial_bounds: InitialBounds<f64> = determine(cash_flows.iter(), 0.50, 100);
+        let config: BisectionConfig<f64> = BisectionConfig::new().with_iteration_limit(200);
+
+        let solved: Irr<f64> = initial_bounds.solve(cash_flows.iter(), &config);
+        let expected: Irr<f64> = bisection_with_config(
+            cash_flows.iter(),
+            &config.with_bracket(BracketStrategy::Bounds(
+                initial_bounds.rate_low(),
+                initial_bounds.rate_high(),
+            )),
+        );
+
+        assert!(solved.approx_eq(&expected, 0.0));
+        assert_eq!(
+            solved.npv_evaluations(),
+            initial_bounds.npv_evaluations() + expected.npv_evaluations()
+        );
+    }
+
+    #[test]
+    fn it_does_not_solve_from_invalid_bounds() {
+        let cash_flows: Vec<f32> = vec![100.0, 60.0, 60.0];
+        let initial_bounds: InitialBounds<f32> = determine(cash_flows.iter(), 0.10, 10);
+        let solved: Irr<f32> = initial_bounds.solve(cash_flows.iter(), &BisectionConfig::new());
+
+        assert!(!solved.is_valid());
+        assert!(solved.irr().is_nan());
+        assert_eq!(
+            solved.failure_reason(),
+            Some(FailureReason::BracketNotFound)
+        );
+        assert_eq!(solved.npv_evaluations(), initial_bounds.npv_evaluations());
+    }
+
+    #[test]
+    fn it_accepts_a_guess_that_is_already_a_root() {
+        let cash_flows: Vec<f64> = vec![-100.0, 110.0001];
+        let initial_bounds: InitialBounds<f64> = determine(cash_flows.iter(), 0.10, 100);
+        let solved: Irr<f64> = initial_bounds.solve(cash_flows.iter(), &BisectionConfig::new());
+
+        assert!(initial_bounds.is_valid());
+        assert_eq!(initial_bounds.rate_low(), initial_bounds.rate_high());
+        assert!(solved.is_valid());
+        assert_eq!(solved.irr(), 0.10);
+        assert_eq!(solved.npv(), initial_bounds.npv_rate_low());
+        assert_eq!(solved.iterations_run(), 0);
+        assert_eq!(solved.npv_evaluations(), initial_bounds.npv_evaluations());
+    }
+}
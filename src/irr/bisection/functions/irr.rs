@@ -1,15 +1,30 @@
 //! Calculate the IRR of a series of cash flows with the bisection method.
 
 use num::{abs, Float, Signed};
+use std::borrow::Borrow;
+use std::cell::Cell;
 use std::iter::{Product, Sum};
+use std::ops::ControlFlow;
 
+use crate::cash_flows::scale;
 use crate::irr::bisection::constants::NPV_PRECISION;
 use crate::irr::bisection::functions::are_equal_enough;
+use crate::irr::bisection::functions::initial_bounds;
+use crate::irr::bisection::functions::initial_bounds::determine_with_npv_and_tolerance;
 use crate::irr::bisection::functions::midpoint;
+use crate::irr::bisection::structs::bracket_hint::BracketHint;
+use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy, Convergence};
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::initial_bounds::InitialBounds;
 use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::bisection::structs::iteration::Iteration;
+use crate::irr::error::IrrError;
+use crate::irr::evaluations::counted;
 use crate::present_value::from_cash_flows_and_discount_rate as pv;
-use std::fmt::{Debug, Display};
-use std::slice::Iter;
+use crate::present_value::from_cash_flows_with_stub_period as stub_period_pv;
+use crate::present_value::from_cash_flows_with_terminal_growth as terminal_growth_pv;
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+use crate::present_value::from_sparse_cash_flows_and_discount_rate as sparse_pv;
 
 /// An implementation of the bisection root finding algorithm for calculating the IRR of a series of cash flows.
 ///
@@ -18,6 +33,8 @@ use std::slice::Iter;
 ///
 /// # Comments
 /// A function for finding initial values may be added soon.
+/// The cash flows can be any collection or iterator that can be cloned, as each NPV evaluation iterates over them again e.g. `cash_flows.iter()`, `&cash_flows` or a `VecDeque`.
+/// A series with more than one sign change may have several IRRs, of which only one in the bracket is returned; see `irr::multiplicity::inspect`.
 ///
 /// # Example with f32
 /// ```
@@ -28,10 +45,10 @@ use std::slice::Iter;
 /// let cash_flows: Vec<f32> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,];
 /// let rate_low: f32 = 0.05;
 /// let rate_high: f32 = 0.18;
-/// let iteration_limit: i16 = 100;
-/// let calculated_irr: Irr<f32> = irr(cash_flows.iter(), &rate_low, &rate_high, &iteration_limit);
+/// let iteration_limit: u32 = 100;
+/// let calculated_irr: Irr<f32> = irr(cash_flows.iter(), rate_low, rate_high, iteration_limit);
 /// assert!(calculated_irr.is_valid());
-/// assert!(calculated_irr.get_npv() <= NPV_PRECISION);
+/// assert!(calculated_irr.npv() <= NPV_PRECISION);
 /// ```
 ///
 /// # Example with f64
@@ -43,46 +60,563 @@ use std::slice::Iter;
 /// let cash_flows: Vec<f64> = vec![-122.3990963, 24.26782424, -18.61877741, -2.555946884, -8.814622596, 32.05035057, 12.11973328, 7.743486592, 9.158469173, -21.97032692, 11.18895709];
 /// let rate_low: f64 = -0.25;
 /// let rate_high: f64 = 0.25;
-/// let iteration_limit: i16 = 100;
-/// let calculated_irr: Irr<f64> = irr(cash_flows.iter(), &rate_low, &rate_high, &iteration_limit);
+/// let iteration_limit: u32 = 100;
+/// let calculated_irr: Irr<f64> = irr(cash_flows.iter(), rate_low, rate_high, iteration_limit);
 /// assert!(calculated_irr.is_valid());
-/// assert!(calculated_irr.get_npv() <= f64::from(NPV_PRECISION));
+/// assert!(calculated_irr.npv() <= f64::from(NPV_PRECISION));
 /// ```
-pub fn bisection<T>(
-    cash_flows: Iter<T>,
-    rate_low_guess: &T,
-    rate_high_guess: &T,
-    iteration_limit: &i16,
+pub fn bisection<T, I, B>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
 ) -> Irr<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
 {
-    let mut rate_low: T = *rate_low_guess;
-    let mut rate_high: T = *rate_high_guess;
+    bisection_with_npv(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// The bisection method on a slice of cash flows, which each NPV evaluation indexes directly rather than cloning an iterator.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::{bisection, bisection_from_slice};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// assert_eq!(
+///     bisection_from_slice(&cash_flows, 0.0, 0.5, 100).irr(),
+///     bisection(cash_flows.iter(), 0.0, 0.5, 100).irr()
+/// );
+/// ```
+pub fn bisection_from_slice<T>(
+    cash_flows: &[T],
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    bisection_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// The bisection method with a caller-supplied tolerance for how close to zero the NPV must be, in the currency of the cash flows.
+///
+/// # Comments
+/// `bisection` uses `NPV_PRECISION`, which is too tight for cash flows in the billions and too loose for ones in fractions of a cent.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::irr::{bisection, bisection_with_tolerance};
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // f32 cannot resolve an NPV of billions to within NPV_PRECISION
+/// let cash_flows: Vec<f32> = vec![-1_000_000_000.0, 600_000_000.0, 600_000_000.0];
+/// let default_tolerance: Irr<f32> = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+/// let scaled_tolerance: Irr<f32> =
+///     bisection_with_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 1_000.0);
+/// assert!(!default_tolerance.is_valid());
+/// assert!(scaled_tolerance.is_valid());
+/// assert!((scaled_tolerance.irr() - 0.130_662).abs() < 0.000_01);
+/// ```
+pub fn bisection_with_tolerance<T, I, B>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    npv_tolerance: T,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    bisection_with_npv_and_tolerances(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        npv_tolerance,
+        None,
+        T::zero(),
+        &mut |_| ControlFlow::Continue(()),
+    )
+}
+
+/// The bisection method, stopping once the bracket is narrower than `rate_tolerance` as well as when the NPV is within `NPV_PRECISION` of zero.
+///
+/// # Comments
+/// Where the NPV is steep in the rate, e.g. for large or long-dated cash flows, the NPV may never come within `NPV_PRECISION` of zero, or only at a rate more precise than needed.
+/// The IRR is valid if either criterion is met, since the bracket always contains a sign change of the NPV.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_rate_tolerance;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<f64> = vec![-1_000_000_000.0, 600_000_000.0, 600_000_000.0];
+/// let calculated_irr: Irr<f64> =
+///     bisection_with_rate_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 0.000_001);
+/// assert!(calculated_irr.is_valid());
+/// assert!(calculated_irr.iterations_run() <= 19);
+/// assert!((calculated_irr.irr() - 0.130_662).abs() < 0.000_001);
+/// ```
+pub fn bisection_with_rate_tolerance<T, I, B>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    rate_tolerance: T,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    bisection_with_npv_and_tolerances(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        T::from(NPV_PRECISION).unwrap(),
+        Some(rate_tolerance),
+        T::zero(),
+        &mut |_| ControlFlow::Continue(()),
+    )
+}
+
+/// The bisection method with its parameters gathered in a `BisectionConfig`, including how to find the bracket.
+///
+/// # Comments
+/// The NPV evaluations of any bracket search or hint check are included in the count of the result.
+/// If the configuration normalizes, the cash flows are divided by their scale before solving and the NPVs of the result are multiplied by it.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_config;
+/// use time_value::irr::bisection::structs::config::BisectionConfig;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // the default configuration searches for the bracket
+/// let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+/// let calculated_irr: Irr<f32> = bisection_with_config(cash_flows.iter(), &BisectionConfig::new());
+/// assert!(calculated_irr.is_valid());
+///
+/// // in f32 the NPV of billions cannot be found to within a tenth of a cent, but that of the rescaled cash flows can
+/// let cash_flows: Vec<f32> = vec![-1.0e9, 6.0e8, 6.0e8];
+/// let unscaled: Irr<f32> = bisection_with_config(cash_flows.iter(), &BisectionConfig::new());
+/// assert!(!unscaled.is_valid());
+///
+/// let calculated_irr: Irr<f32> =
+///     bisection_with_config(cash_flows.iter(), &BisectionConfig::new().with_normalize(true));
+/// assert!(calculated_irr.is_valid());
+/// assert_eq!(calculated_irr.scale(), Some(1.0e9));
+/// assert!((calculated_irr.irr() - 0.130_662).abs() < 0.001);
+/// ```
+pub fn bisection_with_config<T, I, B>(cash_flows: I, config: &BisectionConfig<T>) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    if config.normalize() {
+        let cash_flows: Vec<T> = cash_flows
+            .into_iter()
+            .map(|cash_flow| *cash_flow.borrow())
+            .collect();
+        let scale: T = scale(cash_flows.iter());
+        let rescaled: Vec<T> = cash_flows
+            .iter()
+            .map(|cash_flow| *cash_flow / scale)
+            .collect();
+        return bisection_with_config(rescaled.iter(), &config.with_normalize(false))
+            .with_scale(scale);
+    }
+
+    let npv_at = |rate: T| pv(cash_flows.clone(), rate);
+    let rate_tolerance: Option<T> = match config.convergence() {
+        Convergence::Npv => None,
+        Convergence::NpvOrRateInterval(rate_tolerance) => Some(rate_tolerance),
+    };
+    let solve = |rate_low: T, rate_high: T, prior_npv_evaluations: u32| {
+        let mut history: Vec<Iteration<T>> = Vec::new();
+        let irr: Irr<T> = bisection_with_npv_and_tolerances(
+            npv_at,
+            rate_low,
+            rate_high,
+            config.iteration_limit(),
+            config.npv_tolerance(),
+            rate_tolerance,
+            T::zero(),
+            &mut |iteration| {
+                if config.record_history() {
+                    history.push(*iteration);
+                }
+                ControlFlow::Continue(())
+            },
+        );
+        let irr: Irr<T> = irr.with_npv_evaluations(prior_npv_evaluations + irr.npv_evaluations());
+        if config.record_history() {
+            irr.with_history(history)
+        } else {
+            irr
+        }
+    };
+    let search = |rate_guess: T, prior_npv_evaluations: u32| {
+        let initial_bounds: InitialBounds<T> = determine_with_npv_and_tolerance(
+            npv_at,
+            rate_guess,
+            config.iteration_limit(),
+            config.npv_tolerance(),
+        );
+        solve(
+            initial_bounds.rate_low(),
+            initial_bounds.rate_high(),
+            prior_npv_evaluations + initial_bounds.npv_evaluations(),
+        )
+    };
 
-    let mut npv_rate_low: T = pv(cash_flows.clone(), &rate_low);
-    let mut npv_rate_high: T = pv(cash_flows.clone(), &rate_high);
+    match config.bracket() {
+        BracketStrategy::Bounds(rate_low, rate_high) => solve(rate_low, rate_high, 0),
+        BracketStrategy::Search(rate_guess) => search(rate_guess, 0),
+        BracketStrategy::Hint(hint, rate_guess) => {
+            let rate_low: T = hint.rate_low();
+            let rate_high: T = hint.rate_high();
+            if npv_at(rate_low) * npv_at(rate_high) <= T::zero() {
+                solve(rate_low, rate_high, 2)
+            } else {
+                search(rate_guess, 2)
+            }
+        }
+    }
+}
+
+/// The bisection method, calling `observer` with the state of each iteration e.g. to report progress or to stop early.
+///
+/// # Comments
+/// The observer is first called with iteration 0, the initial bracket and its midpoint, and then after every iteration.
+/// Returning `ControlFlow::Break(())` stops the bisection; unless the NPV is already within `NPV_PRECISION` of zero the result is then invalid, with the failure reason `FailureReason::Stopped`.
+///
+/// # Example with f64
+/// ```
+/// use std::ops::ControlFlow;
+/// use time_value::irr::bisection::functions::irr::bisection_with_observer;
+/// use time_value::irr::bisection::structs::failure_reason::FailureReason;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let mut widths: Vec<f64> = vec![];
+/// let calculated_irr: Irr<f64> = bisection_with_observer(cash_flows.iter(), 0.05, 0.18, 100, |iteration| {
+///     widths.push(iteration.rate_high() - iteration.rate_low());
+///     ControlFlow::Continue(())
+/// });
+/// assert!(calculated_irr.is_valid());
+/// assert_eq!(widths.len() as u32, calculated_irr.iterations_run() + 1);
+///
+/// // stop once the bracket is narrower than one basis point
+/// let stopped: Irr<f64> = bisection_with_observer(cash_flows.iter(), 0.05, 0.18, 100, |iteration| {
+///     if iteration.rate_high() - iteration.rate_low() < 0.000_1 {
+///         ControlFlow::Break(())
+///     } else {
+///         ControlFlow::Continue(())
+///     }
+/// });
+/// assert_eq!(stopped.failure_reason(), Some(FailureReason::Stopped));
+/// assert!((stopped.irr() - 0.0928).abs() < 0.000_1);
+/// ```
+pub fn bisection_with_observer<T, I, B, O>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    mut observer: O,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+    O: FnMut(&Iteration<T>) -> ControlFlow<()>,
+{
+    bisection_with_npv_and_tolerances(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        T::from(NPV_PRECISION).unwrap(),
+        None,
+        T::zero(),
+        &mut observer,
+    )
+}
+
+/// The bisection method, returning an error that explains why no IRR was found rather than an invalid `Irr`.
+///
+/// # Comments
+/// The inputs are checked before solving: there must be at least one cash flow, every cash flow and guess must be finite, and the iteration limit must be positive.
+/// Otherwise the result is `bisection(...).into_result()`; see `Irr::error`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::try_bisection;
+/// use time_value::irr::error::IrrError;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr = try_bisection(cash_flows.iter(), 0.05, 0.18, 100).unwrap();
+/// assert!((calculated_irr.irr() - 0.0928).abs() < 0.000_1);
+///
+/// let result = try_bisection(cash_flows.iter(), 0.05, 0.18, 2);
+/// assert_eq!(result.unwrap_err(), IrrError::IterationLimitReached);
+/// ```
+pub fn try_bisection<T, I, B>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Result<Irr<T>, IrrError>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    if cash_flows.clone().into_iter().next().is_none() {
+        return Err(IrrError::InvalidInput("there are no cash flows"));
+    }
+    if !cash_flows
+        .clone()
+        .into_iter()
+        .all(|cash_flow| cash_flow.borrow().is_finite())
+    {
+        return Err(IrrError::InvalidInput("a cash flow is not finite"));
+    }
+    if !rate_low_guess.is_finite() || !rate_high_guess.is_finite() {
+        return Err(IrrError::InvalidInput("a rate guess is not finite"));
+    }
+    if iteration_limit == 0 {
+        return Err(IrrError::InvalidInput("the iteration limit is zero"));
+    }
+
+    bisection(cash_flows, rate_low_guess, rate_high_guess, iteration_limit).into_result()
+}
+
+/// The bisection method for a series of sparse cash flows, given as `(period, cash flow)` pairs.
+///
+/// # Comments
+/// Periods without a cash flow are skipped rather than discounted as zeros, so long horizons with few cash flows are cheap to solve.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_sparse;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<(u32, f64)> = vec![(0, -100.0), (30, 1_000.0)];
+/// let calculated_irr: Irr<f64> = bisection_sparse(cash_flows.iter(), 0.0, 0.5, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.0798).abs() < 0.0001);
+/// ```
+pub fn bisection_sparse<T, I, B>(
+    cash_flows: I,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<(u32, T)>,
+{
+    bisection_with_npv(
+        |rate| sparse_pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// The bisection method for a series of cash flows whose last cash flow grows at `growth` in perpetuity; see `present_value::from_cash_flows_with_terminal_growth`.
+///
+/// # Comments
+/// The NPV is infinite at rates at or below `growth`, so `rate_low_guess` may be set to `growth` when the last cash flow is positive.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_terminal_growth;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // paying 100 for 5 next period growing at 5% forever returns 10%
+/// let cash_flows: Vec<f64> = vec![-100.0, 5.0];
+/// let calculated_irr: Irr<f64> =
+///     bisection_with_terminal_growth(cash_flows.iter(), 0.05, 0.05, 1.0, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.10).abs() < 0.000_001);
+/// ```
+pub fn bisection_with_terminal_growth<T, I, B>(
+    cash_flows: I,
+    growth: T,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    bisection_with_npv(
+        |rate| terminal_growth_pv(cash_flows.clone(), growth, rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// The bisection method for a series of cash flows whose first period is a stub of `first_period_fraction` of a full period; see `present_value::from_cash_flows_with_stub_period`.
+///
+/// # Comments
+/// The IRR is a rate per full period.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_stub_period;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// // valued half way through the year, 110 at the next year end returns 10% a year on 100 * 1.1^0.5
+/// let cash_flows: Vec<f64> = vec![-100.0 * 1.10_f64.powf(0.5), 110.0];
+/// let calculated_irr: Irr<f64> = bisection_with_stub_period(cash_flows.iter(), 0.5, 0.0, 1.0, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.10).abs() < 0.000_1);
+/// ```
+pub fn bisection_with_stub_period<T, I, B>(
+    cash_flows: I,
+    first_period_fraction: T,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    bisection_with_npv(
+        |rate| stub_period_pv(cash_flows.clone(), rate, first_period_fraction),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+pub(crate) fn bisection_with_npv<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    bisection_with_npv_and_tolerances(
+        npv_at,
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        T::from(NPV_PRECISION).unwrap(),
+        None,
+        T::zero(),
+        &mut |_| ControlFlow::Continue(()),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn bisection_with_npv_and_tolerances<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    npv_tolerance: T,
+    rate_tolerance: Option<T>,
+    relative_rate_tolerance: T,
+    observer: &mut dyn FnMut(&Iteration<T>) -> ControlFlow<()>,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    let is_narrow_enough = |rate_low: &T, rate_high: &T| {
+        is_narrow_enough(
+            *rate_low,
+            *rate_high,
+            rate_tolerance,
+            relative_rate_tolerance,
+        )
+    };
+
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "bisection",
+        rate_low_guess = crate::trace::value(rate_low_guess),
+        rate_high_guess = crate::trace::value(rate_high_guess),
+        iteration_limit = iteration_limit,
+        npv_tolerance = crate::trace::value(npv_tolerance),
+    )
+    .entered();
+
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let mut rate_low: T = rate_low_guess;
+    let mut rate_high: T = rate_high_guess;
+
+    let mut npv_rate_low: T = npv_at(rate_low);
+    let mut npv_rate_high: T = npv_at(rate_high);
 
     if T::zero() < npv_rate_low * npv_rate_high {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            npv_rate_low = crate::trace::value(npv_rate_low),
+            npv_rate_high = crate::trace::value(npv_rate_high),
+            "the NPVs at the bounds have the same sign"
+        );
         return Irr::new(
             rate_low,
             npv_rate_low,
             rate_high,
             npv_rate_high,
-            *iteration_limit,
+            iteration_limit,
             0,
             T::nan(),
             T::nan(),
             false,
-        );
+        )
+        .with_npv_evaluations(npv_evaluations.get())
+        .with_failure_reason(FailureReason::BracketNotFound);
     }
 
-    let mut irr: T = midpoint::calculate(&rate_low, &rate_high);
-    let mut npv: T = pv(cash_flows.clone(), &irr);
-    let mut iterations_run: i16 = 0;
-    let precision: T = T::from(NPV_PRECISION).unwrap();
-
-    while iterations_run < *iteration_limit && !are_equal_enough::is_true(&precision, &npv) {
+    let mut irr: T = midpoint::calculate(rate_low, rate_high);
+    let mut npv: T = npv_at(irr);
+    let mut iterations_run: u32 = 0;
+    let mut is_stopped: bool =
+        observer(&Iteration::new(0, rate_low, rate_high, irr, npv)).is_break();
+    while !is_stopped
+        && iterations_run < iteration_limit
+        && !are_equal_enough::is_true_with_tolerances(npv, T::zero(), npv_tolerance, T::epsilon())
+        && !is_narrow_enough(&rate_low, &rate_high)
+        // once the bounds are adjacent values of T, bisecting cannot narrow them further
+        && !are_equal_enough::is_true_within_ulps(rate_low, rate_high, 1)
+    {
         iterations_run += 1;
 
         if npv_rate_low * npv < T::zero() {
@@ -93,24 +627,171 @@ where
             npv_rate_low = npv;
         }
 
-        irr = midpoint::calculate(&rate_low, &rate_high);
-        npv = pv(cash_flows.clone(), &irr);
+        irr = midpoint::calculate(rate_low, rate_high);
+        npv = npv_at(irr);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            iterations_run,
+            rate_low = crate::trace::value(rate_low),
+            rate_high = crate::trace::value(rate_high),
+            irr = crate::trace::value(irr),
+            npv = crate::trace::value(npv),
+            "bisection iteration"
+        );
+        is_stopped = observer(&Iteration::new(
+            iterations_run,
+            rate_low,
+            rate_high,
+            irr,
+            npv,
+        ))
+        .is_break();
     }
 
-    Irr::new(
+    let calculated_irr: Irr<T> = Irr::new(
         rate_low,
         npv_rate_low,
         rate_high,
         npv_rate_high,
-        *iteration_limit,
+        iteration_limit,
         iterations_run,
         irr,
         npv,
-        abs(npv) <= T::from(NPV_PRECISION).unwrap(),
+        abs(npv) <= npv_tolerance || is_narrow_enough(&rate_low, &rate_high),
+    )
+    .with_npv_evaluations(npv_evaluations.get());
+
+    let calculated_irr: Irr<T> = match calculated_irr.failure_reason() {
+        None => calculated_irr,
+        Some(_) if !npv.is_finite() => {
+            calculated_irr.with_failure_reason(FailureReason::NonFiniteNpv)
+        }
+        Some(_) if is_stopped => calculated_irr.with_failure_reason(FailureReason::Stopped),
+        Some(_) if are_equal_enough::is_true_within_ulps(rate_low, rate_high, 1) => {
+            calculated_irr.with_failure_reason(FailureReason::BracketExhausted)
+        }
+        Some(_) => calculated_irr.with_failure_reason(FailureReason::MaxIterations),
+    };
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        iterations_run,
+        irr = crate::trace::value(irr),
+        npv = crate::trace::value(npv),
+        is_valid = calculated_irr.is_valid(),
+        failure_reason = ?calculated_irr.failure_reason(),
+        "bisection finished"
+    );
+    calculated_irr
+}
+
+/// Whether a bracket is narrower than `rate_tolerance`, or than `relative_rate_tolerance` times the larger magnitude of its bounds; a relative tolerance of zero is not checked.
+pub(crate) fn is_narrow_enough<T>(
+    rate_low: T,
+    rate_high: T,
+    rate_tolerance: Option<T>,
+    relative_rate_tolerance: T,
+) -> bool
+where
+    T: Float,
+{
+    let width: T = (rate_high - rate_low).abs();
+    let is_within_absolute: bool = match rate_tolerance {
+        Some(rate_tolerance) => width <= rate_tolerance,
+        None => false,
+    };
+    let is_within_relative: bool = T::zero() < relative_rate_tolerance
+        && width <= relative_rate_tolerance * rate_low.abs().max(rate_high.abs());
+    is_within_absolute || is_within_relative
+}
+
+/// Re-solves the IRR of a series of cash flows, warm-starting from the result for a similar series e.g. the same series before one more month of data was added.
+///
+/// # Comments
+/// The bracket of `previous` is used if it still contains a sign change of the NPV; otherwise new bounds are searched for outwards from the IRR of `previous` with `initial_bounds::determine`.
+/// A converged bracket is narrow, so when the cash flows change only slightly the search starts much closer to the new IRR than a cold start would.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::{bisection, resolve};
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let mut cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let iteration_limit: u32 = 100;
+/// let previous: Irr<f64> = bisection(cash_flows.iter(), 0.05, 0.18, iteration_limit);
+///
+/// cash_flows.push(10.0);
+/// let resolved: Irr<f64> = resolve(&previous, cash_flows.iter(), iteration_limit);
+/// assert!(resolved.is_valid());
+/// assert!(resolved.irr() > previous.irr());
+/// ```
+pub fn resolve<T, I, B>(previous: &Irr<T>, cash_flows: I, iteration_limit: u32) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    let hint: BracketHint<T> = BracketHint::new(previous.rate_low(), previous.rate_high());
+    with_hint(cash_flows, Some(&hint), previous.irr(), iteration_limit)
+}
+
+/// Calculates the IRR of a series of cash flows, trying a bracket hint before falling back to searching for initial bounds.
+///
+/// # Comments
+/// The hint is used if its rates have NPVs of opposite signs; otherwise, or without a hint, bounds are searched for outwards from `rate_guess` with `initial_bounds::determine`.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::irr::with_hint;
+/// use time_value::irr::bisection::structs::bracket_hint::BracketHint;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<f32> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let stale_hint: BracketHint<f32> = BracketHint::new(0.20, 0.30);
+/// let calculated_irr: Irr<f32> = with_hint(cash_flows.iter(), Some(&stale_hint), 0.10, 100);
+/// assert!(calculated_irr.is_valid());
+/// ```
+pub fn with_hint<T, I, B>(
+    cash_flows: I,
+    hint: Option<&BracketHint<T>>,
+    rate_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    let mut hint_npv_evaluations: u32 = 0;
+    if let Some(hint) = hint {
+        let npv_rate_low: T = pv(cash_flows.clone(), hint.rate_low());
+        let npv_rate_high: T = pv(cash_flows.clone(), hint.rate_high());
+        hint_npv_evaluations = 2;
+        if npv_rate_low * npv_rate_high <= T::zero() {
+            let irr: Irr<T> = bisection(
+                cash_flows,
+                hint.rate_low(),
+                hint.rate_high(),
+                iteration_limit,
+            );
+            return irr.with_npv_evaluations(hint_npv_evaluations + irr.npv_evaluations());
+        }
+    }
+
+    let initial_bounds: InitialBounds<T> =
+        initial_bounds::determine(cash_flows.clone(), rate_guess, iteration_limit);
+    let irr: Irr<T> = bisection(
+        cash_flows,
+        initial_bounds.rate_low(),
+        initial_bounds.rate_high(),
+        iteration_limit,
+    );
+    irr.with_npv_evaluations(
+        hint_npv_evaluations + initial_bounds.npv_evaluations() + irr.npv_evaluations(),
     )
 }
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod bisection_tests {
     use crate::irr::bisection::functions::initial_bounds;
     use crate::irr::bisection::functions::irr::{bisection as irr, Irr, NPV_PRECISION};
@@ -120,12 +801,12 @@ mod bisection_tests {
     use rand::distributions::uniform::SampleUniform;
     use rand::prelude::ThreadRng;
     use rand::{thread_rng, Rng};
-    use std::fmt::{Debug, Display};
+
     use std::iter::{Product, Sum};
 
-    fn generate_random_cash_flows<T>(thread_range: &mut ThreadRng, vector_size: &i16) -> Vec<T>
+    fn generate_random_cash_flows<T>(thread_range: &mut ThreadRng, vector_size: usize) -> Vec<T>
     where
-        T: Float + Product<T> + Sum<T> + Signed + Display + Debug + SampleUniform,
+        T: Float + Product<T> + Sum<T> + Signed + SampleUniform,
     {
         //ensure the first element is negative
         let mut cash_flows: Vec<T> =
@@ -140,38 +821,74 @@ mod bisection_tests {
     #[test]
     fn it_works_with_random_inputs() {
         let mut thread_range: ThreadRng = thread_rng();
-        let vector_size: i16 = 20;
+        let vector_size: usize = 20;
         let rate_guess: f32 = 0.05;
-        let iteration_limit: i16 = 1_000;
-        let mut cash_flows: Vec<f32> = generate_random_cash_flows(&mut thread_range, &vector_size);
+        let iteration_limit: u32 = 1_000;
+        let mut cash_flows: Vec<f32> = generate_random_cash_flows(&mut thread_range, vector_size);
 
         for _ in 0..100 {
             let initial_bounds: InitialBounds<f32> =
-                initial_bounds::determine(cash_flows.iter(), &rate_guess, &iteration_limit);
+                initial_bounds::determine(cash_flows.iter(), rate_guess, iteration_limit);
 
             if initial_bounds.is_valid() {
                 let calculated_irr: Irr<f32> = irr(
                     cash_flows.iter(),
-                    &initial_bounds.get_rate_low(),
-                    &initial_bounds.get_rate_high(),
-                    &iteration_limit,
+                    initial_bounds.rate_low(),
+                    initial_bounds.rate_high(),
+                    iteration_limit,
                 );
 
                 if calculated_irr.is_valid() {
-                    assert!(calculated_irr.get_npv() <= NPV_PRECISION);
+                    assert!(calculated_irr.npv() <= NPV_PRECISION);
                 } else {
-                    if calculated_irr.get_iterations_run() == 0 {
-                        assert!(calculated_irr.get_irr().is_nan())
+                    if calculated_irr.iterations_run() == 0 {
+                        assert!(calculated_irr.irr().is_nan())
                     } else {
-                        assert!(calculated_irr.get_iterations_run() <= iteration_limit)
+                        assert!(calculated_irr.iterations_run() <= iteration_limit)
                     }
                 }
             }
 
-            cash_flows = generate_random_cash_flows(&mut thread_range, &vector_size);
+            cash_flows = generate_random_cash_flows(&mut thread_range, vector_size);
         }
     }
 
+    #[test]
+    fn it_counts_one_npv_evaluation_per_iteration() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr: Irr<f64> = irr(cash_flows.iter(), 0.0, 0.5, 100);
+
+        assert_eq!(
+            calculated_irr.npv_evaluations(),
+            3 + calculated_irr.iterations_run()
+        );
+    }
+
+    #[test]
+    fn it_accepts_other_collections() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let deque: std::collections::VecDeque<f64> = cash_flows.iter().copied().collect();
+        let expected: f64 = irr(cash_flows.iter(), 0.0, 0.5, 100).irr();
+
+        assert_eq!(irr(&deque, 0.0, 0.5, 100).irr(), expected);
+        assert_eq!(
+            irr(
+                cash_flows.iter().map(|cash_flow| cash_flow * 1.0),
+                0.0,
+                0.5,
+                100
+            )
+            .irr(),
+            expected
+        );
+        let initial_bounds: InitialBounds<f64> = initial_bounds::determine(&deque, 0.10, 100);
+        assert!(initial_bounds.is_valid());
+        assert_eq!(
+            initial_bounds::determine_from_slice(&cash_flows, 0.10, 100).rate_low(),
+            initial_bounds.rate_low()
+        );
+    }
+
     #[test]
     fn it_works_on_known_example_0_f32() {
         let cash_flows: Vec<f32> = vec![
@@ -179,16 +896,16 @@ mod bisection_tests {
         ];
         let rate_low_guess: f32 = 0.05;
         let rate_high_guess: f32 = 0.18;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let irr_approximation: Irr<f32> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
         );
 
         assert!(irr_approximation.is_valid());
-        assert!(irr_approximation.get_npv() <= NPV_PRECISION);
+        assert!(irr_approximation.npv() <= NPV_PRECISION);
     }
 
     #[test]
@@ -198,16 +915,16 @@ mod bisection_tests {
         ];
         let rate_low_guess: f64 = 0.05;
         let rate_high_guess: f64 = 0.18;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let calculated_irr: Irr<f64> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
         );
 
         assert!(calculated_irr.is_valid());
-        assert!(calculated_irr.get_npv() <= f64::from(NPV_PRECISION));
+        assert!(calculated_irr.npv() <= f64::from(NPV_PRECISION));
     }
 
     #[test]
@@ -227,16 +944,16 @@ mod bisection_tests {
         ];
         let rate_low_guess: f32 = 0.01;
         let rate_high_guess: f32 = 0.05;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let calculated_irr: Irr<f32> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
         );
 
         assert!(calculated_irr.is_valid());
-        assert!(calculated_irr.get_npv() <= NPV_PRECISION);
+        assert!(calculated_irr.npv() <= NPV_PRECISION);
     }
 
     #[test]
@@ -256,16 +973,16 @@ mod bisection_tests {
         ];
         let rate_low_guess: f64 = 0.01;
         let rate_high_guess: f64 = 0.05;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let calculated_irr: Irr<f64> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
         );
 
         assert!(calculated_irr.is_valid());
-        assert!(calculated_irr.get_npv() <= f64::from(NPV_PRECISION));
+        assert!(calculated_irr.npv() <= f64::from(NPV_PRECISION));
     }
 
     #[test]
@@ -285,16 +1002,16 @@ mod bisection_tests {
         ];
         let rate_low_guess: f32 = -0.25;
         let rate_high_guess: f32 = 0.25;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let calculated_irr: Irr<f32> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
         );
 
         assert!(calculated_irr.is_valid());
-        assert!(calculated_irr.get_npv() <= NPV_PRECISION);
+        assert!(calculated_irr.npv() <= NPV_PRECISION);
     }
 
     #[test]
@@ -314,15 +1031,457 @@ mod bisection_tests {
         ];
         let rate_low_guess: f64 = -0.25;
         let rate_high_guess: f64 = 0.25;
-        let iteration_limit: i16 = 100;
+        let iteration_limit: u32 = 100;
         let calculated_irr: Irr<f64> = irr(
             cash_flows.iter(),
-            &rate_low_guess,
-            &rate_high_guess,
-            &iteration_limit,
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
+        );
+
+        assert!(calculated_irr.is_valid());
+        assert!(calculated_irr.npv() <= f64::from(NPV_PRECISION));
+    }
+}
+
+#[cfg(test)]
+mod bisection_with_tolerance_tests {
+    use crate::irr::bisection::functions::irr::{bisection, bisection_with_tolerance};
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+    use crate::irr::error::IrrError;
+
+    #[test]
+    fn it_matches_bisection_with_the_default_tolerance() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let default_irr = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+        let tolerance_irr = bisection_with_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 0.001);
+
+        assert_eq!(default_irr.irr(), tolerance_irr.irr());
+        assert_eq!(default_irr.is_valid(), tolerance_irr.is_valid());
+    }
+
+    #[test]
+    fn it_stops_once_the_bounds_are_adjacent() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr = bisection_with_tolerance(cash_flows.iter(), 0.0, 0.5, 1_000, 0.0);
+
+        assert!(calculated_irr.iterations_run() < 100);
+        assert_eq!(
+            calculated_irr.rate_low().to_bits() + 1,
+            calculated_irr.rate_high().to_bits()
         );
+        assert!(!calculated_irr.is_valid());
+        assert_eq!(
+            calculated_irr.failure_reason(),
+            Some(FailureReason::BracketExhausted)
+        );
+        assert_eq!(calculated_irr.error(), Some(IrrError::NotConverged));
+    }
+
+    #[test]
+    fn it_applies_a_tighter_tolerance() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr = bisection_with_tolerance(cash_flows.iter(), 0.0, 0.5, 16, 0.000_001);
+
+        assert!(bisection(cash_flows.iter(), 0.0, 0.5, 16).is_valid());
+        assert!(!calculated_irr.is_valid());
+    }
+}
+
+#[cfg(test)]
+mod bisection_with_rate_tolerance_tests {
+    use crate::irr::bisection::functions::irr::{bisection, bisection_with_rate_tolerance};
+
+    #[test]
+    fn it_stops_once_the_bracket_is_narrow_enough() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr = bisection_with_rate_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 0.01);
 
         assert!(calculated_irr.is_valid());
-        assert!(calculated_irr.get_npv() <= f64::from(NPV_PRECISION));
+        assert!(calculated_irr.rate_high() - calculated_irr.rate_low() <= 0.01);
+        assert!(
+            calculated_irr.iterations_run()
+                < bisection(cash_flows.iter(), 0.0, 0.5, 100).iterations_run()
+        );
+    }
+
+    #[test]
+    fn it_is_invalid_if_the_limit_is_reached_first() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr =
+            bisection_with_rate_tolerance(cash_flows.iter(), 0.0, 0.5, 3, 0.000_001);
+
+        assert!(!calculated_irr.is_valid());
+    }
+}
+
+#[cfg(test)]
+mod bisection_with_observer_tests {
+    use crate::irr::bisection::functions::irr::{bisection, bisection_with_observer};
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+    use std::ops::ControlFlow;
+
+    #[test]
+    fn it_matches_bisection_when_never_stopped() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let observed = bisection_with_observer(cash_flows.iter(), 0.0, 0.5, 100, |_| {
+            ControlFlow::Continue(())
+        });
+        let unobserved = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+
+        assert_eq!(observed.irr(), unobserved.irr());
+        assert_eq!(observed.npv_evaluations(), unobserved.npv_evaluations());
+    }
+
+    #[test]
+    fn it_stops_before_the_first_iteration() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let stopped =
+            bisection_with_observer(cash_flows.iter(), 0.0, 0.5, 100, |_| ControlFlow::Break(()));
+
+        assert_eq!(stopped.iterations_run(), 0);
+        assert_eq!(stopped.irr(), 0.25);
+        assert_eq!(stopped.failure_reason(), Some(FailureReason::Stopped));
+    }
+}
+
+#[cfg(test)]
+mod bisection_with_config_tests {
+    use crate::irr::bisection::functions::irr::{
+        bisection, bisection_with_config, bisection_with_rate_tolerance, with_hint,
+    };
+    use crate::irr::bisection::structs::bracket_hint::BracketHint;
+    use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy, Convergence};
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_matches_the_free_functions() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let hint: BracketHint<f64> = BracketHint::new(0.50, 0.60);
+        let bounds: BisectionConfig<f64> =
+            BisectionConfig::new().with_bracket(BracketStrategy::Bounds(0.0, 0.5));
+        let rate_interval: BisectionConfig<f64> =
+            bounds.with_convergence(Convergence::NpvOrRateInterval(0.01));
+        let hinted: BisectionConfig<f64> =
+            BisectionConfig::new().with_bracket(BracketStrategy::Hint(hint, 0.10));
+
+        let pairs: Vec<(Irr<f64>, Irr<f64>)> = vec![
+            (
+                bisection_with_config(cash_flows.iter(), &bounds),
+                bisection(cash_flows.iter(), 0.0, 0.5, 100),
+            ),
+            (
+                bisection_with_config(cash_flows.iter(), &rate_interval),
+                bisection_with_rate_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 0.01),
+            ),
+            (
+                bisection_with_config(cash_flows.iter(), &hinted),
+                with_hint(cash_flows.iter(), Some(&hint), 0.10, 100),
+            ),
+        ];
+        for (configured, free) in pairs.iter() {
+            assert_eq!(configured.irr(), free.irr());
+            assert_eq!(configured.npv_evaluations(), free.npv_evaluations());
+        }
+    }
+
+    #[test]
+    fn it_has_sane_defaults() {
+        let config: BisectionConfig<f32> = BisectionConfig::default();
+        assert_eq!(config.iteration_limit(), 100);
+        assert_eq!(config.convergence(), Convergence::Npv);
+        assert_eq!(config.bracket(), BracketStrategy::Search(0.10));
+        assert!(!config.record_history());
+    }
+
+    #[test]
+    fn it_records_the_history_only_when_asked() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let config: BisectionConfig<f64> =
+            BisectionConfig::new().with_bracket(BracketStrategy::Bounds(0.0, 0.5));
+
+        assert!(bisection_with_config(cash_flows.iter(), &config)
+            .history()
+            .is_none());
+
+        let calculated_irr: Irr<f64> =
+            bisection_with_config(cash_flows.iter(), &config.with_record_history(true));
+        let history = calculated_irr.history().unwrap();
+        assert_eq!(history.len() as u32, calculated_irr.iterations_run() + 1);
+        for (iteration, record) in history.iter().enumerate() {
+            assert_eq!(record.iteration(), iteration as u32);
+            assert!(record.rate_low() <= record.irr());
+            assert!(record.irr() <= record.rate_high());
+        }
+        let last = history.last().unwrap();
+        assert_eq!(last.irr(), calculated_irr.irr());
+        assert_eq!(last.npv(), calculated_irr.npv());
+    }
+
+    #[test]
+    fn it_reports_npvs_in_the_original_units_when_normalizing() {
+        let cash_flows: Vec<f64> = vec![-1e9, 6e8, 6e8];
+        let config: BisectionConfig<f64> = BisectionConfig::new()
+            .with_bracket(BracketStrategy::Bounds(0.0, 0.5))
+            .with_normalize(true)
+            .with_record_history(true);
+        let calculated_irr: Irr<f64> = bisection_with_config(cash_flows.iter(), &config);
+
+        assert_eq!(calculated_irr.scale(), Some(1e9));
+        assert!((calculated_irr.irr() - 0.130_662).abs() < 0.001);
+        assert!(calculated_irr.npv().abs() < 0.001 * 1e9);
+        assert!(calculated_irr.npv_rate_low() > 1e6);
+        assert_eq!(
+            calculated_irr.history().unwrap().last().unwrap().npv(),
+            calculated_irr.npv()
+        );
+        assert_eq!(
+            bisection_with_config(cash_flows.iter(), &config.with_normalize(false)).scale(),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod failure_reason_tests {
+    use crate::irr::bisection::functions::initial_bounds;
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+
+    #[test]
+    fn it_records_why_bisection_failed() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let nan_cash_flows: Vec<f64> = vec![-100.0, f64::NAN];
+
+        assert_eq!(
+            bisection(cash_flows.iter(), 0.5, 0.6, 100).failure_reason(),
+            Some(FailureReason::BracketNotFound)
+        );
+        assert_eq!(
+            bisection(cash_flows.iter(), 0.0, 0.5, 3).failure_reason(),
+            Some(FailureReason::MaxIterations)
+        );
+        assert_eq!(
+            bisection(nan_cash_flows.iter(), 0.0, 0.5, 100).failure_reason(),
+            Some(FailureReason::NonFiniteNpv)
+        );
+    }
+
+    #[test]
+    fn it_records_why_no_initial_bounds_were_found() {
+        let no_sign_change: Vec<f64> = vec![100.0, 60.0, 60.0];
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+
+        assert_eq!(
+            initial_bounds::determine(no_sign_change.iter(), 0.1, 5).failure_reason(),
+            Some(FailureReason::BracketNotFound)
+        );
+        assert_eq!(
+            initial_bounds::determine(cash_flows.iter(), 0.1, 100).failure_reason(),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod try_bisection_tests {
+    use crate::irr::bisection::functions::irr::try_bisection;
+    use crate::irr::error::IrrError;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn it_rejects_invalid_input() {
+        let no_cash_flows: Vec<f64> = vec![];
+        let infinite_cash_flow: Vec<f64> = vec![-100.0, f64::INFINITY];
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+
+        for result in [
+            try_bisection(no_cash_flows.iter(), 0.0, 0.5, 100),
+            try_bisection(infinite_cash_flow.iter(), 0.0, 0.5, 100),
+            try_bisection(cash_flows.iter(), f64::NAN, 0.5, 100),
+            try_bisection(cash_flows.iter(), 0.0, 0.5, 0),
+        ]
+        .iter()
+        {
+            assert!(matches!(result, Err(IrrError::InvalidInput(_))));
+        }
+    }
+
+    #[test]
+    fn it_reports_no_sign_change() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let result = try_bisection(cash_flows.iter(), 0.5, 0.6, 100);
+        assert_eq!(result.unwrap_err(), IrrError::NoSignChange);
+    }
+
+    #[test]
+    fn it_returns_a_valid_irr() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr = try_bisection(cash_flows.iter(), 0.0, 0.5, 100).unwrap();
+        assert!(calculated_irr.is_valid());
+    }
+
+    #[test]
+    fn it_works_with_other_collections() {
+        let cash_flows: VecDeque<f64> = VecDeque::new();
+        assert!(matches!(
+            try_bisection(&cash_flows, 0.0, 0.5, 100),
+            Err(IrrError::InvalidInput(_))
+        ));
+
+        let cash_flows: VecDeque<f64> = vec![-100.0, 60.0, 60.0].into_iter().collect();
+        assert!(try_bisection(&cash_flows, 0.0, 0.5, 100).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod resolve_tests {
+    use crate::irr::bisection::functions::irr::{bisection, resolve};
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_reuses_a_bracket_that_still_holds() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let previous: Irr<f32> = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+        let resolved: Irr<f32> = resolve(&previous, cash_flows.iter(), 100);
+
+        assert!(resolved.is_valid());
+        assert!(resolved.iterations_run() <= previous.iterations_run());
+    }
+
+    #[test]
+    fn it_matches_a_cold_start() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![25.0; 59]);
+        let iteration_limit: u32 = 1_000;
+        let previous: Irr<f64> = bisection(cash_flows.iter(), -0.5, 0.5, iteration_limit);
+
+        cash_flows.push(25.0);
+        let resolved: Irr<f64> = resolve(&previous, cash_flows.iter(), iteration_limit);
+        let cold: Irr<f64> = bisection(cash_flows.iter(), -0.5, 0.5, iteration_limit);
+
+        assert!(resolved.is_valid());
+        assert!((resolved.irr() - cold.irr()).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_works_with_a_map_over_a_struct_field() {
+        struct Payment {
+            amount: f64,
+        }
+        let payments: Vec<Payment> = vec![-100.0, 60.0, 60.0]
+            .into_iter()
+            .map(|amount| Payment { amount })
+            .collect();
+        let amounts = payments.iter().map(|payment| payment.amount);
+        let previous: Irr<f64> = bisection(amounts.clone(), 0.0, 0.5, 100);
+        let resolved: Irr<f64> = resolve(&previous, amounts, 100);
+
+        assert!(resolved.is_valid());
+        assert!((resolved.irr() - previous.irr()).abs() < 0.000_001);
+    }
+}
+
+#[cfg(test)]
+mod with_hint_tests {
+    use crate::irr::bisection::functions::irr::with_hint;
+    use crate::irr::bisection::structs::bracket_hint::BracketHint;
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_uses_a_valid_hint() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let hint: BracketHint<f64> = BracketHint::new(0.10, 0.20);
+        let calculated_irr: Irr<f64> = with_hint(cash_flows.iter(), Some(&hint), 0.5, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert!(calculated_irr.rate_low() >= hint.rate_low());
+        assert!(calculated_irr.rate_high() <= hint.rate_high());
+    }
+
+    #[test]
+    fn it_counts_the_npv_evaluations_of_every_stage() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let stale_hint: BracketHint<f64> = BracketHint::new(0.50, 0.60);
+        let hinted_irr: Irr<f64> = with_hint(cash_flows.iter(), Some(&stale_hint), 0.10, 100);
+        let cold_irr: Irr<f64> = with_hint(cash_flows.iter(), None, 0.10, 100);
+
+        assert_eq!(hinted_irr.npv_evaluations(), cold_irr.npv_evaluations() + 2);
+        // the bracket search evaluates the NPV on top of the bisection
+        assert!(cold_irr.npv_evaluations() > 3 + cold_irr.iterations_run());
+    }
+
+    #[test]
+    fn it_works_without_a_hint() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr: Irr<f64> = with_hint(cash_flows.iter(), None, 0.10, 100);
+
+        assert!(calculated_irr.is_valid());
+    }
+}
+
+#[cfg(test)]
+mod bisection_with_stub_period_tests {
+    use crate::irr::bisection::functions::irr::{bisection, bisection_with_stub_period};
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::present_value::from_cash_flows_with_stub_period;
+
+    #[test]
+    fn it_matches_bisection_at_a_fraction_of_one() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let stub: Irr<f64> = bisection_with_stub_period(cash_flows.iter(), 1.0, 0.0, 1.0, 100);
+        let whole: Irr<f64> = bisection(cash_flows.iter(), 0.0, 1.0, 100);
+
+        assert!(stub.is_valid());
+        assert!((stub.irr() - whole.irr()).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_zeroes_the_stub_period_npv() {
+        let cash_flows: Vec<f64> = vec![-1_000.0, 300.0, 400.0, 500.0];
+        let calculated_irr: Irr<f64> =
+            bisection_with_stub_period(cash_flows.iter(), 0.25, 0.0, 1.0, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert!(
+            from_cash_flows_with_stub_period(cash_flows.iter(), calculated_irr.irr(), 0.25).abs()
+                < 0.001
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::irr::bisection::functions::initial_bounds::determine;
+    use crate::irr::bisection::functions::irr::bisection_with_config;
+    use crate::irr::bisection::structs::config::BisectionConfig;
+    use crate::irr::bisection::structs::initial_bounds::InitialBounds;
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_round_trips_an_irr_with_history() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let config: BisectionConfig<f64> = BisectionConfig::new().with_record_history(true);
+        let calculated_irr: Irr<f64> = bisection_with_config(cash_flows.iter(), &config);
+
+        let json: String = serde_json::to_string(&calculated_irr).unwrap();
+        let deserialized: Irr<f64> = serde_json::from_str(&json).unwrap();
+        assert!(deserialized == calculated_irr);
+        assert!(json.contains("\"history\":[{\"iteration\":0,"));
+    }
+
+    #[test]
+    fn it_round_trips_initial_bounds_and_config() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let initial_bounds: InitialBounds<f32> = determine(cash_flows.iter(), 0.10, 100);
+        let json: String = serde_json::to_string(&initial_bounds).unwrap();
+        assert!(serde_json::from_str::<InitialBounds<f32>>(&json).unwrap() == initial_bounds);
+
+        let config: BisectionConfig<f32> = BisectionConfig::new().with_iteration_limit(20);
+        let json: String = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BisectionConfig<f32>>(&json).unwrap(),
+            config
+        );
     }
 }
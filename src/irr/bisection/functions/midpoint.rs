@@ -13,12 +13,12 @@ use std::iter::{Product, Sum};
 ///
 /// let a: f32 = 1.0;
 /// let b: f32 = 2.0;
-/// let mid_point: f32 = midpoint::calculate(&a, &b);
-/// assert!(are_equal_enough::is_true(&mid_point, &1.5));
+/// let mid_point: f32 = midpoint::calculate(a, b);
+/// assert!(are_equal_enough::is_true(mid_point, 1.5));
 /// ```
-pub fn calculate<T>(a: &T, c: &T) -> T
+pub fn calculate<T>(a: T, c: T) -> T
 where
     T: Float + Product<T> + Sum<T> + Signed,
 {
-    *a + (*c - *a) / T::from(2.0).unwrap()
+    a + (c - a) / T::from(2.0).unwrap()
 }
@@ -0,0 +1,85 @@
+//! Refines an IRR found with the bisection method.
+
+use num::{abs, Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+use crate::present_value::npv_derivative;
+
+/// Applies a single Newton step to the IRR of a bisection result, marking the result as polished if the step improves it.
+///
+/// # Comments
+/// The step is only accepted if it stays within the bracket of the result and reduces the absolute NPV; otherwise the result is returned unchanged.
+/// This keeps the robustness of the bracketing while getting close to machine precision for one extra pair of NPV evaluations.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection as irr;
+/// use time_value::irr::bisection::functions::polish;
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: Irr<f64> = irr(cash_flows.iter(), 0.05, 0.18, 10);
+/// let polished_irr: Irr<f64> = polish::newton(cash_flows.iter(), &calculated_irr);
+///
+/// assert!(polished_irr.is_polished());
+/// assert!(polished_irr.npv().abs() < calculated_irr.npv().abs());
+/// ```
+pub fn newton<T>(cash_flows: Iter<T>, irr: &Irr<T>) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let unchanged: Irr<T> = irr.with_irr(irr.irr(), irr.npv(), irr.is_valid(), irr.is_polished());
+
+    let derivative: T = npv_derivative(cash_flows.clone(), irr.irr());
+    let rate: T = irr.irr() - irr.npv() / derivative;
+    let lower: T = irr.rate_low().min(irr.rate_high());
+    let upper: T = irr.rate_low().max(irr.rate_high());
+    if !rate.is_finite() || rate < lower || upper < rate {
+        return unchanged;
+    }
+
+    let npv: T = pv(cash_flows, rate);
+    let npv_evaluations: u32 = irr.npv_evaluations() + 1;
+    if abs(irr.npv()) <= abs(npv) {
+        return unchanged.with_npv_evaluations(npv_evaluations);
+    }
+
+    irr.with_irr(rate, npv, abs(npv) <= T::from(NPV_PRECISION).unwrap(), true)
+        .with_npv_evaluations(npv_evaluations)
+}
+
+#[cfg(test)]
+mod newton_tests {
+    use crate::irr::bisection::functions::irr::bisection as irr;
+    use crate::irr::bisection::functions::polish;
+    use crate::irr::bisection::structs::irr::Irr;
+
+    #[test]
+    fn it_does_not_polish_an_invalid_bracket() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let calculated_irr: Irr<f32> = irr(cash_flows.iter(), 0.5, 0.6, 100);
+        let polished_irr: Irr<f32> = polish::newton(cash_flows.iter(), &calculated_irr);
+
+        assert!(!polished_irr.is_polished());
+        assert!(polished_irr.irr().is_nan());
+    }
+
+    #[test]
+    fn it_polishes_an_early_stop() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr: Irr<f64> = irr(cash_flows.iter(), 0.0, 0.5, 5);
+        let polished_irr: Irr<f64> = polish::newton(cash_flows.iter(), &calculated_irr);
+
+        assert!(!calculated_irr.is_valid());
+        assert!(polished_irr.is_polished());
+        assert!(polished_irr.is_valid());
+        assert_eq!(
+            polished_irr.iterations_run(),
+            calculated_irr.iterations_run()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! A struct containing a candidate bracket for the bisection method e.g. one persisted from a previous run.
+
+use num::Float;
+
+/// A pair of rates expected, but not guaranteed, to have NPVs of opposite signs.
+///
+/// # Example
+/// ```
+/// use time_value::irr::bisection::structs::bracket_hint::BracketHint;
+///
+/// let hint: BracketHint<f64> = BracketHint::new(0.05, 0.18);
+/// assert_eq!(hint.rate_low(), 0.05);
+/// assert_eq!(hint.rate_high(), 0.18);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BracketHint<T>
+where
+    T: Float,
+{
+    rate_low: T,
+    rate_high: T,
+}
+
+impl<T> BracketHint<T>
+where
+    T: Float,
+{
+    pub fn new(rate_low: T, rate_high: T) -> BracketHint<T> {
+        BracketHint {
+            rate_low,
+            rate_high,
+        }
+    }
+
+    pub fn rate_low(&self) -> T {
+        self.rate_low
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_low` instead")]
+    pub fn get_rate_low(&self) -> T {
+        self.rate_low()
+    }
+
+    pub fn rate_high(&self) -> T {
+        self.rate_high
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_high` instead")]
+    pub fn get_rate_high(&self) -> T {
+        self.rate_high()
+    }
+}
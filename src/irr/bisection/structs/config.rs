@@ -0,0 +1,180 @@
+//! A builder for the parameters of the bisection method.
+
+use num::Float;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::structs::bracket_hint::BracketHint;
+
+/// When the bisection method stops, besides reaching the iteration limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Convergence<T>
+where
+    T: Float,
+{
+    /// Once the NPV is within the NPV tolerance of zero.
+    Npv,
+    /// Once the NPV is within the NPV tolerance of zero or the bracket is narrower than the rate tolerance; see `bisection_with_rate_tolerance`.
+    NpvOrRateInterval(T),
+}
+
+/// How the bisection method finds the rates that bracket the IRR.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BracketStrategy<T>
+where
+    T: Float,
+{
+    /// Use these rates, which are assumed to have NPVs of opposite signs.
+    Bounds(T, T),
+    /// Search outwards from this rate with `initial_bounds::determine`.
+    Search(T),
+    /// Use the hint if its rates have NPVs of opposite signs, and otherwise search outwards from the rate; see `with_hint`.
+    Hint(BracketHint<T>, T),
+}
+
+/// The parameters of the bisection method, for use with `bisection_with_config`.
+///
+/// # Comments
+/// By default the NPV tolerance is `NPV_PRECISION`, the iteration limit is 100, the method stops only on the NPV, the bracket is searched for outwards from 10%, no history is recorded, and the cash flows are not rescaled.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_config;
+/// use time_value::irr::bisection::structs::config::{BisectionConfig, BracketStrategy, Convergence};
+/// use time_value::irr::bisection::structs::irr::Irr;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let config: BisectionConfig<f64> = BisectionConfig::new()
+///     .with_npv_tolerance(0.000_01)
+///     .with_iteration_limit(200)
+///     .with_convergence(Convergence::NpvOrRateInterval(0.000_000_1))
+///     .with_bracket(BracketStrategy::Bounds(0.05, 0.18));
+///
+/// let calculated_irr: Irr<f64> = bisection_with_config(cash_flows.iter(), &config);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.0928).abs() < 0.000_1);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BisectionConfig<T>
+where
+    T: Float,
+{
+    npv_tolerance: T,
+    iteration_limit: u32,
+    convergence: Convergence<T>,
+    bracket: BracketStrategy<T>,
+    record_history: bool,
+    normalize: bool,
+}
+
+impl<T> BisectionConfig<T>
+where
+    T: Float,
+{
+    /// Starts a configuration with the defaults.
+    pub fn new() -> BisectionConfig<T> {
+        BisectionConfig {
+            npv_tolerance: T::from(NPV_PRECISION).unwrap(),
+            iteration_limit: 100,
+            convergence: Convergence::Npv,
+            bracket: BracketStrategy::Search(T::from(0.10).unwrap()),
+            record_history: false,
+            normalize: false,
+        }
+    }
+
+    /// How close to zero the NPV must be, in the currency of the cash flows.
+    pub fn with_npv_tolerance(mut self, npv_tolerance: T) -> BisectionConfig<T> {
+        self.npv_tolerance = npv_tolerance;
+        self
+    }
+
+    /// The iteration limit of the bisection, and of the bracket search if there is one.
+    pub fn with_iteration_limit(mut self, iteration_limit: u32) -> BisectionConfig<T> {
+        self.iteration_limit = iteration_limit;
+        self
+    }
+
+    pub fn with_convergence(mut self, convergence: Convergence<T>) -> BisectionConfig<T> {
+        self.convergence = convergence;
+        self
+    }
+
+    pub fn with_bracket(mut self, bracket: BracketStrategy<T>) -> BisectionConfig<T> {
+        self.bracket = bracket;
+        self
+    }
+
+    /// Whether to record every iteration of the bisection in the result; see `Irr::history`.
+    pub fn with_record_history(mut self, record_history: bool) -> BisectionConfig<T> {
+        self.record_history = record_history;
+        self
+    }
+
+    /// Whether to divide the cash flows by their scale before solving, so that the NPV tolerance applies to the rescaled cash flows; see `cash_flows::scale` and `Irr::scale`.
+    pub fn with_normalize(mut self, normalize: bool) -> BisectionConfig<T> {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn npv_tolerance(&self) -> T {
+        self.npv_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_tolerance` instead")]
+    pub fn get_npv_tolerance(&self) -> T {
+        self.npv_tolerance()
+    }
+
+    pub fn iteration_limit(&self) -> u32 {
+        self.iteration_limit
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    pub fn convergence(&self) -> Convergence<T> {
+        self.convergence
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `convergence` instead")]
+    pub fn get_convergence(&self) -> Convergence<T> {
+        self.convergence()
+    }
+
+    pub fn bracket(&self) -> BracketStrategy<T> {
+        self.bracket
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `bracket` instead")]
+    pub fn get_bracket(&self) -> BracketStrategy<T> {
+        self.bracket()
+    }
+
+    pub fn record_history(&self) -> bool {
+        self.record_history
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `record_history` instead")]
+    pub fn get_record_history(&self) -> bool {
+        self.record_history()
+    }
+
+    /// Whether the cash flows are divided by their scale before solving; see `with_normalize`.
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+}
+
+impl<T> Default for BisectionConfig<T>
+where
+    T: Float,
+{
+    fn default() -> BisectionConfig<T> {
+        BisectionConfig::new()
+    }
+}
@@ -0,0 +1,51 @@
+//! The reason an IRR or initial bounds calculation failed.
+
+use std::fmt::{Display, Error, Formatter};
+
+/// Why an `Irr` or `InitialBounds` is not valid.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection;
+/// use time_value::irr::bisection::structs::failure_reason::FailureReason;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// assert_eq!(bisection(cash_flows.iter(), 0.05, 0.18, 100).failure_reason(), None);
+/// assert_eq!(
+///     bisection(cash_flows.iter(), 0.05, 0.18, 2).failure_reason(),
+///     Some(FailureReason::MaxIterations)
+/// );
+/// assert_eq!(format!("{}", FailureReason::MaxIterations), "max_iterations");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FailureReason {
+    /// The NPVs at the bounds have the same sign, or no bounds with NPVs of opposite signs were found.
+    BracketNotFound,
+    /// The iteration limit was reached before the NPV was within the NPV tolerance of zero.
+    MaxIterations,
+    /// The bounds are adjacent values of the float type, so the bracket cannot be narrowed further, but the NPV there is not within the NPV tolerance of zero e.g. because the tolerance is finer than the NPV can be calculated.
+    BracketExhausted,
+    /// An NPV was infinite or NaN.
+    NonFiniteNpv,
+    /// The inputs do not define a calculation, e.g. a window of returns that extends past the valuations.
+    InvalidInput,
+    /// The calculation stopped before the iteration limit without the NPV being within the NPV tolerance of zero.
+    NotConverged,
+    /// The calculation was stopped by an observer before the NPV was within the NPV tolerance of zero.
+    Stopped,
+}
+
+impl Display for FailureReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            FailureReason::BracketNotFound => write!(f, "bracket_not_found"),
+            FailureReason::MaxIterations => write!(f, "max_iterations"),
+            FailureReason::BracketExhausted => write!(f, "bracket_exhausted"),
+            FailureReason::NonFiniteNpv => write!(f, "non_finite_npv"),
+            FailureReason::InvalidInput => write!(f, "invalid_input"),
+            FailureReason::NotConverged => write!(f, "not_converged"),
+            FailureReason::Stopped => write!(f, "stopped"),
+        }
+    }
+}
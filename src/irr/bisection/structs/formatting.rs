@@ -0,0 +1,31 @@
+//! Helpers shared by the `Display` implementations of the bisection structs.
+
+use num::Float;
+use std::fmt::{Display, Formatter};
+
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+
+/// Formats a value with the precision requested by the formatter, if any e.g. `{:.4}`.
+pub(crate) fn value<T: Display>(f: &Formatter<'_>, value: T) -> String {
+    match f.precision() {
+        Some(precision) => format!("{:.*}", precision, value),
+        None => value.to_string(),
+    }
+}
+
+/// Formats a rate like `value`, as a percentage when the alternate flag is set e.g. `{:#.2}`.
+pub(crate) fn rate<T: Float + Display>(f: &Formatter<'_>, rate: T) -> String {
+    if f.alternate() {
+        format!("{}%", value(f, rate * T::from(100.0).unwrap()))
+    } else {
+        value(f, rate)
+    }
+}
+
+/// Formats a failure reason, or `none` if there is none.
+pub(crate) fn failure_reason(failure_reason: Option<FailureReason>) -> String {
+    match failure_reason {
+        Some(failure_reason) => failure_reason.to_string(),
+        None => String::from("none"),
+    }
+}
@@ -1,9 +1,17 @@
 //! A struct containing information for determining the initial bounds for use with the bisection method.
 
 use num::{Float, Signed};
+use std::borrow::Borrow;
 use std::fmt::{Debug, Display, Error, Formatter};
 use std::iter::{Product, Sum};
 
+use crate::irr::bisection::functions::are_equal_enough;
+use crate::irr::bisection::functions::irr::bisection_with_config;
+use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy};
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::formatting;
+use crate::irr::bisection::structs::irr::Irr;
+
 /// # Example
 /// ```
 /// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
@@ -17,31 +25,38 @@ use std::iter::{Product, Sum};
 /// 24,
 /// true,
 /// );
+///
+/// assert!(format!("{:.1}", initial_bounds).starts_with("rate_low: 0.0\nnpv_rate_low: 1.0\n"));
+/// assert!(format!("{:#.1}", initial_bounds).starts_with("rate_low: 1.0%\nnpv_rate_low: 1.0\n"));
 /// ```
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InitialBounds<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
     rate_low: T,
     npv_rate_low: T,
     rate_high: T,
     npv_rate_high: T,
-    iteration_limit: i16,
-    iterations_run: i16,
+    iteration_limit: u32,
+    iterations_run: u32,
+    npv_evaluations: u32,
     is_valid: bool,
+    failure_reason: Option<FailureReason>,
 }
 
 impl<T> InitialBounds<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
     pub fn new(
         rate_low: T,
         npv_rate_low: T,
         rate_high: T,
         npv_rate_high: T,
-        iteration_limit: i16,
-        iterations_run: i16,
+        iteration_limit: u32,
+        iterations_run: u32,
         is_valid: bool,
     ) -> InitialBounds<T> {
         InitialBounds {
@@ -51,52 +66,209 @@ where
             npv_rate_high,
             iteration_limit,
             iterations_run,
+            npv_evaluations: 0,
             is_valid,
+            failure_reason: if is_valid {
+                None
+            } else if !npv_rate_low.is_finite() || !npv_rate_high.is_finite() {
+                Some(FailureReason::NonFiniteNpv)
+            } else {
+                Some(FailureReason::BracketNotFound)
+            },
         }
     }
 
-    pub fn get_rate_low(&self) -> T {
+    /// Returns a copy with the number of NPV evaluations replaced.
+    pub(crate) fn with_npv_evaluations(&self, npv_evaluations: u32) -> InitialBounds<T> {
+        InitialBounds {
+            npv_evaluations,
+            ..*self
+        }
+    }
+
+    pub fn rate_low(&self) -> T {
         self.rate_low
     }
 
-    pub fn get_npv_rate_low(&self) -> T {
+    pub fn npv_rate_low(&self) -> T {
         self.npv_rate_low
     }
 
-    pub fn get_rate_high(&self) -> T {
+    pub fn rate_high(&self) -> T {
         self.rate_high
     }
 
-    pub fn get_npv_rate_high(&self) -> T {
+    pub fn npv_rate_high(&self) -> T {
         self.npv_rate_high
     }
 
-    pub fn get_iteration_limit(&self) -> i16 {
+    pub fn iteration_limit(&self) -> u32 {
         self.iteration_limit
     }
 
-    pub fn get_iterations_run(&self) -> i16 {
+    pub fn iterations_run(&self) -> u32 {
         self.iterations_run
     }
 
+    pub fn npv_evaluations(&self) -> u32 {
+        self.npv_evaluations
+    }
+
     pub fn is_valid(&self) -> bool {
         self.is_valid
     }
+
+    /// Why no bounds were found, or `None` if they were.
+    pub fn failure_reason(&self) -> Option<FailureReason> {
+        self.failure_reason
+    }
+
+    /// Whether the rates and NPVs of two sets of bounds are each within `tolerance`, and both or neither are valid; see `Irr::approx_eq`.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::irr::bisection::functions::initial_bounds::determine;
+    /// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
+    ///
+    /// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+    /// let initial_bounds: InitialBounds<f64> = determine(cash_flows.iter(), 0.10, 100);
+    /// let copied: InitialBounds<f64> = initial_bounds;
+    ///
+    /// assert!(copied == initial_bounds);
+    /// assert!(copied.approx_eq(&determine(cash_flows.iter(), 0.100_000_1, 100), 0.001));
+    /// ```
+    pub fn approx_eq(&self, other: &InitialBounds<T>, tolerance: T) -> bool {
+        let within = |a: T, b: T| are_equal_enough::is_true_within(a, b, tolerance);
+        self.is_valid == other.is_valid
+            && within(self.rate_low, other.rate_low)
+            && within(self.npv_rate_low, other.npv_rate_low)
+            && within(self.rate_high, other.rate_high)
+            && within(self.npv_rate_high, other.npv_rate_high)
+    }
+
+    /// Calculates the IRR of the cash flows for which the bounds were determined, by bisection between the bounds with the rest of `config`.
+    ///
+    /// # Comments
+    /// The bracket of `config` is replaced by the bounds, and the NPV evaluations made to determine them are included in those of the IRR.
+    /// If the bounds are not valid no bisection is run, and the IRR is not valid for the same reason.
+    /// Nor is one run if the bounds are a single rate, i.e. the guess was already close enough to a root; that rate is the IRR.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::irr::bisection::functions::initial_bounds::determine;
+    /// use time_value::irr::bisection::structs::config::BisectionConfig;
+    /// use time_value::irr::bisection::structs::initial_bounds::InitialBounds;
+    /// use time_value::irr::bisection::structs::irr::Irr;
+    ///
+    /// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+    /// let initial_bounds: InitialBounds<f64> = determine(cash_flows.iter(), 0.10, 100);
+    /// let calculated_irr: Irr<f64> = initial_bounds.solve(cash_flows.iter(), &BisectionConfig::new());
+    ///
+    /// assert!(calculated_irr.is_valid());
+    /// assert!((calculated_irr.irr() - 0.130_662).abs() < 0.000_01);
+    /// ```
+    pub fn solve<I, B>(&self, cash_flows: I, config: &BisectionConfig<T>) -> Irr<T>
+    where
+        I: IntoIterator<Item = B> + Clone,
+        B: Borrow<T>,
+    {
+        if let Some(failure_reason) = self.failure_reason {
+            return Irr::new(
+                self.rate_low,
+                self.npv_rate_low,
+                self.rate_high,
+                self.npv_rate_high,
+                config.iteration_limit(),
+                0,
+                T::nan(),
+                T::nan(),
+                false,
+            )
+            .with_npv_evaluations(self.npv_evaluations)
+            .with_failure_reason(failure_reason);
+        }
+        if self.rate_low == self.rate_high {
+            return Irr::new(
+                self.rate_low,
+                self.npv_rate_low,
+                self.rate_high,
+                self.npv_rate_high,
+                config.iteration_limit(),
+                0,
+                self.rate_low,
+                self.npv_rate_low,
+                true,
+            )
+            .with_npv_evaluations(self.npv_evaluations);
+        }
+        let config: BisectionConfig<T> =
+            config.with_bracket(BracketStrategy::Bounds(self.rate_low, self.rate_high));
+        let irr: Irr<T> = bisection_with_config(cash_flows, &config);
+        irr.with_npv_evaluations(self.npv_evaluations + irr.npv_evaluations())
+    }
+}
+
+/// The accessors prefixed with `get_`, kept for one release after the move to accessors named after their fields.
+impl<T> InitialBounds<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    #[deprecated(since = "0.9.0", note = "use `rate_low` instead")]
+    pub fn get_rate_low(&self) -> T {
+        self.rate_low()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_rate_low` instead")]
+    pub fn get_npv_rate_low(&self) -> T {
+        self.npv_rate_low()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_high` instead")]
+    pub fn get_rate_high(&self) -> T {
+        self.rate_high()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_rate_high` instead")]
+    pub fn get_npv_rate_high(&self) -> T {
+        self.npv_rate_high()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iterations_run` instead")]
+    pub fn get_iterations_run(&self) -> u32 {
+        self.iterations_run()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_evaluations` instead")]
+    pub fn get_npv_evaluations(&self) -> u32 {
+        self.npv_evaluations()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `failure_reason` instead")]
+    pub fn get_failure_reason(&self) -> Option<FailureReason> {
+        self.failure_reason()
+    }
 }
 
 impl<T> Debug for InitialBounds<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Debug + Display,
+    T: Float + Product<T> + Sum<T> + Signed + Debug,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         f.debug_struct("InitialBounds")
-            .field("rate_low", &self.get_rate_low())
-            .field("npv_rate_low", &self.get_npv_rate_low())
-            .field("rate_high", &self.get_rate_high())
-            .field("npv_rate_high", &self.get_npv_rate_high())
-            .field("iteration_limit", &self.get_iteration_limit())
-            .field("iterations_run", &self.get_iterations_run())
+            .field("rate_low", &self.rate_low())
+            .field("npv_rate_low", &self.npv_rate_low())
+            .field("rate_high", &self.rate_high())
+            .field("npv_rate_high", &self.npv_rate_high())
+            .field("iteration_limit", &self.iteration_limit())
+            .field("iterations_run", &self.iterations_run())
+            .field("npv_evaluations", &self.npv_evaluations())
             .field("is_valid", &self.is_valid())
+            .field("failure_reason", &self.failure_reason())
             .finish()
     }
 }
@@ -108,14 +280,16 @@ where
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         write!(
             f,
-            "rate_low: {}\nnpv_rate_low: {}\nrate_high: {}\nnpv_rate_high: {}\niteration_limit: {}\n iterations_run: {}\nis_valid: {}\n",
-            self.get_rate_low(),
-            self.get_npv_rate_low(),
-            self.get_rate_high(),
-            self.get_npv_rate_high(),
-            self.get_iteration_limit(),
-            self.get_iterations_run(),
-            self.is_valid()
+            "rate_low: {}\nnpv_rate_low: {}\nrate_high: {}\nnpv_rate_high: {}\niteration_limit: {}\n iterations_run: {}\nnpv_evaluations: {}\nis_valid: {}\nfailure_reason: {}\n",
+            formatting::rate(f, self.rate_low()),
+            formatting::value(f, self.npv_rate_low()),
+            formatting::rate(f, self.rate_high()),
+            formatting::value(f, self.npv_rate_high()),
+            self.iteration_limit(),
+            self.iterations_run(),
+            self.npv_evaluations(),
+            self.is_valid(),
+            formatting::failure_reason(self.failure_reason())
         )
     }
 }
@@ -5,6 +5,13 @@ use std::iter::{Product, Sum};
 
 use std::fmt::{Debug, Display, Error, Formatter};
 
+use crate::irr::bisection::functions::are_equal_enough;
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::formatting;
+use crate::irr::bisection::structs::iteration::Iteration;
+use crate::irr::error::IrrError;
+use crate::rate::{BasisPoints, Percent};
+
 /// Contains information useful to finding the IRR of a given cash flow series.
 ///
 /// # Example: taking a punt at the IRR
@@ -20,13 +27,13 @@ use std::fmt::{Debug, Display, Error, Formatter};
 ///
 /// let rate_one_guess: f32 = 0.01;
 /// let rate_two_guess: f32 = 0.05;
-/// let rate_guess: f32 = midpoint::calculate(&rate_one_guess, &rate_two_guess);
+/// let rate_guess: f32 = midpoint::calculate(rate_one_guess, rate_two_guess);
 ///
-/// let npv_guess_one: f32 = pv(cash_flows.iter(), &rate_one_guess);
-/// let npv_guess_two: f32 = pv(cash_flows.iter(), &rate_two_guess);
-/// let npv_guess: f32 =  pv(cash_flows.iter(), &rate_guess);
+/// let npv_guess_one: f32 = pv(cash_flows.iter(), rate_one_guess);
+/// let npv_guess_two: f32 = pv(cash_flows.iter(), rate_two_guess);
+/// let npv_guess: f32 =  pv(cash_flows.iter(), rate_guess);
 ///
-/// let iteration_limit: i16 = 0;
+/// let iteration_limit: u32 = 0;
 ///
 /// let is_valid: bool = abs(npv_guess) <= NPV_PRECISION;
 ///
@@ -56,8 +63,8 @@ use std::fmt::{Debug, Display, Error, Formatter};
 /// let npv_two_guess: f32 = 2.0;
 /// let npv_guess: f32 = 1.5;
 ///
-/// let iteration_limit: i16 = 4;
-/// let iterations_run: i16 = 3;
+/// let iteration_limit: u32 = 4;
+/// let iterations_run: u32 = 3;
 ///
 /// let is_valid: bool = false;
 ///
@@ -74,41 +81,65 @@ use std::fmt::{Debug, Display, Error, Formatter};
 ///         );
 ///
 /// assert_eq!(irr_guess.rate_low(), rate_one_guess);
-/// assert_eq!(irr_guess.get_rate_high(), rate_two_guess);
-/// assert_eq!(irr_guess.get_npv_rate_low(), npv_one_guess);
-/// assert_eq!(irr_guess.get_npv_rate_high(), npv_two_guess);
-/// assert_eq!(irr_guess.get_irr(), rate_guess);
-/// assert_eq!(irr_guess.get_iteration_limit(), iteration_limit);
-/// assert_eq!(irr_guess.get_iterations_run(), iterations_run);
-/// assert_eq!(irr_guess.get_npv(), npv_guess);
+/// assert_eq!(irr_guess.rate_high(), rate_two_guess);
+/// assert_eq!(irr_guess.npv_rate_low(), npv_one_guess);
+/// assert_eq!(irr_guess.npv_rate_high(), npv_two_guess);
+/// assert_eq!(irr_guess.irr(), rate_guess);
+/// assert_eq!(irr_guess.iteration_limit(), iteration_limit);
+/// assert_eq!(irr_guess.iterations_run(), iterations_run);
+/// assert_eq!(irr_guess.npv(), npv_guess);
+/// assert_eq!(irr_guess.irr_percent().value(), 1.5);
+/// assert_eq!(irr_guess.irr_basis_points().value(), 150.0);
 /// assert!(!irr_guess.is_valid());
+/// assert!(!irr_guess.is_polished());
+/// ```
+///
+/// # Example: Display precision
+/// `Display` honours the requested precision, and the alternate flag shows rates as percentages.
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection as irr;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr = irr(cash_flows.iter(), 0.05, 0.18, 100);
+///
+/// assert!(format!("{:.4}", calculated_irr).contains("\nirr: 0.0928\n"));
+/// assert!(format!("{:#.2}", calculated_irr).contains("\nirr: 9.28%\n"));
 /// ```
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Irr<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
     rate_low: T,
     npv_rate_low: T,
     rate_high: T,
     npv_rate_high: T,
-    iteration_limit: i16,
-    iterations_run: i16,
+    iteration_limit: u32,
+    iterations_run: u32,
     irr: T,
     npv: T,
+    npv_evaluations: u32,
     is_valid: bool,
+    is_polished: bool,
+    failure_reason: Option<FailureReason>,
+    history: Option<Vec<Iteration<T>>>,
+    scale: Option<T>,
 }
 
 impl<T> Irr<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Display + Debug,
+    T: Float + Product<T> + Sum<T> + Signed,
 {
+    /// Creates an `Irr`; if it is not valid, the failure reason is inferred from the other fields unless the solver replaces it.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         rate_low: T,
         npv_rate_low: T,
         rate_high: T,
         npv_rate_high: T,
-        iteration_limit: i16,
-        iterations_run: i16,
+        iteration_limit: u32,
+        iterations_run: u32,
         irr: T,
         npv: T,
         is_valid: bool,
@@ -120,9 +151,90 @@ where
             npv_rate_high,
             iteration_limit,
             iterations_run,
+            irr,
+            npv,
+            npv_evaluations: 0,
+            is_valid,
+            is_polished: false,
+            failure_reason: if is_valid {
+                None
+            } else {
+                Some(infer_failure_reason(
+                    npv_rate_low,
+                    npv_rate_high,
+                    iteration_limit,
+                    iterations_run,
+                    irr,
+                    npv,
+                ))
+            },
+            history: None,
+            scale: None,
+        }
+    }
+
+    /// Returns a copy with the IRR and its NPV replaced e.g. by those of a polishing step.
+    pub(crate) fn with_irr(&self, irr: T, npv: T, is_valid: bool, is_polished: bool) -> Irr<T> {
+        Irr {
             irr,
             npv,
             is_valid,
+            is_polished,
+            failure_reason: if is_valid {
+                None
+            } else {
+                self.failure_reason.or(Some(FailureReason::NotConverged))
+            },
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy with the failure reason replaced by one known to the solver.
+    pub(crate) fn with_failure_reason(&self, failure_reason: FailureReason) -> Irr<T> {
+        Irr {
+            failure_reason: Some(failure_reason),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy with the number of NPV evaluations replaced.
+    pub(crate) fn with_npv_evaluations(&self, npv_evaluations: u32) -> Irr<T> {
+        Irr {
+            npv_evaluations,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy with the iteration history replaced.
+    pub(crate) fn with_history(&self, history: Vec<Iteration<T>>) -> Irr<T> {
+        Irr {
+            history: Some(history),
+            ..*self
+        }
+    }
+
+    /// Returns a copy for cash flows `scale` times larger than those solved, with the NPVs multiplied by `scale` and the scale recorded.
+    pub(crate) fn with_scale(&self, scale: T) -> Irr<T> {
+        Irr {
+            npv_rate_low: self.npv_rate_low * scale,
+            npv_rate_high: self.npv_rate_high * scale,
+            npv: self.npv * scale,
+            history: self.history.as_ref().map(|history| {
+                history
+                    .iter()
+                    .map(|iteration| {
+                        Iteration::new(
+                            iteration.iteration(),
+                            iteration.rate_low(),
+                            iteration.rate_high(),
+                            iteration.irr(),
+                            iteration.npv() * scale,
+                        )
+                    })
+                    .collect()
+            }),
+            scale: Some(scale),
+            ..self.clone()
         }
     }
 
@@ -130,54 +242,196 @@ where
         self.rate_low
     }
 
-    pub fn get_npv_rate_low(&self) -> T {
+    pub fn npv_rate_low(&self) -> T {
         self.npv_rate_low
     }
 
-    pub fn get_rate_high(&self) -> T {
+    pub fn rate_high(&self) -> T {
         self.rate_high
     }
 
-    pub fn get_npv_rate_high(&self) -> T {
+    pub fn npv_rate_high(&self) -> T {
         self.npv_rate_high
     }
 
-    pub fn get_iteration_limit(&self) -> i16 {
+    pub fn iteration_limit(&self) -> u32 {
         self.iteration_limit
     }
 
-    pub fn get_iterations_run(&self) -> i16 {
+    pub fn iterations_run(&self) -> u32 {
         self.iterations_run
     }
 
-    pub fn get_irr(&self) -> T {
+    pub fn irr(&self) -> T {
         self.irr
     }
 
-    pub fn get_npv(&self) -> T {
+    pub fn npv(&self) -> T {
         self.npv
     }
 
+    /// The number of NPV evaluations made to find the IRR, including any made to search for the bracket.
+    pub fn npv_evaluations(&self) -> u32 {
+        self.npv_evaluations
+    }
+
+    pub fn irr_percent(&self) -> Percent<T> {
+        Percent::from_decimal(self.irr)
+    }
+
+    pub fn irr_basis_points(&self) -> BasisPoints<T> {
+        BasisPoints::from_decimal(self.irr)
+    }
+
     pub fn is_valid(&self) -> bool {
         self.is_valid
     }
+
+    pub fn is_polished(&self) -> bool {
+        self.is_polished
+    }
+
+    /// Why the IRR is not valid, or `None` if it is valid.
+    pub fn failure_reason(&self) -> Option<FailureReason> {
+        self.failure_reason
+    }
+
+    /// Every iteration of the solver, if it was asked to record them; see `BisectionConfig::with_record_history`.
+    pub fn history(&self) -> Option<&[Iteration<T>]> {
+        self.history.as_deref()
+    }
+
+    /// The scale by which the cash flows were divided before solving, if they were; the NPVs are in the currency of the original cash flows.
+    pub fn scale(&self) -> Option<T> {
+        self.scale
+    }
+
+    /// The error corresponding to the failure reason, or `None` if the IRR is valid.
+    pub fn error(&self) -> Option<IrrError> {
+        self.failure_reason
+            .map(|failure_reason| match failure_reason {
+                FailureReason::BracketNotFound => IrrError::NoSignChange,
+                FailureReason::MaxIterations => IrrError::IterationLimitReached,
+                FailureReason::NonFiniteNpv => IrrError::InvalidInput("an NPV is not finite"),
+                FailureReason::InvalidInput => IrrError::InvalidInput("the inputs are not valid"),
+                FailureReason::BracketExhausted
+                | FailureReason::NotConverged
+                | FailureReason::Stopped => IrrError::NotConverged,
+            })
+    }
+
+    /// Whether the rates and NPVs of two results are each within `tolerance`, and both or neither are valid.
+    ///
+    /// # Comments
+    /// The iteration and evaluation counts are not compared, so results from different solvers or settings can be reconciled; two NaN values are equal.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::irr::bisection::functions::irr::{bisection, bisection_with_tolerance};
+    ///
+    /// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+    /// let coarse = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+    /// let fine = bisection_with_tolerance(cash_flows.iter(), 0.0, 0.5, 100, 0.000_001);
+    ///
+    /// assert!(coarse.approx_eq(&fine, 0.01));
+    /// assert!(!coarse.approx_eq(&fine, 0.000_000_001));
+    /// ```
+    pub fn approx_eq(&self, other: &Irr<T>, tolerance: T) -> bool {
+        let within = |a: T, b: T| are_equal_enough::is_true_within(a, b, tolerance);
+        self.is_valid == other.is_valid
+            && within(self.rate_low, other.rate_low)
+            && within(self.npv_rate_low, other.npv_rate_low)
+            && within(self.rate_high, other.rate_high)
+            && within(self.npv_rate_high, other.npv_rate_high)
+            && within(self.irr, other.irr)
+            && within(self.npv, other.npv)
+    }
+
+    /// Converts the IRR into a `Result`, which is an error if the IRR is not valid; see `error`.
+    pub fn into_result(self) -> Result<Irr<T>, IrrError> {
+        match self.error() {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
+}
+
+/// The accessors prefixed with `get_`, kept for one release after the move to accessors named after their fields.
+impl<T> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    #[deprecated(since = "0.9.0", note = "use `npv_rate_low` instead")]
+    pub fn get_npv_rate_low(&self) -> T {
+        self.npv_rate_low()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_high` instead")]
+    pub fn get_rate_high(&self) -> T {
+        self.rate_high()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_rate_high` instead")]
+    pub fn get_npv_rate_high(&self) -> T {
+        self.npv_rate_high()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iterations_run` instead")]
+    pub fn get_iterations_run(&self) -> u32 {
+        self.iterations_run()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `irr` instead")]
+    pub fn get_irr(&self) -> T {
+        self.irr()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv` instead")]
+    pub fn get_npv(&self) -> T {
+        self.npv()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_evaluations` instead")]
+    pub fn get_npv_evaluations(&self) -> u32 {
+        self.npv_evaluations()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `failure_reason` instead")]
+    pub fn get_failure_reason(&self) -> Option<FailureReason> {
+        self.failure_reason()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `history` instead")]
+    pub fn get_history(&self) -> Option<&[Iteration<T>]> {
+        self.history()
+    }
 }
 
 impl<T> Debug for Irr<T>
 where
-    T: Float + Product<T> + Sum<T> + Signed + Debug + Display,
+    T: Float + Product<T> + Sum<T> + Signed + Debug,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         f.debug_struct("Irr")
             .field("rate_low", &self.rate_low())
-            .field("npv_rate_low", &self.get_npv_rate_low())
-            .field("rate_high", &self.get_rate_high())
-            .field("npv_rate_high", &self.get_npv_rate_high())
-            .field("iteration_limit", &self.get_irr())
-            .field("iterations_run", &self.get_npv())
-            .field("irr", &self.get_irr())
-            .field("npv", &self.get_npv())
+            .field("npv_rate_low", &self.npv_rate_low())
+            .field("rate_high", &self.rate_high())
+            .field("npv_rate_high", &self.npv_rate_high())
+            .field("iteration_limit", &self.iteration_limit())
+            .field("iterations_run", &self.iterations_run())
+            .field("irr", &self.irr())
+            .field("npv", &self.npv())
+            .field("npv_evaluations", &self.npv_evaluations())
             .field("is_valid", &self.is_valid())
+            .field("is_polished", &self.is_polished())
+            .field("failure_reason", &self.failure_reason())
+            .field("history", &self.history())
+            .field("scale", &self.scale())
             .finish()
     }
 }
@@ -189,16 +443,45 @@ where
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         write!(
             f,
-            "rate_low: {}\nnpv_rate_low: {}\nrate_high: {}\nnpv_rate_high: {}\niteration_limit: {}\n iterations_run: {}\nirr: {}\n npv: {}\nis_valid: {}\n",
-            self.rate_low(),
-            self.get_npv_rate_low(),
-            self.get_rate_high(),
-            self.get_npv_rate_high(),
-            self.get_iteration_limit(),
-            self.get_iterations_run(),
-            self.get_irr(),
-            self.get_npv(),
-            self.is_valid()
+            "rate_low: {}\nnpv_rate_low: {}\nrate_high: {}\nnpv_rate_high: {}\niteration_limit: {}\n iterations_run: {}\nirr: {}\n npv: {}\nnpv_evaluations: {}\nis_valid: {}\nis_polished: {}\nfailure_reason: {}\n",
+            formatting::rate(f, self.rate_low()),
+            formatting::value(f, self.npv_rate_low()),
+            formatting::rate(f, self.rate_high()),
+            formatting::value(f, self.npv_rate_high()),
+            self.iteration_limit(),
+            self.iterations_run(),
+            formatting::rate(f, self.irr()),
+            formatting::value(f, self.npv()),
+            self.npv_evaluations(),
+            self.is_valid(),
+            self.is_polished(),
+            formatting::failure_reason(self.failure_reason())
         )
     }
 }
+
+/// Infers why an IRR is not valid from its fields, for solvers that do not record the reason themselves.
+fn infer_failure_reason<T>(
+    npv_rate_low: T,
+    npv_rate_high: T,
+    iteration_limit: u32,
+    iterations_run: u32,
+    irr: T,
+    npv: T,
+) -> FailureReason
+where
+    T: Float,
+{
+    if !npv_rate_low.is_finite()
+        || !npv_rate_high.is_finite()
+        || (!irr.is_nan() && !npv.is_finite())
+    {
+        FailureReason::NonFiniteNpv
+    } else if irr.is_nan() {
+        FailureReason::BracketNotFound
+    } else if 0 < iteration_limit && iteration_limit <= iterations_run {
+        FailureReason::MaxIterations
+    } else {
+        FailureReason::NotConverged
+    }
+}
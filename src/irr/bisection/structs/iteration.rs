@@ -0,0 +1,102 @@
+//! A struct recording the state of the bisection method after one iteration.
+
+use num::Float;
+
+/// The bracket, midpoint and NPV of the midpoint after an iteration of the bisection method.
+///
+/// # Comments
+/// Iteration 0 is the initial bracket and its midpoint, before any bisection.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection_with_config;
+/// use time_value::irr::bisection::structs::config::{BisectionConfig, BracketStrategy};
+/// use time_value::irr::bisection::structs::iteration::Iteration;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// let config: BisectionConfig<f64> = BisectionConfig::new()
+///     .with_bracket(BracketStrategy::Bounds(0.0, 0.5))
+///     .with_record_history(true);
+///
+/// let history: Vec<Iteration<f64>> =
+///     bisection_with_config(cash_flows.iter(), &config).history().unwrap().to_vec();
+/// assert_eq!(history[0].iteration(), 0);
+/// assert_eq!(history[0].irr(), 0.25);
+/// assert_eq!(history[1].rate_low(), 0.0);
+/// assert_eq!(history[1].rate_high(), 0.25);
+/// assert_eq!(history[1].irr(), 0.125);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Iteration<T>
+where
+    T: Float,
+{
+    iteration: u32,
+    rate_low: T,
+    rate_high: T,
+    irr: T,
+    npv: T,
+}
+
+impl<T> Iteration<T>
+where
+    T: Float,
+{
+    pub fn new(iteration: u32, rate_low: T, rate_high: T, irr: T, npv: T) -> Iteration<T> {
+        Iteration {
+            iteration,
+            rate_low,
+            rate_high,
+            irr,
+            npv,
+        }
+    }
+
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration` instead")]
+    pub fn get_iteration(&self) -> u32 {
+        self.iteration()
+    }
+
+    pub fn rate_low(&self) -> T {
+        self.rate_low
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_low` instead")]
+    pub fn get_rate_low(&self) -> T {
+        self.rate_low()
+    }
+
+    pub fn rate_high(&self) -> T {
+        self.rate_high
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_high` instead")]
+    pub fn get_rate_high(&self) -> T {
+        self.rate_high()
+    }
+
+    /// The midpoint of the bracket.
+    pub fn irr(&self) -> T {
+        self.irr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `irr` instead")]
+    pub fn get_irr(&self) -> T {
+        self.irr()
+    }
+
+    /// The NPV at the midpoint of the bracket.
+    pub fn npv(&self) -> T {
+        self.npv
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv` instead")]
+    pub fn get_npv(&self) -> T {
+        self.npv()
+    }
+}
@@ -0,0 +1,363 @@
+//! Calculate the IRR of a series of cash flows with Brent's method.
+
+use num::{abs, Float, Signed};
+use std::cell::Cell;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::functions::irr::is_narrow_enough;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::evaluations::counted;
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+
+/// An implementation of Brent's root finding algorithm for calculating the IRR of a series of cash flows.
+///
+/// # Assumptions
+/// As with `irr::bisection::functions::irr::bisection`, it is assumed that the NPVs at `rate_low_guess` and `rate_high_guess` have opposite signs.
+///
+/// # Comments
+/// Each iteration tries inverse quadratic interpolation or the secant method, falling back to bisection whenever the interpolated step would not shrink the bracket fast enough.
+/// Convergence is therefore guaranteed like bisection, but typically takes far fewer NPV evaluations.
+/// Iteration stops once the bracket can no longer be narrowed at the precision of `T` or the NPV is exactly zero.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::constants::NPV_PRECISION;
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::irr::brent::functions::irr::brent;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: Irr<f64> = brent(cash_flows.iter(), 0.05, 0.18, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert!(calculated_irr.npv().abs() <= f64::from(NPV_PRECISION));
+/// assert!(calculated_irr.iterations_run() < 15);
+/// ```
+pub fn brent<T>(
+    cash_flows: Iter<T>,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    brent_from_slice(
+        cash_flows.as_slice(),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// Brent's method on a slice of cash flows, which each NPV evaluation indexes directly.
+pub fn brent_from_slice<T>(
+    cash_flows: &[T],
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    brent_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+pub(crate) fn brent_with_npv<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    brent_with_npv_and_tolerances(
+        npv_at,
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        T::from(NPV_PRECISION).unwrap(),
+        None,
+        T::zero(),
+    )
+}
+
+/// Brent's method, valid by the same rule as `bisection_with_npv_and_tolerances`: the NPV is within `npv_tolerance` of zero, or the final bracket is narrow enough; see `is_narrow_enough`.
+pub(crate) fn brent_with_npv_and_tolerances<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    npv_tolerance: T,
+    rate_tolerance: Option<T>,
+    relative_rate_tolerance: T,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    let is_narrow_enough = |rate_low: T, rate_high: T| {
+        is_narrow_enough(rate_low, rate_high, rate_tolerance, relative_rate_tolerance)
+    };
+
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let two: T = T::from(2.0).unwrap();
+    let three: T = T::from(3.0).unwrap();
+    let half: T = T::from(0.5).unwrap();
+
+    let mut a: T = rate_low_guess;
+    let mut b: T = rate_high_guess;
+    let mut npv_a: T = npv_at(a);
+    let mut npv_b: T = npv_at(b);
+
+    if T::zero() < npv_a * npv_b {
+        return Irr::new(
+            a,
+            npv_a,
+            b,
+            npv_b,
+            iteration_limit,
+            0,
+            T::nan(),
+            T::nan(),
+            false,
+        )
+        .with_npv_evaluations(npv_evaluations.get());
+    }
+
+    // b is the best estimate, a the previous one and c the contrapoint, so that [b, c] brackets the root
+    let mut c: T = b;
+    let mut npv_c: T = npv_b;
+    let mut step: T = b - a;
+    let mut previous_step: T = step;
+    let mut iterations_run: u32 = 0;
+
+    while iterations_run < iteration_limit {
+        if T::zero() < npv_b * npv_c {
+            c = a;
+            npv_c = npv_a;
+            step = b - a;
+            previous_step = step;
+        }
+        if abs(npv_c) < abs(npv_b) {
+            a = b;
+            b = c;
+            c = a;
+            npv_a = npv_b;
+            npv_b = npv_c;
+            npv_c = npv_a;
+        }
+
+        let tolerance: T = two * T::epsilon() * abs(b) + half * T::epsilon();
+        let midpoint_step: T = half * (c - b);
+        if abs(midpoint_step) <= tolerance || npv_b.is_zero() || is_narrow_enough(b, c) {
+            break;
+        }
+
+        iterations_run += 1;
+
+        if tolerance <= abs(previous_step) && abs(npv_b) < abs(npv_a) {
+            let s: T = npv_b / npv_a;
+            let (mut p, mut q): (T, T) = if a == c {
+                // secant
+                (two * midpoint_step * s, T::one() - s)
+            } else {
+                // inverse quadratic interpolation
+                let q: T = npv_a / npv_c;
+                let r: T = npv_b / npv_c;
+                (
+                    s * (two * midpoint_step * q * (q - r) - (b - a) * (r - T::one())),
+                    (q - T::one()) * (r - T::one()) * (s - T::one()),
+                )
+            };
+            if T::zero() < p {
+                q = -q;
+            }
+            p = abs(p);
+
+            let interpolation_limit: T = three * midpoint_step * q - abs(tolerance * q);
+            let step_limit: T = abs(previous_step * q);
+            if two * p < interpolation_limit.min(step_limit) {
+                previous_step = step;
+                step = p / q;
+            } else {
+                step = midpoint_step;
+                previous_step = step;
+            }
+        } else {
+            step = midpoint_step;
+            previous_step = step;
+        }
+
+        a = b;
+        npv_a = npv_b;
+        b = if tolerance < abs(step) {
+            b + step
+        } else {
+            b + tolerance * midpoint_step.signum()
+        };
+        npv_b = npv_at(b);
+    }
+
+    // the last step may have left b on the same side of the root as c
+    if T::zero() < npv_b * npv_c {
+        c = a;
+        npv_c = npv_a;
+    }
+
+    let (rate_low, npv_rate_low, rate_high, npv_rate_high) = if b < c {
+        (b, npv_b, c, npv_c)
+    } else {
+        (c, npv_c, b, npv_b)
+    };
+
+    Irr::new(
+        rate_low,
+        npv_rate_low,
+        rate_high,
+        npv_rate_high,
+        iteration_limit,
+        iterations_run,
+        b,
+        npv_b,
+        npv_rate_low * npv_rate_high <= T::zero()
+            && (abs(npv_b) <= npv_tolerance || is_narrow_enough(rate_low, rate_high)),
+    )
+    .with_npv_evaluations(npv_evaluations.get())
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod brent_tests {
+    use crate::irr::bisection::constants::NPV_PRECISION;
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::brent::functions::irr::{brent, brent_with_npv_and_tolerances};
+    use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+    #[test]
+    fn it_rejects_an_invalid_bracket() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let calculated_irr: Irr<f32> = brent(cash_flows.iter(), 0.5, 0.6, 100);
+
+        assert!(!calculated_irr.is_valid());
+        assert!(calculated_irr.irr().is_nan());
+        assert_eq!(calculated_irr.iterations_run(), 0);
+        assert_eq!(calculated_irr.npv_evaluations(), 2);
+    }
+
+    #[test]
+    fn it_counts_one_npv_evaluation_per_iteration() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr: Irr<f64> = brent(cash_flows.iter(), 0.0, 0.5, 100);
+
+        assert_eq!(
+            calculated_irr.npv_evaluations(),
+            2 + calculated_irr.iterations_run()
+        );
+    }
+
+    #[test]
+    fn it_works_with_a_root_at_a_bound() {
+        let cash_flows: Vec<f64> = vec![-100.0, 110.0];
+        let calculated_irr: Irr<f64> = brent(cash_flows.iter(), 0.10, 0.50, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert_eq!(calculated_irr.irr(), 0.10);
+    }
+
+    #[test]
+    fn it_works_with_a_root_at_zero() {
+        let cash_flows: Vec<f64> = vec![-100.0, 50.0, 50.0];
+        let calculated_irr: Irr<f64> = brent(cash_flows.iter(), -0.3, 0.7, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert!(calculated_irr.irr().abs() < 0.000_000_001);
+        assert!(calculated_irr.iterations_run() < 100);
+    }
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let cash_flows: Vec<f64> = vec![
+            -122.3990963,
+            24.26782424,
+            -18.61877741,
+            -2.555946884,
+            -8.814622596,
+            32.05035057,
+            12.11973328,
+            7.743486592,
+            9.158469173,
+            -21.97032692,
+            11.18895709,
+        ];
+        let brent_irr: Irr<f64> = brent(cash_flows.iter(), -0.25, 0.25, 100);
+        let bisection_irr: Irr<f64> = bisection(cash_flows.iter(), -0.25, 0.25, 100);
+
+        assert!(brent_irr.is_valid());
+        assert!((brent_irr.irr() - bisection_irr.irr()).abs() < 0.000_001);
+        assert!(brent_irr.iterations_run() < bisection_irr.iterations_run());
+    }
+
+    #[test]
+    fn it_reports_a_bracket_of_the_root_at_the_iteration_limit() {
+        let cash_flows: Vec<f64> = vec![
+            -100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,
+        ];
+        for iteration_limit in 1..8 {
+            let calculated_irr: Irr<f64> = brent(cash_flows.iter(), -0.5, 0.9, iteration_limit);
+
+            assert!(calculated_irr.npv_rate_low() * calculated_irr.npv_rate_high() <= 0.0);
+            assert!(calculated_irr.rate_low() <= calculated_irr.irr());
+            assert!(calculated_irr.irr() <= calculated_irr.rate_high());
+            assert_eq!(
+                calculated_irr.is_valid(),
+                calculated_irr.npv().abs() <= f64::from(NPV_PRECISION)
+            );
+        }
+    }
+
+    #[test]
+    fn it_is_valid_once_the_bracket_is_narrower_than_the_rate_tolerance() {
+        // f32 cannot resolve an NPV of billions to within NPV_PRECISION
+        let cash_flows: Vec<f32> = vec![-1.0e9, 6.0e8, 6.0e8];
+        let npv_at = |rate: f32| pv(cash_flows.iter(), rate);
+        let npv_tolerance: f32 = NPV_PRECISION;
+        let strict: Irr<f32> =
+            brent_with_npv_and_tolerances(npv_at, 0.0, 0.5, 100, npv_tolerance, None, 0.0);
+        let narrow: Irr<f32> = brent_with_npv_and_tolerances(
+            npv_at,
+            0.0,
+            0.5,
+            100,
+            npv_tolerance,
+            Some(0.000_001),
+            0.0,
+        );
+
+        assert!(!strict.is_valid());
+        assert!(narrow.is_valid());
+        assert!(narrow.rate_high() - narrow.rate_low() <= 0.000_001);
+        assert!((narrow.irr() - 0.130_662).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_works_with_long_series() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; 600]);
+        let calculated_irr: Irr<f64> = brent(cash_flows.iter(), 0.0, 0.1, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert!(calculated_irr.rate_low() <= calculated_irr.irr());
+        assert!(calculated_irr.irr() <= calculated_irr.rate_high());
+    }
+}
@@ -0,0 +1,118 @@
+//! Calculates the IRR of a series of cash flows in one call, determining the initial bounds and then running the bisection method.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::functions::initial_bounds::determine;
+use crate::irr::bisection::structs::config::BisectionConfig;
+use crate::irr::bisection::structs::initial_bounds::InitialBounds;
+use crate::irr::bisection::structs::irr::Irr;
+
+/// The initial bounds found by `calculate` and the IRR found between them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calculation<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    initial_bounds: InitialBounds<T>,
+    irr: Irr<T>,
+}
+
+impl<T> Calculation<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn initial_bounds(&self) -> &InitialBounds<T> {
+        &self.initial_bounds
+    }
+
+    /// The IRR, whose NPV evaluations include those made to determine the initial bounds.
+    pub fn irr(&self) -> &Irr<T> {
+        &self.irr
+    }
+
+    /// Whether both the initial bounds and the IRR are valid.
+    pub fn is_valid(&self) -> bool {
+        self.initial_bounds.is_valid() && self.irr.is_valid()
+    }
+
+    pub fn into_irr(self) -> Irr<T> {
+        self.irr
+    }
+}
+
+/// Calculates the IRR of a series of cash flows by searching outwards from `rate_guess` for initial bounds and then running the bisection method between them.
+///
+/// # Comments
+/// `iteration_limit` applies to the search and to the bisection method separately; see `initial_bounds::determine` and `InitialBounds::solve`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::calculate::{calculate, Calculation};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// let calculation: Calculation<f64> = calculate(cash_flows.iter(), 0.10, 100);
+///
+/// assert!(calculation.is_valid());
+/// assert!((calculation.irr().irr() - 0.130_662).abs() < 0.000_01);
+/// assert!(calculation.initial_bounds().rate_low() <= calculation.irr().irr());
+/// ```
+pub fn calculate<T>(cash_flows: Iter<T>, rate_guess: T, iteration_limit: u32) -> Calculation<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let initial_bounds: InitialBounds<T> =
+        determine(cash_flows.clone(), rate_guess, iteration_limit);
+    let irr: Irr<T> = initial_bounds.solve(
+        cash_flows,
+        &BisectionConfig::new().with_iteration_limit(iteration_limit),
+    );
+    Calculation {
+        initial_bounds,
+        irr,
+    }
+}
+
+#[cfg(test)]
+mod calculate_tests {
+    use crate::irr::bisection::functions::irr::bisection_with_config;
+    use crate::irr::bisection::structs::config::{BisectionConfig, BracketStrategy};
+    use crate::irr::calculate::{calculate, Calculation};
+
+    #[test]
+    fn it_matches_bisection_with_a_search() {
+        let cash_flows: Vec<f64> = vec![
+            -100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,
+        ];
+        let calculation: Calculation<f64> = calculate(cash_flows.iter(), 0.50, 100);
+        let config: BisectionConfig<f64> =
+            BisectionConfig::new().with_bracket(BracketStrategy::Search(0.50));
+
+        assert!(calculation.is_valid());
+        assert!(calculation
+            .irr()
+            .approx_eq(&bisection_with_config(cash_flows.iter(), &config), 0.0));
+    }
+
+    #[test]
+    fn it_reports_invalid_initial_bounds() {
+        let cash_flows: Vec<f32> = vec![100.0, 60.0, 60.0];
+        let calculation: Calculation<f32> = calculate(cash_flows.iter(), 0.10, 20);
+
+        assert!(!calculation.is_valid());
+        assert!(!calculation.initial_bounds().is_valid());
+        assert!(calculation.into_irr().irr().is_nan());
+    }
+
+    #[test]
+    fn it_accepts_a_guess_that_is_already_the_irr() {
+        let cash_flows: Vec<f64> = vec![-100.0, 110.0001];
+        let calculation: Calculation<f64> = calculate(cash_flows.iter(), 0.10, 100);
+
+        assert!(calculation.is_valid());
+        assert_eq!(calculation.irr().irr(), 0.10);
+        assert_eq!(calculation.irr().iterations_run(), 0);
+    }
+}
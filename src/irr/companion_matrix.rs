@@ -0,0 +1,128 @@
+//! Find every IRR of a series of cash flows as a root of its NPV polynomial.
+
+use nalgebra::DMatrix;
+use num::{abs, Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// The tolerance, relative to the magnitude of a root, below which its imaginary part is treated as zero and two roots are treated as one.
+const ROOT_TOLERANCE: f64 = 0.000_001;
+
+/// Finds every IRR above -100% of a series of cash flows from the eigenvalues of the companion matrix of its NPV polynomial.
+///
+/// # Comments
+/// With `x = 1 / (1 + rate)` the NPV is the polynomial `sum(cash_flow_t * x^t)`, and every positive real root `x` is an IRR.
+/// Unlike scanning a grid of rates, no IRR can be missed between grid points, but the eigenvalue computation is cubic in the number of cash flows, so this suits short series.
+/// The calculation is done in `f64`; each IRR is returned with its bracket collapsed to the IRR and no iterations run, and is valid if its NPV is within `NPV_PRECISION` of zero.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::irr::companion_matrix::all_irrs;
+///
+/// // the IRRs are 0%, 10% and 20%
+/// let cash_flows: Vec<f64> = vec![-1_000.0, 3_300.0, -3_620.0, 1_320.0];
+/// let irrs: Vec<Irr<f64>> = all_irrs(cash_flows.iter());
+/// assert_eq!(irrs.len(), 3);
+/// assert!((irrs[1].irr() - 0.10).abs() < 0.000_000_1);
+/// ```
+pub fn all_irrs<T>(cash_flows: Iter<T>) -> Vec<Irr<T>>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let coefficients: Vec<f64> = cash_flows
+        .clone()
+        .map(|cash_flow| cash_flow.to_f64().unwrap())
+        .collect();
+    // leading zero cash flows are roots at x = 0 i.e. an infinite rate, and trailing ones do not raise the degree
+    let first: Option<usize> = coefficients.iter().position(|c| *c != 0.0);
+    let last: Option<usize> = coefficients.iter().rposition(|c| *c != 0.0);
+    let coefficients: &[f64] = match (first, last) {
+        (Some(first), Some(last)) => &coefficients[first..=last],
+        _ => &[],
+    };
+    let degree: usize = coefficients.len().saturating_sub(1);
+    if degree == 0 {
+        return vec![];
+    }
+
+    let mut companion: DMatrix<f64> = DMatrix::zeros(degree, degree);
+    for row in 1..degree {
+        companion[(row, row - 1)] = 1.0;
+    }
+    for row in 0..degree {
+        companion[(row, degree - 1)] = -coefficients[row] / coefficients[degree];
+    }
+
+    let mut rates: Vec<f64> = companion
+        .complex_eigenvalues()
+        .iter()
+        .filter(|x| x.im.abs() <= ROOT_TOLERANCE * (1.0 + x.re.abs()) && 0.0 < x.re)
+        .map(|x| 1.0 / x.re - 1.0)
+        .collect();
+    rates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    rates.dedup_by(|a, b| (*a - *b).abs() <= ROOT_TOLERANCE * (1.0 + b.abs()));
+
+    rates
+        .into_iter()
+        .map(|rate| {
+            let rate: T = T::from(rate).unwrap();
+            let npv: T = pv(cash_flows.clone(), rate);
+            Irr::new(
+                rate,
+                npv,
+                rate,
+                npv,
+                0,
+                0,
+                rate,
+                npv,
+                abs(npv) <= T::from(NPV_PRECISION).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod all_irrs_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::companion_matrix::all_irrs;
+
+    #[test]
+    fn it_works_without_an_irr() {
+        let no_cash_flows: Vec<f64> = vec![];
+        let one_cash_flow: Vec<f64> = vec![0.0, -100.0, 0.0];
+        let no_sign_change: Vec<f64> = vec![100.0, 10.0, 10.0];
+        assert!(all_irrs(no_cash_flows.iter()).is_empty());
+        assert!(all_irrs(one_cash_flow.iter()).is_empty());
+        assert!(all_irrs(no_sign_change.iter()).is_empty());
+    }
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let cash_flows: Vec<f32> = vec![
+            -100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0,
+        ];
+        let irrs: Vec<Irr<f32>> = all_irrs(cash_flows.iter());
+        let calculated_irr: Irr<f32> = bisection(cash_flows.iter(), 0.05, 0.18, 100);
+
+        assert_eq!(irrs.len(), 1);
+        assert!(irrs[0].is_valid());
+        assert!((irrs[0].irr() - calculated_irr.irr()).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_ignores_leading_zeros_and_finds_a_double_root() {
+        // (1 - 1.1x)^2 has a double root at an IRR of 10%
+        let cash_flows: Vec<f64> = vec![0.0, 0.0, 1.0, -2.2, 1.21];
+        let irrs: Vec<Irr<f64>> = all_irrs(cash_flows.iter());
+
+        assert_eq!(irrs.len(), 1);
+        assert!((irrs[0].irr() - 0.10).abs() < 0.000_01);
+    }
+}
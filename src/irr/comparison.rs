@@ -0,0 +1,299 @@
+//! Functions and structs for comparing the IRR solvers on the same series of cash flows.
+
+use num::{abs, Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+use std::time::{Duration, Instant};
+
+use crate::irr::auto::choose;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::false_position::structs::damping::Variant;
+use crate::irr::hybrid::functions::irr::hybrid_with_npv;
+use crate::irr::root_finder::{Bisection, Brent, FalsePosition, RootFinder};
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+use crate::present_value::npv_derivative;
+
+/// The IRR solvers that can be compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolverKind {
+    /// The solver chosen by `irr::auto::choose`.
+    Auto,
+    Bisection,
+    Brent,
+    FalsePosition(Variant),
+    Hybrid,
+}
+
+/// The bracket, iteration limit and agreement tolerance shared by every solver in a comparison.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparisonConfig<T>
+where
+    T: Float,
+{
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    tolerance: T,
+}
+
+impl<T> ComparisonConfig<T>
+where
+    T: Float,
+{
+    pub fn new(
+        rate_low_guess: T,
+        rate_high_guess: T,
+        iteration_limit: u32,
+        tolerance: T,
+    ) -> ComparisonConfig<T> {
+        ComparisonConfig {
+            rate_low_guess,
+            rate_high_guess,
+            iteration_limit,
+            tolerance,
+        }
+    }
+
+    pub fn rate_low_guess(&self) -> T {
+        self.rate_low_guess
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_low_guess` instead")]
+    pub fn get_rate_low_guess(&self) -> T {
+        self.rate_low_guess()
+    }
+
+    pub fn rate_high_guess(&self) -> T {
+        self.rate_high_guess
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate_high_guess` instead")]
+    pub fn get_rate_high_guess(&self) -> T {
+        self.rate_high_guess()
+    }
+
+    pub fn iteration_limit(&self) -> u32 {
+        self.iteration_limit
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    pub fn tolerance(&self) -> T {
+        self.tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `tolerance` instead")]
+    pub fn get_tolerance(&self) -> T {
+        self.tolerance()
+    }
+}
+
+/// The outcome of one solver in a comparison.
+///
+/// # Comments
+/// NPV evaluations include those used to check the bracket, but not the evaluations of the NPV derivative made by the hybrid solver.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverComparison<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    kind: SolverKind,
+    irr: Irr<T>,
+    runtime: Duration,
+    agrees: bool,
+}
+
+impl<T> SolverComparison<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn kind(&self) -> SolverKind {
+        self.kind
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `kind` instead")]
+    pub fn get_kind(&self) -> SolverKind {
+        self.kind()
+    }
+
+    pub fn irr(&self) -> &Irr<T> {
+        &self.irr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `irr` instead")]
+    pub fn get_irr(&self) -> &Irr<T> {
+        self.irr()
+    }
+
+    pub fn npv_evaluations(&self) -> u32 {
+        self.irr.npv_evaluations()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `npv_evaluations` instead")]
+    pub fn get_npv_evaluations(&self) -> u32 {
+        self.npv_evaluations()
+    }
+
+    pub fn runtime(&self) -> Duration {
+        self.runtime
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `runtime` instead")]
+    pub fn get_runtime(&self) -> Duration {
+        self.runtime()
+    }
+
+    /// Whether the IRR is valid and within the tolerance of the valid IRR with the smallest absolute NPV.
+    pub fn agrees(&self) -> bool {
+        self.agrees
+    }
+}
+
+/// Runs each solver on the same series of cash flows and bracket, reporting the cost and agreement of each.
+///
+/// # Comments
+/// Runtimes are measured with a single run of each solver, so they are only indicative for short series.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::comparison::{compare_solvers, ComparisonConfig, SolverComparison, SolverKind};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let config: ComparisonConfig<f64> = ComparisonConfig::new(0.05, 0.18, 100, 0.000_01);
+/// let comparisons: Vec<SolverComparison<f64>> =
+///     compare_solvers(cash_flows.iter(), &[SolverKind::Bisection, SolverKind::Brent], &config);
+///
+/// assert!(comparisons.iter().all(|comparison| comparison.agrees()));
+/// assert!(comparisons[1].npv_evaluations() < comparisons[0].npv_evaluations());
+/// ```
+pub fn compare_solvers<T>(
+    cash_flows: Iter<T>,
+    kinds: &[SolverKind],
+    config: &ComparisonConfig<T>,
+) -> Vec<SolverComparison<T>>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let mut comparisons: Vec<SolverComparison<T>> = kinds
+        .iter()
+        .map(|kind| {
+            let start: Instant = Instant::now();
+            let irr: Irr<T> = solve_with_npv(
+                *kind,
+                cash_flows.clone(),
+                &|rate| pv(cash_flows.clone(), rate),
+                config.rate_low_guess,
+                config.rate_high_guess,
+                config.iteration_limit,
+            );
+            let runtime: Duration = start.elapsed();
+
+            SolverComparison {
+                kind: *kind,
+                irr,
+                runtime,
+                agrees: false,
+            }
+        })
+        .collect();
+
+    let reference: Option<T> = comparisons
+        .iter()
+        .filter(|comparison| comparison.irr.is_valid())
+        .min_by(|a, b| abs(a.irr.npv()).partial_cmp(&abs(b.irr.npv())).unwrap())
+        .map(|comparison| comparison.irr.irr());
+    if let Some(reference) = reference {
+        for comparison in comparisons.iter_mut() {
+            comparison.agrees = comparison.irr.is_valid()
+                && abs(comparison.irr.irr() - reference) <= config.tolerance;
+        }
+    }
+
+    comparisons
+}
+
+pub(crate) fn solve_with_npv<T>(
+    kind: SolverKind,
+    cash_flows: Iter<T>,
+    npv_at: &dyn Fn(T) -> T,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let low: T = rate_low_guess;
+    let high: T = rate_high_guess;
+    match kind {
+        SolverKind::Auto => solve_with_npv(
+            choose(cash_flows.clone()).kind(),
+            cash_flows,
+            npv_at,
+            low,
+            high,
+            iteration_limit,
+        ),
+        SolverKind::Bisection => Bisection::new(iteration_limit).solve(npv_at, low, high),
+        SolverKind::Brent => Brent::new(iteration_limit).solve(npv_at, low, high),
+        SolverKind::FalsePosition(variant) => {
+            FalsePosition::new(iteration_limit, variant).solve(npv_at, low, high)
+        }
+        SolverKind::Hybrid => hybrid_with_npv(
+            npv_at,
+            |rate| npv_derivative(cash_flows.clone(), rate),
+            low,
+            high,
+            iteration_limit,
+        )
+        .into_irr(),
+    }
+}
+
+#[cfg(test)]
+mod compare_solvers_tests {
+    use crate::irr::comparison::{compare_solvers, ComparisonConfig, SolverComparison, SolverKind};
+    use crate::irr::false_position::structs::damping::Variant;
+
+    #[test]
+    fn it_works_with_every_solver() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let kinds: Vec<SolverKind> = vec![
+            SolverKind::Auto,
+            SolverKind::Bisection,
+            SolverKind::Brent,
+            SolverKind::FalsePosition(Variant::Illinois),
+            SolverKind::FalsePosition(Variant::AndersonBjorck),
+            SolverKind::Hybrid,
+        ];
+        let config: ComparisonConfig<f64> = ComparisonConfig::new(0.0, 0.5, 100, 0.000_01);
+        let comparisons: Vec<SolverComparison<f64>> =
+            compare_solvers(cash_flows.iter(), &kinds, &config);
+
+        assert_eq!(comparisons.len(), kinds.len());
+        for (comparison, kind) in comparisons.iter().zip(kinds.iter()) {
+            assert_eq!(comparison.kind(), *kind);
+            assert!(comparison.agrees());
+            assert!(2 <= comparison.npv_evaluations());
+        }
+    }
+
+    #[test]
+    fn it_finds_no_agreement_without_a_valid_irr() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let config: ComparisonConfig<f32> = ComparisonConfig::new(0.5, 0.6, 100, 0.000_1);
+        let comparisons: Vec<SolverComparison<f32>> = compare_solvers(
+            cash_flows.iter(),
+            &[SolverKind::Bisection, SolverKind::Brent],
+            &config,
+        );
+
+        assert!(comparisons.iter().all(|comparison| !comparison.agrees()));
+    }
+}
@@ -0,0 +1,72 @@
+//! Errors describing why an IRR could not be found.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The reason a solver did not find an IRR.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::functions::irr::try_bisection;
+/// use time_value::irr::error::IrrError;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 20.0, 20.0, 20.0];
+/// let result = try_bisection(cash_flows.iter(), 0.5, 0.6, 100);
+/// assert_eq!(result.unwrap_err(), IrrError::NoSignChange);
+/// assert_eq!(format!("{}", IrrError::NoSignChange), "the NPVs at the bounds have the same sign");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrrError {
+    /// The inputs cannot be solved, with a description of the problem.
+    InvalidInput(&'static str),
+    /// The NPVs at the bounds have the same sign, so the bounds do not bracket an IRR.
+    NoSignChange,
+    /// The iteration limit was reached before the NPV was within the NPV tolerance of zero.
+    IterationLimitReached,
+    /// The solver stopped before the iteration limit without the NPV being within the NPV tolerance of zero, e.g. because the precision of `T` was exhausted.
+    NotConverged,
+}
+
+impl Display for IrrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            IrrError::InvalidInput(description) => write!(f, "invalid input: {}", description),
+            IrrError::NoSignChange => write!(f, "the NPVs at the bounds have the same sign"),
+            IrrError::IterationLimitReached => write!(f, "the iteration limit was reached"),
+            IrrError::NotConverged => write!(f, "the NPV did not converge to zero"),
+        }
+    }
+}
+
+impl Error for IrrError {}
+
+#[cfg(test)]
+mod irr_error_tests {
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::error::IrrError;
+
+    #[test]
+    fn it_accepts_a_valid_irr() {
+        let irr: Irr<f64> = Irr::new(0.0, -1.0, 0.2, 1.0, 10, 3, 0.1, 0.0, true);
+        assert_eq!(irr.error(), None);
+        assert!(irr.into_result().is_ok());
+    }
+
+    #[test]
+    fn it_distinguishes_why_an_irr_is_invalid() {
+        let no_sign_change: Irr<f64> =
+            Irr::new(0.5, -1.0, 0.6, -2.0, 10, 0, f64::NAN, f64::NAN, false);
+        let limit_reached: Irr<f64> = Irr::new(0.0, -1.0, 0.2, 1.0, 10, 10, 0.1, 0.5, false);
+        let not_converged: Irr<f64> = Irr::new(0.0, -1.0, 0.2, 1.0, 10, 4, 0.1, 0.5, false);
+        let not_a_number: Irr<f64> =
+            Irr::new(0.0, f64::NAN, 0.2, 1.0, 10, 10, 0.1, f64::NAN, false);
+
+        assert_eq!(no_sign_change.error(), Some(IrrError::NoSignChange));
+        assert_eq!(limit_reached.error(), Some(IrrError::IterationLimitReached));
+        assert_eq!(not_converged.error(), Some(IrrError::NotConverged));
+        assert!(matches!(
+            not_a_number.error(),
+            Some(IrrError::InvalidInput(_))
+        ));
+    }
+}
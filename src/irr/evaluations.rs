@@ -0,0 +1,15 @@
+//! Counting the NPV evaluations made by the solvers.
+
+use std::cell::Cell;
+
+/// Wraps an NPV function so that every evaluation increments `npv_evaluations`.
+pub(crate) fn counted<'a, T, F>(npv_at: F, npv_evaluations: &'a Cell<u32>) -> impl Fn(T) -> T + 'a
+where
+    T: 'a,
+    F: Fn(T) -> T + 'a,
+{
+    move |rate: T| {
+        npv_evaluations.set(npv_evaluations.get() + 1);
+        npv_at(rate)
+    }
+}
@@ -0,0 +1,301 @@
+//! Calculate the IRR of a series of cash flows with the modified false position method.
+
+use num::{abs, Float, Signed};
+use std::cell::Cell;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::functions::irr::is_narrow_enough;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::evaluations::counted;
+use crate::irr::false_position::structs::damping::{DampedUpdate, Endpoint, Variant};
+use crate::irr::false_position::structs::irr::FalsePositionIrr;
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+
+/// An implementation of the modified false position (regula falsi) method for calculating the IRR of a series of cash flows.
+///
+/// # Assumptions
+/// As with `irr::bisection::functions::irr::bisection`, it is assumed that the NPVs at `rate_low_guess` and `rate_high_guess` have opposite signs.
+///
+/// # Comments
+/// Plain false position stalls when the NPV curve is very flat or strongly curved near the root, because one endpoint is retained forever.
+/// Whenever the same endpoint is retained twice in a row its NPV is damped according to `variant`, which restores superlinear convergence.
+/// Every damping is recorded in the result, so a long run of damped updates of one endpoint reveals a difficult NPV curve.
+/// Iteration stops once successive estimates agree at the precision of `T` or the NPV is exactly zero.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::false_position::functions::irr::false_position;
+/// use time_value::irr::false_position::structs::damping::Variant;
+/// use time_value::irr::false_position::structs::irr::FalsePositionIrr;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: FalsePositionIrr<f64> =
+///     false_position(cash_flows.iter(), 0.05, 0.18, 100, Variant::Illinois);
+/// assert!(calculated_irr.irr().is_valid());
+/// assert!(calculated_irr.irr().iterations_run() < 20);
+/// assert!(!calculated_irr.damped_updates().is_empty());
+/// ```
+pub fn false_position<T>(
+    cash_flows: Iter<T>,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    variant: Variant,
+) -> FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    false_position_from_slice(
+        cash_flows.as_slice(),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        variant,
+    )
+}
+
+/// The false position method on a slice of cash flows, which each NPV evaluation indexes directly.
+pub fn false_position_from_slice<T>(
+    cash_flows: &[T],
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    variant: Variant,
+) -> FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    false_position_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        variant,
+    )
+}
+
+pub(crate) fn false_position_with_npv<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    variant: Variant,
+) -> FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    false_position_with_npv_and_tolerances(
+        npv_at,
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+        variant,
+        T::from(NPV_PRECISION).unwrap(),
+        T::zero(),
+    )
+}
+
+/// The modified false position method, valid by the same rule as `bisection_with_npv_and_tolerances`: the NPV is within `npv_tolerance` of zero, or the final bracket is narrow enough; see `is_narrow_enough`.
+pub(crate) fn false_position_with_npv_and_tolerances<T, F>(
+    npv_at: F,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+    variant: Variant,
+    npv_tolerance: T,
+    relative_rate_tolerance: T,
+) -> FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    let is_narrow_enough =
+        |low: T, high: T| is_narrow_enough(low, high, None, relative_rate_tolerance);
+
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let two: T = T::from(2.0).unwrap();
+    let half: T = T::from(0.5).unwrap();
+
+    let mut low: T = rate_low_guess;
+    let mut high: T = rate_high_guess;
+    let mut npv_low: T = npv_at(low);
+    let mut npv_high: T = npv_at(high);
+
+    if T::zero() < npv_low * npv_high {
+        let irr: Irr<T> = Irr::new(
+            low,
+            npv_low,
+            high,
+            npv_high,
+            iteration_limit,
+            0,
+            T::nan(),
+            T::nan(),
+            false,
+        )
+        .with_npv_evaluations(npv_evaluations.get());
+        return FalsePositionIrr::new(irr, variant, vec![]);
+    }
+
+    // the damped NPVs only steer the interpolation; the bracket reports the actual NPVs
+    let mut weight_low: T = npv_low;
+    let mut weight_high: T = npv_high;
+    let mut last_replaced: Option<Endpoint> = None;
+    let mut damped_updates: Vec<DampedUpdate<T>> = vec![];
+
+    let (mut estimate, mut npv_estimate): (T, T) = if abs(npv_low) < abs(npv_high) {
+        (low, npv_low)
+    } else {
+        (high, npv_high)
+    };
+    let mut iterations_run: u32 = 0;
+
+    while iterations_run < iteration_limit && !npv_estimate.is_zero() {
+        iterations_run += 1;
+
+        let previous_estimate: T = estimate;
+        estimate = (weight_low * high - weight_high * low) / (weight_low - weight_high);
+        npv_estimate = npv_at(estimate);
+
+        let replaced: Endpoint = if T::zero() < npv_estimate * npv_high {
+            Endpoint::High
+        } else {
+            Endpoint::Low
+        };
+        let (retained, npv_replaced): (Endpoint, T) = match replaced {
+            Endpoint::High => {
+                let npv_replaced: T = weight_high;
+                high = estimate;
+                npv_high = npv_estimate;
+                weight_high = npv_estimate;
+                (Endpoint::Low, npv_replaced)
+            }
+            Endpoint::Low => {
+                let npv_replaced: T = weight_low;
+                low = estimate;
+                npv_low = npv_estimate;
+                weight_low = npv_estimate;
+                (Endpoint::High, npv_replaced)
+            }
+        };
+
+        if last_replaced == Some(replaced) {
+            let factor: T = match variant {
+                Variant::Illinois => half,
+                Variant::AndersonBjorck => {
+                    let factor: T = T::one() - npv_estimate / npv_replaced;
+                    if T::zero() < factor {
+                        factor
+                    } else {
+                        half
+                    }
+                }
+            };
+            match retained {
+                Endpoint::Low => weight_low = weight_low * factor,
+                Endpoint::High => weight_high = weight_high * factor,
+            }
+            damped_updates.push(DampedUpdate::new(iterations_run, retained, factor));
+        }
+        last_replaced = Some(replaced);
+
+        let tolerance: T = two * T::epsilon() * abs(estimate) + half * T::epsilon();
+        if abs(estimate - previous_estimate) <= tolerance || is_narrow_enough(low, high) {
+            break;
+        }
+    }
+
+    let irr: Irr<T> = Irr::new(
+        low,
+        npv_low,
+        high,
+        npv_high,
+        iteration_limit,
+        iterations_run,
+        estimate,
+        npv_estimate,
+        abs(npv_estimate) <= npv_tolerance || is_narrow_enough(low, high),
+    )
+    .with_npv_evaluations(npv_evaluations.get());
+    FalsePositionIrr::new(irr, variant, damped_updates)
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod false_position_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::false_position::functions::irr::false_position;
+    use crate::irr::false_position::structs::damping::{Endpoint, Variant};
+    use crate::irr::false_position::structs::irr::FalsePositionIrr;
+
+    #[test]
+    fn it_rejects_an_invalid_bracket() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let calculated_irr: FalsePositionIrr<f32> =
+            false_position(cash_flows.iter(), 0.5, 0.6, 100, Variant::Illinois);
+
+        assert!(!calculated_irr.irr().is_valid());
+        assert!(calculated_irr.irr().irr().is_nan());
+        assert!(calculated_irr.damped_updates().is_empty());
+    }
+
+    #[test]
+    fn it_works_with_a_root_at_a_bound() {
+        let cash_flows: Vec<f64> = vec![-100.0, 110.0];
+        let calculated_irr: FalsePositionIrr<f64> =
+            false_position(cash_flows.iter(), 0.10, 0.50, 100, Variant::AndersonBjorck);
+
+        assert!(calculated_irr.irr().is_valid());
+        assert_eq!(calculated_irr.irr().irr(), 0.10);
+        assert_eq!(calculated_irr.irr().iterations_run(), 0);
+    }
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let cash_flows: Vec<f64> = vec![
+            -122.3990963,
+            24.26782424,
+            -18.61877741,
+            -2.555946884,
+            -8.814622596,
+            32.05035057,
+            12.11973328,
+            7.743486592,
+            9.158469173,
+            -21.97032692,
+            11.18895709,
+        ];
+        let bisection_irr: Irr<f64> = bisection(cash_flows.iter(), -0.25, 0.25, 100);
+        for variant in [Variant::Illinois, Variant::AndersonBjorck].iter() {
+            let calculated_irr: FalsePositionIrr<f64> =
+                false_position(cash_flows.iter(), -0.25, 0.25, 100, *variant);
+
+            assert!(calculated_irr.irr().is_valid());
+            assert!((calculated_irr.irr().irr() - bisection_irr.irr()).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn it_damps_the_retained_endpoint_of_a_flat_curve() {
+        // the NPV is nearly flat at high rates, so plain false position keeps replacing the low endpoint
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; 600]);
+        for variant in [Variant::Illinois, Variant::AndersonBjorck].iter() {
+            let calculated_irr: FalsePositionIrr<f64> =
+                false_position(cash_flows.iter(), 0.0, 10.0, 100, *variant);
+
+            assert!(calculated_irr.irr().is_valid());
+            assert!(calculated_irr.irr().iterations_run() < 100);
+            assert!(calculated_irr
+                .damped_updates()
+                .iter()
+                .any(|update| update.endpoint() == Endpoint::High));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Structs describing how the modified false position method damps its endpoints.
+
+use num::Float;
+
+/// The rule used to damp the NPV of an endpoint that has been retained twice in a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// Halves the retained NPV.
+    Illinois,
+    /// Scales the retained NPV by `1 - npv_new / npv_replaced`, or halves it if that is not positive.
+    AndersonBjorck,
+}
+
+/// An endpoint of the bracket.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Endpoint {
+    Low,
+    High,
+}
+
+/// A record of one damped endpoint update.
+///
+/// # Example
+/// ```
+/// use time_value::irr::false_position::structs::damping::{DampedUpdate, Endpoint};
+///
+/// let update: DampedUpdate<f64> = DampedUpdate::new(3, Endpoint::Low, 0.5);
+/// assert_eq!(update.iteration(), 3);
+/// assert_eq!(update.endpoint(), Endpoint::Low);
+/// assert_eq!(update.factor(), 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DampedUpdate<T>
+where
+    T: Float,
+{
+    iteration: u32,
+    endpoint: Endpoint,
+    factor: T,
+}
+
+impl<T> DampedUpdate<T>
+where
+    T: Float,
+{
+    pub fn new(iteration: u32, endpoint: Endpoint, factor: T) -> DampedUpdate<T> {
+        DampedUpdate {
+            iteration,
+            endpoint,
+            factor,
+        }
+    }
+
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration` instead")]
+    pub fn get_iteration(&self) -> u32 {
+        self.iteration()
+    }
+
+    pub fn endpoint(&self) -> Endpoint {
+        self.endpoint
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `endpoint` instead")]
+    pub fn get_endpoint(&self) -> Endpoint {
+        self.endpoint()
+    }
+
+    pub fn factor(&self) -> T {
+        self.factor
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `factor` instead")]
+    pub fn get_factor(&self) -> T {
+        self.factor()
+    }
+}
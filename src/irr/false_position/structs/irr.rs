@@ -0,0 +1,71 @@
+//! A struct containing the result of calculating an IRR with the modified false position method.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::false_position::structs::damping::{DampedUpdate, Variant};
+
+/// The IRR found by the modified false position method, with a record of every damped endpoint update.
+///
+/// # Comments
+/// Many damped updates of the same endpoint indicate an NPV curve that is very flat or strongly curved near the root.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    irr: Irr<T>,
+    variant: Variant,
+    damped_updates: Vec<DampedUpdate<T>>,
+}
+
+impl<T> FalsePositionIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn new(
+        irr: Irr<T>,
+        variant: Variant,
+        damped_updates: Vec<DampedUpdate<T>>,
+    ) -> FalsePositionIrr<T> {
+        FalsePositionIrr {
+            irr,
+            variant,
+            damped_updates,
+        }
+    }
+
+    pub fn irr(&self) -> &Irr<T> {
+        &self.irr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `irr` instead")]
+    pub fn get_irr(&self) -> &Irr<T> {
+        self.irr()
+    }
+
+    /// Discards the record of damped updates.
+    pub fn into_irr(self) -> Irr<T> {
+        self.irr
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `variant` instead")]
+    pub fn get_variant(&self) -> Variant {
+        self.variant()
+    }
+
+    pub fn damped_updates(&self) -> &[DampedUpdate<T>] {
+        &self.damped_updates
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `damped_updates` instead")]
+    pub fn get_damped_updates(&self) -> &[DampedUpdate<T>] {
+        self.damped_updates()
+    }
+}
@@ -0,0 +1,209 @@
+//! Calculate the IRR of a series of cash flows with Halley's method.
+
+use num::{abs, Float, Signed};
+use std::cell::Cell;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::functions::midpoint;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::evaluations::counted;
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+use crate::present_value::{npv_derivative, npv_second_derivative};
+
+/// An implementation of Halley's method for calculating the IRR of a series of cash flows, safeguarded by a bracket.
+///
+/// # Assumptions
+/// As with `irr::bisection::functions::irr::bisection`, it is assumed that the NPVs at `rate_low_guess` and `rate_high_guess` have opposite signs.
+///
+/// # Comments
+/// Each iteration takes the step `-2 * npv * npv' / (2 * npv'^2 - npv * npv'')`, which converges cubically on smooth NPV curves.
+/// The bracket is narrowed around the root as the iterations proceed, and a step that would leave it is replaced by bisection.
+/// Iteration stops once the step is negligible at the precision of `T` or the NPV is exactly zero.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::irr::halley::functions::irr::halley;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: Irr<f64> = halley(cash_flows.iter(), 0.05, 0.18, 100);
+/// assert!(calculated_irr.is_valid());
+/// assert!(calculated_irr.iterations_run() <= 5);
+/// ```
+pub fn halley<T>(
+    cash_flows: Iter<T>,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    halley_from_slice(
+        cash_flows.as_slice(),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// Halley's method on a slice of cash flows, which the NPV evaluations index directly.
+pub fn halley_from_slice<T>(
+    cash_flows: &[T],
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    halley_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        |rate| npv_derivative(cash_flows.iter(), rate),
+        |rate| npv_second_derivative(cash_flows.iter(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+pub(crate) fn halley_with_npv<T, F, D, S>(
+    npv_at: F,
+    npv_derivative_at: D,
+    npv_second_derivative_at: S,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+    D: Fn(T) -> T,
+    S: Fn(T) -> T,
+{
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let two: T = T::from(2.0).unwrap();
+    let half: T = T::from(0.5).unwrap();
+
+    let mut rate_low: T = rate_low_guess;
+    let mut rate_high: T = rate_high_guess;
+    let mut npv_rate_low: T = npv_at(rate_low);
+    let mut npv_rate_high: T = npv_at(rate_high);
+
+    if T::zero() < npv_rate_low * npv_rate_high {
+        return Irr::new(
+            rate_low,
+            npv_rate_low,
+            rate_high,
+            npv_rate_high,
+            iteration_limit,
+            0,
+            T::nan(),
+            T::nan(),
+            false,
+        )
+        .with_npv_evaluations(npv_evaluations.get());
+    }
+
+    let mut irr: T = midpoint::calculate(rate_low, rate_high);
+    let mut npv: T = npv_at(irr);
+    let mut iterations_run: u32 = 0;
+
+    while iterations_run < iteration_limit && !npv.is_zero() {
+        iterations_run += 1;
+
+        if npv_rate_low * npv < T::zero() {
+            rate_high = irr;
+            npv_rate_high = npv;
+        } else {
+            rate_low = irr;
+            npv_rate_low = npv;
+        }
+
+        let first: T = npv_derivative_at(irr);
+        let second: T = npv_second_derivative_at(irr);
+        let step: T = -two * npv * first / (two * first * first - npv * second);
+        let lower: T = rate_low.min(rate_high);
+        let upper: T = rate_low.max(rate_high);
+        let next_irr: T = if step.is_finite() && lower < irr + step && irr + step < upper {
+            irr + step
+        } else {
+            midpoint::calculate(rate_low, rate_high)
+        };
+
+        let tolerance: T = two * T::epsilon() * abs(next_irr) + half * T::epsilon();
+        let has_converged: bool = abs(next_irr - irr) <= tolerance;
+        irr = next_irr;
+        npv = npv_at(irr);
+        if has_converged {
+            break;
+        }
+    }
+
+    Irr::new(
+        rate_low,
+        npv_rate_low,
+        rate_high,
+        npv_rate_high,
+        iteration_limit,
+        iterations_run,
+        irr,
+        npv,
+        abs(npv) <= T::from(NPV_PRECISION).unwrap(),
+    )
+    .with_npv_evaluations(npv_evaluations.get())
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod halley_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::halley::functions::irr::halley;
+
+    #[test]
+    fn it_rejects_an_invalid_bracket() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let calculated_irr: Irr<f32> = halley(cash_flows.iter(), 0.5, 0.6, 100);
+
+        assert!(!calculated_irr.is_valid());
+        assert!(calculated_irr.irr().is_nan());
+    }
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let cash_flows: Vec<f64> = vec![
+            -122.3990963,
+            24.26782424,
+            -18.61877741,
+            -2.555946884,
+            -8.814622596,
+            32.05035057,
+            12.11973328,
+            7.743486592,
+            9.158469173,
+            -21.97032692,
+            11.18895709,
+        ];
+        let halley_irr: Irr<f64> = halley(cash_flows.iter(), -0.25, 0.25, 100);
+        let bisection_irr: Irr<f64> = bisection(cash_flows.iter(), -0.25, 0.25, 100);
+
+        assert!(halley_irr.is_valid());
+        assert!((halley_irr.irr() - bisection_irr.irr()).abs() < 0.000_001);
+        assert!(halley_irr.iterations_run() < 10);
+    }
+
+    #[test]
+    fn it_stays_in_the_bracket_on_a_flat_curve() {
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; 600]);
+        let calculated_irr: Irr<f64> = halley(cash_flows.iter(), 0.0, 10.0, 100);
+
+        assert!(calculated_irr.is_valid());
+        assert!(0.0 <= calculated_irr.irr() && calculated_irr.irr() <= 10.0);
+    }
+}
@@ -0,0 +1,205 @@
+//! Calculate the IRR of a series of cash flows with Newton's method, falling back to bisection.
+
+use num::{abs, Float, Signed};
+use std::cell::Cell;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::functions::irr::bisection_with_npv;
+use crate::irr::bisection::functions::midpoint;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::evaluations::counted;
+use crate::irr::hybrid::structs::irr::{HybridIrr, Method};
+use crate::present_value::from_slice_and_discount_rate as slice_pv;
+use crate::present_value::npv_derivative;
+
+/// Calculates the IRR of a series of cash flows with Newton's method for speed, falling back to bisection for robustness.
+///
+/// # Assumptions
+/// As with `irr::bisection::functions::irr::bisection`, it is assumed that the NPVs at `rate_low_guess` and `rate_high_guess` have opposite signs.
+///
+/// # Comments
+/// Newton's method starts from the midpoint of the bracket.
+/// If an iterate escapes the bracket, is not finite or increases the absolute NPV, the Newton iterations are abandoned and bisection runs within the original bracket for the remaining iterations.
+/// The result reports which method produced the final answer.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::hybrid::functions::irr::hybrid;
+/// use time_value::irr::hybrid::structs::irr::{HybridIrr, Method};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: HybridIrr<f64> = hybrid(cash_flows.iter(), 0.05, 0.18, 100);
+/// assert_eq!(calculated_irr.method(), Method::Newton);
+/// assert!(calculated_irr.irr().is_valid());
+/// assert!(calculated_irr.irr().iterations_run() < 10);
+/// ```
+pub fn hybrid<T>(
+    cash_flows: Iter<T>,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> HybridIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    hybrid_from_slice(
+        cash_flows.as_slice(),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+/// The hybrid method on a slice of cash flows, which the NPV evaluations index directly.
+pub fn hybrid_from_slice<T>(
+    cash_flows: &[T],
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> HybridIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    hybrid_with_npv(
+        |rate| slice_pv(cash_flows, rate),
+        |rate| npv_derivative(cash_flows.iter(), rate),
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit,
+    )
+}
+
+pub(crate) fn hybrid_with_npv<T, F, D>(
+    npv_at: F,
+    npv_derivative_at: D,
+    rate_low_guess: T,
+    rate_high_guess: T,
+    iteration_limit: u32,
+) -> HybridIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+    D: Fn(T) -> T,
+{
+    let npv_evaluations: Cell<u32> = Cell::new(0);
+    let npv_at = counted(npv_at, &npv_evaluations);
+
+    let two: T = T::from(2.0).unwrap();
+    let half: T = T::from(0.5).unwrap();
+
+    let npv_rate_low: T = npv_at(rate_low_guess);
+    let npv_rate_high: T = npv_at(rate_high_guess);
+    let lower: T = rate_low_guess.min(rate_high_guess);
+    let upper: T = rate_low_guess.max(rate_high_guess);
+
+    let mut rate: T = midpoint::calculate(rate_low_guess, rate_high_guess);
+    let mut npv: T = npv_at(rate);
+    let mut iterations_run: u32 = 0;
+    let mut has_converged: bool = npv.is_zero();
+    let mut has_escaped: bool = T::zero() < npv_rate_low * npv_rate_high;
+
+    while !has_converged && !has_escaped && iterations_run < iteration_limit {
+        iterations_run += 1;
+
+        let next_rate: T = rate - npv / npv_derivative_at(rate);
+        if !next_rate.is_finite() || next_rate < lower || upper < next_rate {
+            has_escaped = true;
+            break;
+        }
+        let next_npv: T = npv_at(next_rate);
+        if abs(npv) < abs(next_npv) {
+            has_escaped = true;
+            break;
+        }
+
+        let tolerance: T = two * T::epsilon() * abs(next_rate) + half * T::epsilon();
+        has_converged = abs(next_rate - rate) <= tolerance || next_npv.is_zero();
+        rate = next_rate;
+        npv = next_npv;
+    }
+
+    if has_converged || !has_escaped {
+        let irr: Irr<T> = Irr::new(
+            rate_low_guess,
+            npv_rate_low,
+            rate_high_guess,
+            npv_rate_high,
+            iteration_limit,
+            iterations_run,
+            rate,
+            npv,
+            abs(npv) <= T::from(NPV_PRECISION).unwrap(),
+        )
+        .with_npv_evaluations(npv_evaluations.get());
+        return HybridIrr::new(irr, Method::Newton, iterations_run);
+    }
+
+    let irr: Irr<T> = bisection_with_npv(
+        npv_at,
+        rate_low_guess,
+        rate_high_guess,
+        iteration_limit - iterations_run,
+    );
+    HybridIrr::new(
+        irr.with_npv_evaluations(npv_evaluations.get()),
+        Method::Bisection,
+        iterations_run,
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::excessive_precision)]
+mod hybrid_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::hybrid::functions::irr::hybrid;
+    use crate::irr::hybrid::structs::irr::{HybridIrr, Method};
+
+    #[test]
+    fn it_rejects_an_invalid_bracket() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0, 20.0, 20.0];
+        let calculated_irr: HybridIrr<f32> = hybrid(cash_flows.iter(), 0.5, 0.6, 100);
+
+        assert_eq!(calculated_irr.method(), Method::Bisection);
+        assert_eq!(calculated_irr.newton_iterations(), 0);
+        assert!(!calculated_irr.irr().is_valid());
+        assert!(calculated_irr.irr().irr().is_nan());
+    }
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let cash_flows: Vec<f64> = vec![
+            -122.3990963,
+            24.26782424,
+            -18.61877741,
+            -2.555946884,
+            -8.814622596,
+            32.05035057,
+            12.11973328,
+            7.743486592,
+            9.158469173,
+            -21.97032692,
+            11.18895709,
+        ];
+        let hybrid_irr: HybridIrr<f64> = hybrid(cash_flows.iter(), -0.25, 0.25, 100);
+        let bisection_irr: Irr<f64> = bisection(cash_flows.iter(), -0.25, 0.25, 100);
+
+        assert!(hybrid_irr.irr().is_valid());
+        assert!((hybrid_irr.irr().irr() - bisection_irr.irr()).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_falls_back_to_bisection_when_newton_escapes() {
+        // the NPV is very flat at high rates, so the first Newton step from the midpoint overshoots the bracket
+        let mut cash_flows: Vec<f64> = vec![-1_000.0];
+        cash_flows.extend(vec![12.0; 600]);
+        let calculated_irr: HybridIrr<f64> = hybrid(cash_flows.iter(), 0.0, 10.0, 100);
+
+        assert_eq!(calculated_irr.method(), Method::Bisection);
+        assert!(calculated_irr.irr().is_valid());
+        assert!(0.0 <= calculated_irr.irr().irr());
+        assert!(calculated_irr.irr().irr() <= 10.0);
+    }
+}
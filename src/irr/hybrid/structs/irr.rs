@@ -0,0 +1,73 @@
+//! A struct containing the result of calculating an IRR with the hybrid method.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::irr::bisection::structs::irr::Irr;
+
+/// The method that produced the final answer of the hybrid method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Method {
+    Newton,
+    Bisection,
+}
+
+/// The IRR found by the hybrid method, with the method that produced it.
+///
+/// # Comments
+/// When the method is `Bisection`, the Newton iterations that were abandoned are reported by `newton_iterations`; the iterations of the `Irr` are those of the bisection alone.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HybridIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    irr: Irr<T>,
+    method: Method,
+    newton_iterations: u32,
+}
+
+impl<T> HybridIrr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn new(irr: Irr<T>, method: Method, newton_iterations: u32) -> HybridIrr<T> {
+        HybridIrr {
+            irr,
+            method,
+            newton_iterations,
+        }
+    }
+
+    pub fn irr(&self) -> &Irr<T> {
+        &self.irr
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `irr` instead")]
+    pub fn get_irr(&self) -> &Irr<T> {
+        self.irr()
+    }
+
+    pub fn into_irr(self) -> Irr<T> {
+        self.irr
+    }
+
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `method` instead")]
+    pub fn get_method(&self) -> Method {
+        self.method()
+    }
+
+    pub fn newton_iterations(&self) -> u32 {
+        self.newton_iterations
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `newton_iterations` instead")]
+    pub fn get_newton_iterations(&self) -> u32 {
+        self.newton_iterations()
+    }
+}
@@ -0,0 +1,153 @@
+//! Calculate the modified internal rate of return (MIRR) of a series of cash flows.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::root_finder::{RootFinder, SolveResult};
+use crate::power::{powf, powi};
+
+/// Calculates the MIRR of a series of cash flows, where outflows are financed at `finance_rate` and inflows are reinvested at `reinvestment_rate`.
+///
+/// # Comments
+/// With `n` periods after the first, the MIRR is `(FV(inflows, reinvestment_rate) / -PV(outflows, finance_rate))^(1 / n) - 1`, as in spreadsheet `MIRR` functions.
+/// The MIRR is NaN if the series has no inflows or no outflows.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::mirr::mirr;
+///
+/// let cash_flows: Vec<f64> = vec![-120_000.0, 39_000.0, 30_000.0, 21_000.0, 37_000.0, 46_000.0];
+/// let calculated_mirr: f64 = mirr(cash_flows.iter(), 0.10, 0.12);
+/// assert!((calculated_mirr - 0.126_094).abs() < 0.000_001);
+/// ```
+pub fn mirr<T>(cash_flows: Iter<T>, finance_rate: T, reinvestment_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let periods: usize = cash_flows.len().saturating_sub(1);
+    let finance: T = T::one() + finance_rate;
+    let reinvestment: T = T::one() + reinvestment_rate;
+
+    let (outflows, inflows): (T, T) = cash_flows.enumerate().fold(
+        (T::zero(), T::zero()),
+        |(outflows, inflows), (period, cash_flow)| {
+            if *cash_flow < T::zero() {
+                (
+                    outflows + *cash_flow * powi(finance, -(period as i32)),
+                    inflows,
+                )
+            } else {
+                let remaining: i32 = (periods - period) as i32;
+                (
+                    outflows,
+                    inflows + *cash_flow * powi(reinvestment, remaining),
+                )
+            }
+        },
+    );
+
+    if periods == 0 || outflows.is_zero() || inflows.is_zero() {
+        return T::nan();
+    }
+    powf(inflows / -outflows, T::one() / T::from(periods).unwrap()) - T::one()
+}
+
+/// Solves for the reinvestment rate at which the MIRR of a series of cash flows equals `target_mirr`, e.g. a hurdle rate.
+///
+/// # Assumptions
+/// It is assumed that the breakeven reinvestment rate lies between `rate_low_guess` and `rate_high_guess`.
+///
+/// # Comments
+/// The MIRR increases with the reinvestment rate, so there is at most one breakeven rate; `root_finder` may be any `irr::root_finder::RootFinder`.
+/// The `npv` fields of the result hold the difference between the MIRR and `target_mirr`.
+/// Bisection stops once that difference is within `NPV_PRECISION`, which is coarse for a rate, so Brent's method is the better choice when precision matters.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::mirr::breakeven_reinvestment_rate;
+/// use time_value::irr::root_finder::{Brent, SolveResult};
+///
+/// let cash_flows: Vec<f64> = vec![-120_000.0, 39_000.0, 30_000.0, 21_000.0, 37_000.0, 46_000.0];
+/// let solved: SolveResult<f64> =
+///     breakeven_reinvestment_rate(cash_flows.iter(), 0.10, 0.126_094, &Brent::new(100), 0.0, 0.5);
+/// assert!(solved.is_valid());
+/// assert!((solved.irr() - 0.12).abs() < 0.000_01);
+/// ```
+pub fn breakeven_reinvestment_rate<T, R>(
+    cash_flows: Iter<T>,
+    finance_rate: T,
+    target_mirr: T,
+    root_finder: &R,
+    rate_low_guess: T,
+    rate_high_guess: T,
+) -> SolveResult<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    R: RootFinder<T>,
+{
+    root_finder.solve(
+        |reinvestment_rate| mirr(cash_flows.clone(), finance_rate, reinvestment_rate) - target_mirr,
+        rate_low_guess,
+        rate_high_guess,
+    )
+}
+
+#[cfg(test)]
+mod mirr_tests {
+    use crate::irr::mirr::mirr;
+
+    #[test]
+    fn it_is_nan_without_both_inflows_and_outflows() {
+        let no_cash_flows: Vec<f64> = vec![];
+        let no_inflows: Vec<f64> = vec![-100.0, -10.0];
+        let no_outflows: Vec<f64> = vec![100.0, 10.0];
+        assert!(mirr(no_cash_flows.iter(), 0.1, 0.1).is_nan());
+        assert!(mirr(no_inflows.iter(), 0.1, 0.1).is_nan());
+        assert!(mirr(no_outflows.iter(), 0.1, 0.1).is_nan());
+    }
+
+    #[test]
+    fn it_equals_the_irr_when_both_rates_are_the_irr() {
+        let cash_flows: Vec<f64> = vec![-100.0, 0.0, 121.0];
+        assert!((mirr(cash_flows.iter(), 0.1, 0.1) - 0.1).abs() < 0.000_000_1);
+    }
+}
+
+#[cfg(test)]
+mod breakeven_reinvestment_rate_tests {
+    use crate::irr::mirr::{breakeven_reinvestment_rate, mirr};
+    use crate::irr::root_finder::{Bisection, Brent, SolveResult};
+
+    #[test]
+    fn it_round_trips_with_mirr() {
+        let cash_flows: Vec<f64> = vec![-1_000.0, 300.0, -200.0, 600.0, 700.0];
+        let target: f64 = mirr(cash_flows.iter(), 0.08, 0.15);
+        let solved: SolveResult<f64> = breakeven_reinvestment_rate(
+            cash_flows.iter(),
+            0.08,
+            target,
+            &Brent::new(100),
+            -0.5,
+            1.0,
+        );
+
+        assert!(solved.is_valid());
+        assert!((solved.irr() - 0.15).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_rejects_a_target_outside_the_bracket() {
+        let cash_flows: Vec<f64> = vec![-1_000.0, 300.0, 400.0, 500.0];
+        let solved: SolveResult<f64> = breakeven_reinvestment_rate(
+            cash_flows.iter(),
+            0.08,
+            0.5,
+            &Bisection::new(100),
+            0.0,
+            0.2,
+        );
+
+        assert!(!solved.is_valid());
+    }
+}
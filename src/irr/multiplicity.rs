@@ -0,0 +1,154 @@
+//! Inspects a series of cash flows for how many IRRs it may have.
+
+use num::Float;
+use std::slice::Iter;
+
+use crate::cash_flows::sign_changes;
+
+/// Bounds on the number of IRRs above -100% of a series of cash flows.
+///
+/// # Comments
+/// With `x = 1 / (1 + rate)` the NPV is a polynomial in `x`, and rates above -100% correspond to positive `x`, so:
+/// - by Descartes' rule of signs there are at most as many IRRs as sign changes, and the count differs from that by an even number
+/// - equivalently, the NPV has the sign of the first non-zero cash flow as the rate tends to infinity and the sign of the last as it tends to -100%, so an odd count guarantees at least one IRR
+/// - by Norstrom's criterion, if the cumulative cash flows change sign exactly once and do not sum to zero, there is exactly one positive IRR
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Multiplicity {
+    sign_changes: usize,
+    cumulative_sign_changes: usize,
+    is_total_zero: bool,
+}
+
+impl Multiplicity {
+    /// The number of sign changes, ignoring zero cash flows, which bounds the number of IRRs.
+    pub fn sign_changes(&self) -> usize {
+        self.sign_changes
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `sign_changes` instead")]
+    pub fn get_sign_changes(&self) -> usize {
+        self.sign_changes()
+    }
+
+    /// The number of sign changes of the cumulative cash flows, ignoring zeros.
+    pub fn cumulative_sign_changes(&self) -> usize {
+        self.cumulative_sign_changes
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `cumulative_sign_changes` instead")]
+    pub fn get_cumulative_sign_changes(&self) -> usize {
+        self.cumulative_sign_changes()
+    }
+
+    pub fn min_irrs(&self) -> usize {
+        self.sign_changes % 2
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `min_irrs` instead")]
+    pub fn get_min_irrs(&self) -> usize {
+        self.min_irrs()
+    }
+
+    pub fn max_irrs(&self) -> usize {
+        self.sign_changes
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `max_irrs` instead")]
+    pub fn get_max_irrs(&self) -> usize {
+        self.max_irrs()
+    }
+
+    /// Whether there is exactly one IRR above -100%.
+    pub fn has_unique_irr(&self) -> bool {
+        self.sign_changes == 1
+    }
+
+    /// Whether there is exactly one positive IRR, by Norstrom's criterion.
+    ///
+    /// # Comments
+    /// This can hold even when `has_unique_irr` does not, in which case any other IRRs are at or below 0%.
+    pub fn has_unique_positive_irr(&self) -> bool {
+        self.cumulative_sign_changes == 1 && !self.is_total_zero
+    }
+
+    /// Whether a single root found by a solver may not be the only IRR.
+    pub fn may_have_multiple_irrs(&self) -> bool {
+        1 < self.sign_changes
+    }
+}
+
+/// Counts the sign changes of a series of cash flows and of its cumulative sums to bound how many IRRs it has.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::multiplicity::{inspect, Multiplicity};
+///
+/// // the IRRs are 10% and 20%
+/// let cash_flows: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// let multiplicity: Multiplicity = inspect(cash_flows.iter());
+/// assert_eq!(multiplicity.min_irrs(), 0);
+/// assert_eq!(multiplicity.max_irrs(), 2);
+/// assert!(multiplicity.may_have_multiple_irrs());
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 50.0, 50.0];
+/// assert!(inspect(cash_flows.iter()).has_unique_irr());
+/// ```
+pub fn inspect<T>(cash_flows: Iter<T>) -> Multiplicity
+where
+    T: Float,
+{
+    let mut total: T = T::zero();
+    let cumulative: Vec<T> = cash_flows
+        .clone()
+        .map(|cash_flow| {
+            total = total + *cash_flow;
+            total
+        })
+        .collect();
+
+    Multiplicity {
+        sign_changes: sign_changes(cash_flows),
+        cumulative_sign_changes: sign_changes(cumulative.iter()),
+        is_total_zero: total.is_zero(),
+    }
+}
+
+#[cfg(test)]
+mod inspect_tests {
+    use crate::irr::multiplicity::{inspect, Multiplicity};
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f32> = vec![];
+        let multiplicity: Multiplicity = inspect(cash_flows.iter());
+        assert_eq!(multiplicity.max_irrs(), 0);
+        assert!(!multiplicity.has_unique_irr());
+        assert!(!multiplicity.has_unique_positive_irr());
+    }
+
+    #[test]
+    fn it_guarantees_an_irr_for_an_odd_number_of_sign_changes() {
+        let cash_flows: Vec<f64> = vec![-100.0, 300.0, -300.0, 110.0];
+        let multiplicity: Multiplicity = inspect(cash_flows.iter());
+        assert_eq!(multiplicity.min_irrs(), 1);
+        assert_eq!(multiplicity.max_irrs(), 3);
+    }
+
+    #[test]
+    fn it_applies_norstroms_criterion() {
+        // three sign changes, but the cumulative cash flows -100, -50, -70, 10 change sign once
+        let cash_flows: Vec<f64> = vec![-100.0, 50.0, -20.0, 80.0];
+        let multiplicity: Multiplicity = inspect(cash_flows.iter());
+        assert!(multiplicity.may_have_multiple_irrs());
+        assert!(multiplicity.has_unique_positive_irr());
+    }
+
+    #[test]
+    fn it_does_not_apply_norstroms_criterion_to_a_zero_total() {
+        let cash_flows: Vec<f64> = vec![-100.0, 100.0];
+        let multiplicity: Multiplicity = inspect(cash_flows.iter());
+        assert!(multiplicity.has_unique_irr());
+        assert!(!multiplicity.has_unique_positive_irr());
+    }
+}
@@ -0,0 +1,498 @@
+//! A trait for bracketing root finders, so that solvers can be swapped or reused beyond IRRs.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::ops::ControlFlow;
+use std::slice::Iter;
+
+use crate::irr::bisection::constants::NPV_PRECISION;
+use crate::irr::bisection::functions::initial_bounds::determine_with_npv_and_tolerance;
+use crate::irr::bisection::functions::irr::bisection_with_npv_and_tolerances;
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::initial_bounds::InitialBounds;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::brent::functions::irr::brent_with_npv_and_tolerances;
+use crate::irr::false_position::functions::irr::false_position_with_npv_and_tolerances;
+use crate::irr::false_position::structs::damping::Variant;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// The result of a root finder; the `npv` fields hold the value of the function that was solved.
+pub type SolveResult<T> = Irr<T>;
+
+/// A method for finding a root of a function of one variable within a bracket.
+///
+/// # Assumptions
+/// It is assumed that `f(low)` and `f(high)` have opposite signs.
+///
+/// # Comments
+/// The finders in this module converge once `|f(x)|` is within an absolute tolerance of zero, `NPV_PRECISION` by default, or once the bracket is narrower than a relative tolerance times the larger magnitude of its bounds.
+/// The absolute tolerance is in the units of `f`, so it should be scaled with e.g. the cash flows, whereas the relative tolerance is in the units of the root and does not depend on that scale.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::root_finder::{Bisection, Brent, RootFinder, SolveResult};
+///
+/// let solved: SolveResult<f64> = Brent::new(100).solve(|x: f64| x * x - 2.0, 0.0, 2.0);
+/// assert!(solved.is_valid());
+/// assert!((solved.irr() - 2.0_f64.sqrt()).abs() < 0.000_001);
+///
+/// // an NPV of billions cannot come within a tenth of a cent of zero, but the rate can be bracketed to a millionth of itself
+/// let npv = |rate: f32| -1.0e9 + 6.0e8 / (1.0 + rate) + 6.0e8 / (1.0 + rate).powi(2);
+/// assert!(!Bisection::new(100).solve(npv, 0.0, 0.5).is_valid());
+/// let solved: SolveResult<f32> = Bisection::with_tolerances(100, 0.001, 0.000_001).solve(npv, 0.0, 0.5);
+/// assert!(solved.is_valid());
+/// assert!((solved.irr() - 0.130_662).abs() < 0.000_01);
+/// ```
+pub trait RootFinder<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    fn solve<F>(&self, f: F, low: T, high: T) -> SolveResult<T>
+    where
+        F: Fn(T) -> T;
+}
+
+/// The bisection method; see `irr::bisection::functions::irr::bisection`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bisection<T> {
+    iteration_limit: u32,
+    absolute_tolerance: T,
+    relative_tolerance: T,
+}
+
+impl<T> Bisection<T>
+where
+    T: Float,
+{
+    /// Converges once the value of the function is within `NPV_PRECISION` of zero.
+    pub fn new(iteration_limit: u32) -> Bisection<T> {
+        Bisection::with_tolerances(iteration_limit, T::from(NPV_PRECISION).unwrap(), T::zero())
+    }
+
+    /// Converges once the value of the function is within `absolute_tolerance` of zero, or the bracket is narrower than `relative_tolerance` times the magnitude of its bounds; see `RootFinder`.
+    pub fn with_tolerances(
+        iteration_limit: u32,
+        absolute_tolerance: T,
+        relative_tolerance: T,
+    ) -> Bisection<T> {
+        Bisection {
+            iteration_limit,
+            absolute_tolerance,
+            relative_tolerance,
+        }
+    }
+
+    pub fn iteration_limit(&self) -> u32 {
+        self.iteration_limit
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    pub fn absolute_tolerance(&self) -> T {
+        self.absolute_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `absolute_tolerance` instead")]
+    pub fn get_absolute_tolerance(&self) -> T {
+        self.absolute_tolerance()
+    }
+
+    pub fn relative_tolerance(&self) -> T {
+        self.relative_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `relative_tolerance` instead")]
+    pub fn get_relative_tolerance(&self) -> T {
+        self.relative_tolerance()
+    }
+}
+
+impl<T> RootFinder<T> for Bisection<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    fn solve<F>(&self, f: F, low: T, high: T) -> SolveResult<T>
+    where
+        F: Fn(T) -> T,
+    {
+        bisection_with_npv_and_tolerances(
+            f,
+            low,
+            high,
+            self.iteration_limit,
+            self.absolute_tolerance,
+            None,
+            self.relative_tolerance,
+            &mut |_| ControlFlow::Continue(()),
+        )
+    }
+}
+
+/// Brent's method; see `irr::brent::functions::irr::brent`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brent<T> {
+    iteration_limit: u32,
+    absolute_tolerance: T,
+    relative_tolerance: T,
+}
+
+impl<T> Brent<T>
+where
+    T: Float,
+{
+    /// Converges once the value of the function is within `NPV_PRECISION` of zero.
+    pub fn new(iteration_limit: u32) -> Brent<T> {
+        Brent::with_tolerances(iteration_limit, T::from(NPV_PRECISION).unwrap(), T::zero())
+    }
+
+    /// Converges once the value of the function is within `absolute_tolerance` of zero, or the bracket is narrower than `relative_tolerance` times the magnitude of its bounds; see `RootFinder`.
+    pub fn with_tolerances(
+        iteration_limit: u32,
+        absolute_tolerance: T,
+        relative_tolerance: T,
+    ) -> Brent<T> {
+        Brent {
+            iteration_limit,
+            absolute_tolerance,
+            relative_tolerance,
+        }
+    }
+
+    pub fn iteration_limit(&self) -> u32 {
+        self.iteration_limit
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    pub fn absolute_tolerance(&self) -> T {
+        self.absolute_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `absolute_tolerance` instead")]
+    pub fn get_absolute_tolerance(&self) -> T {
+        self.absolute_tolerance()
+    }
+
+    pub fn relative_tolerance(&self) -> T {
+        self.relative_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `relative_tolerance` instead")]
+    pub fn get_relative_tolerance(&self) -> T {
+        self.relative_tolerance()
+    }
+}
+
+impl<T> RootFinder<T> for Brent<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    fn solve<F>(&self, f: F, low: T, high: T) -> SolveResult<T>
+    where
+        F: Fn(T) -> T,
+    {
+        brent_with_npv_and_tolerances(
+            f,
+            low,
+            high,
+            self.iteration_limit,
+            self.absolute_tolerance,
+            None,
+            self.relative_tolerance,
+        )
+    }
+}
+
+/// The modified false position method; see `irr::false_position::functions::irr::false_position`.
+///
+/// # Comments
+/// The record of damped updates is discarded; call `false_position` directly to keep it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FalsePosition<T> {
+    iteration_limit: u32,
+    variant: Variant,
+    absolute_tolerance: T,
+    relative_tolerance: T,
+}
+
+impl<T> FalsePosition<T>
+where
+    T: Float,
+{
+    /// Converges once the value of the function is within `NPV_PRECISION` of zero.
+    pub fn new(iteration_limit: u32, variant: Variant) -> FalsePosition<T> {
+        FalsePosition::with_tolerances(
+            iteration_limit,
+            variant,
+            T::from(NPV_PRECISION).unwrap(),
+            T::zero(),
+        )
+    }
+
+    /// Converges once the value of the function is within `absolute_tolerance` of zero, or the bracket is narrower than `relative_tolerance` times the magnitude of its bounds; see `RootFinder`.
+    pub fn with_tolerances(
+        iteration_limit: u32,
+        variant: Variant,
+        absolute_tolerance: T,
+        relative_tolerance: T,
+    ) -> FalsePosition<T> {
+        FalsePosition {
+            iteration_limit,
+            variant,
+            absolute_tolerance,
+            relative_tolerance,
+        }
+    }
+
+    pub fn iteration_limit(&self) -> u32 {
+        self.iteration_limit
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `iteration_limit` instead")]
+    pub fn get_iteration_limit(&self) -> u32 {
+        self.iteration_limit()
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `variant` instead")]
+    pub fn get_variant(&self) -> Variant {
+        self.variant()
+    }
+
+    pub fn absolute_tolerance(&self) -> T {
+        self.absolute_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `absolute_tolerance` instead")]
+    pub fn get_absolute_tolerance(&self) -> T {
+        self.absolute_tolerance()
+    }
+
+    pub fn relative_tolerance(&self) -> T {
+        self.relative_tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `relative_tolerance` instead")]
+    pub fn get_relative_tolerance(&self) -> T {
+        self.relative_tolerance()
+    }
+}
+
+impl<T> RootFinder<T> for FalsePosition<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    fn solve<F>(&self, f: F, low: T, high: T) -> SolveResult<T>
+    where
+        F: Fn(T) -> T,
+    {
+        false_position_with_npv_and_tolerances(
+            f,
+            low,
+            high,
+            self.iteration_limit,
+            self.variant,
+            self.absolute_tolerance,
+            self.relative_tolerance,
+        )
+        .into_irr()
+    }
+}
+
+/// Calculates the IRR of a series of cash flows with any root finder.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::irr::root_finder::{irr, Bisection, Brent};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let bisection_irr: Irr<f64> = irr(cash_flows.iter(), &Bisection::new(100), 0.05, 0.18);
+/// let brent_irr: Irr<f64> = irr(cash_flows.iter(), &Brent::new(100), 0.05, 0.18);
+/// assert!((bisection_irr.irr() - brent_irr.irr()).abs() < 0.000_01);
+/// ```
+pub fn irr<T, R>(
+    cash_flows: Iter<T>,
+    root_finder: &R,
+    rate_low_guess: T,
+    rate_high_guess: T,
+) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    R: RootFinder<T>,
+{
+    root_finder.solve(
+        |rate| pv(cash_flows.clone(), rate),
+        rate_low_guess,
+        rate_high_guess,
+    )
+}
+
+/// Searches outwards from `guess` for a bracket of a root of `f` and solves within it with Brent's method, for callers that have a guess rather than a bracket e.g. the spreadsheet functions.
+///
+/// The tolerance for `f` is `scale` times the square root of the precision of `T`, where `scale` is the size of the amounts being valued e.g. `cash_flows::scale`, so that it does not depend on their units.
+/// If `f(guess)` is already within the tolerance, the guess is the root; if no bracket is found, the result is not valid and its reason is `FailureReason::BracketNotFound`.
+pub(crate) fn solve_from_guess<T, F>(
+    f: F,
+    guess: T,
+    iteration_limit: u32,
+    scale: T,
+) -> SolveResult<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    let npv_tolerance: T = scale * T::epsilon().sqrt();
+    let initial_bounds: InitialBounds<T> =
+        determine_with_npv_and_tolerance(&f, guess, iteration_limit, npv_tolerance);
+    let bounds = |irr: T, npv: T, is_valid: bool| {
+        Irr::new(
+            initial_bounds.rate_low(),
+            initial_bounds.npv_rate_low(),
+            initial_bounds.rate_high(),
+            initial_bounds.npv_rate_high(),
+            iteration_limit,
+            0,
+            irr,
+            npv,
+            is_valid,
+        )
+        .with_npv_evaluations(initial_bounds.npv_evaluations())
+    };
+    if !initial_bounds.is_valid() {
+        return bounds(T::nan(), T::nan(), false).with_failure_reason(
+            initial_bounds
+                .failure_reason()
+                .unwrap_or(FailureReason::BracketNotFound),
+        );
+    }
+    if initial_bounds.rate_low() == initial_bounds.rate_high() {
+        return bounds(guess, initial_bounds.npv_rate_low(), true);
+    }
+    let solved: SolveResult<T> = Brent::with_tolerances(iteration_limit, npv_tolerance, T::zero())
+        .solve(f, initial_bounds.rate_low(), initial_bounds.rate_high());
+    solved.with_npv_evaluations(initial_bounds.npv_evaluations() + solved.npv_evaluations())
+}
+
+#[cfg(test)]
+mod irr_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::irr::false_position::structs::damping::Variant;
+    use crate::irr::root_finder::{irr, Bisection, Brent, FalsePosition, RootFinder, SolveResult};
+
+    struct Secant;
+
+    impl RootFinder<f64> for Secant {
+        fn solve<F>(&self, f: F, low: f64, high: f64) -> SolveResult<f64>
+        where
+            F: Fn(f64) -> f64,
+        {
+            let (mut a, mut b): (f64, f64) = (low, high);
+            for _ in 0..50 {
+                if f(b).abs() < 0.000_000_001 {
+                    break;
+                }
+                let step: f64 = f(b) * (b - a) / (f(b) - f(a));
+                a = b;
+                b -= step;
+            }
+            Irr::new(
+                low,
+                f(low),
+                high,
+                f(high),
+                50,
+                50,
+                b,
+                f(b),
+                f(b).abs() < 0.000_001,
+            )
+        }
+    }
+
+    #[test]
+    fn it_works_with_a_user_supplied_root_finder() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let secant_irr: Irr<f64> = irr(cash_flows.iter(), &Secant, 0.0, 0.5);
+        let bisection_irr: Irr<f64> = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+
+        assert!(secant_irr.is_valid());
+        assert!((secant_irr.irr() - bisection_irr.irr()).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_converges_within_the_relative_tolerance() {
+        let cash_flows: Vec<f64> = vec![-1.0e12, 6.0e11, 6.0e11];
+        let relative_tolerance: f64 = 0.000_001;
+        let bisection_irr: Irr<f64> = irr(
+            cash_flows.iter(),
+            &Bisection::with_tolerances(100, 0.0, relative_tolerance),
+            0.0,
+            0.5,
+        );
+        let brent_irr: Irr<f64> = irr(
+            cash_flows.iter(),
+            &Brent::with_tolerances(100, 0.0, relative_tolerance),
+            0.0,
+            0.5,
+        );
+
+        assert!(bisection_irr.iterations_run() < 25);
+        for calculated_irr in &[bisection_irr, brent_irr] {
+            assert!(calculated_irr.is_valid());
+            assert!((calculated_irr.irr() - 0.130_662_386).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn it_uses_the_absolute_tolerance() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let loose: Irr<f64> = irr(
+            cash_flows.iter(),
+            &Bisection::with_tolerances(100, 1.0, 0.0),
+            0.0,
+            0.5,
+        );
+        let tight: Irr<f64> = irr(cash_flows.iter(), &Bisection::new(100), 0.0, 0.5);
+        let large_cash_flows: Vec<f32> = vec![-1.0e9, 6.0e8, 6.0e8];
+        let false_position_irr: Irr<f32> = irr(
+            large_cash_flows.iter(),
+            &FalsePosition::with_tolerances(100, Variant::Illinois, 1_000.0, 0.0),
+            0.0,
+            0.5,
+        );
+
+        assert!(loose.is_valid());
+        assert!(loose.npv().abs() <= 1.0);
+        assert!(loose.iterations_run() < tight.iterations_run());
+        assert!(false_position_irr.is_valid());
+        assert!(false_position_irr.npv().abs() <= 1_000.0);
+    }
+
+    #[test]
+    fn it_works_with_false_position() {
+        let cash_flows: Vec<f32> = vec![-100.0, 60.0, 60.0];
+        let calculated_irr: Irr<f32> = irr(
+            cash_flows.iter(),
+            &FalsePosition::new(100, Variant::AndersonBjorck),
+            0.0,
+            0.5,
+        );
+
+        assert!(calculated_irr.is_valid());
+        assert!((calculated_irr.irr() - 0.130_662).abs() < 0.000_01);
+    }
+}
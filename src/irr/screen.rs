@@ -0,0 +1,137 @@
+//! Screens series of cash flows against a hurdle rate without solving for their IRRs.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::cash_flows::sign_changes;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+
+/// Where the IRR of a series of cash flows lies relative to a hurdle rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Screening {
+    Above,
+    Below,
+    /// The NPV at the hurdle rate is exactly zero.
+    At,
+    /// The series does not change sign exactly once, or its NPV at the hurdle rate is not finite, so it must be solved to be compared.
+    Indeterminate,
+}
+
+/// Compares the IRR of a series of cash flows with `threshold` from the sign of its NPV at `threshold`, i.e. with one NPV evaluation.
+///
+/// # Comments
+/// If the series changes sign exactly once it has a single IRR, and the NPV has the sign of the first non-zero cash flow at rates above it, e.g. is negative above the IRR of an investment.
+/// So the IRR is above `threshold` if the NPV there has the opposite sign to the first non-zero cash flow, and below it if they have the same sign.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::screen::{classify, Screening};
+///
+/// // the IRR is about 13.07%
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// assert_eq!(classify(cash_flows.iter(), 0.08), Screening::Above);
+/// assert_eq!(classify(cash_flows.iter(), 0.15), Screening::Below);
+///
+/// // borrowing 100 and repaying 125 costs 25%
+/// let cash_flows: Vec<f64> = vec![100.0, -125.0];
+/// assert_eq!(classify(cash_flows.iter(), 0.25), Screening::At);
+/// assert_eq!(classify(cash_flows.iter(), 0.30), Screening::Below);
+/// ```
+pub fn classify<T>(cash_flows: Iter<T>, threshold: T) -> Screening
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    if sign_changes(cash_flows.clone()) != 1 {
+        return Screening::Indeterminate;
+    }
+    let first: T = match cash_flows.clone().find(|cash_flow| !cash_flow.is_zero()) {
+        Some(first) => *first,
+        None => return Screening::Indeterminate,
+    };
+    let npv: T = pv(cash_flows, threshold);
+    if !npv.is_finite() {
+        Screening::Indeterminate
+    } else if npv.is_zero() {
+        Screening::At
+    } else if npv.signum() == first.signum() {
+        Screening::Below
+    } else {
+        Screening::Above
+    }
+}
+
+/// Compares the IRR of each of several series of cash flows with `threshold`; see `classify`.
+///
+/// # Comments
+/// Only the indeterminate series need to be solved to be compared, e.g. with `bisection` or `all_roots`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::screen::{screen, Screening};
+///
+/// let series: Vec<Vec<f64>> = vec![
+///     vec![-100.0, 60.0, 60.0],
+///     vec![-100.0, 52.0, 52.0],
+///     vec![-100.0, 230.0, -132.0],
+/// ];
+/// let screenings: Vec<Screening> = screen(&series, 0.08);
+/// assert_eq!(
+///     screenings,
+///     vec![Screening::Above, Screening::Below, Screening::Indeterminate]
+/// );
+/// ```
+pub fn screen<T, I, S>(series: I, threshold: T) -> Vec<Screening>
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = S>,
+    S: AsRef<[T]>,
+{
+    series
+        .into_iter()
+        .map(|cash_flows| classify(cash_flows.as_ref().iter(), threshold))
+        .collect()
+}
+
+#[cfg(test)]
+mod screen_tests {
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::screen::{classify, screen, Screening};
+
+    #[test]
+    fn it_agrees_with_bisection() {
+        let series: Vec<Vec<f64>> = (1..40)
+            .map(|inflow| vec![-100.0, 2.0 * inflow as f64, 2.0 * inflow as f64, 50.0])
+            .collect();
+        let screenings: Vec<Screening> = screen(&series, 0.08);
+
+        for (cash_flows, screening) in series.iter().zip(screenings) {
+            let irr: f64 = bisection(cash_flows.iter(), 0.0, 1.0, 100).irr();
+            let expected: Screening = if 0.08 < irr {
+                Screening::Above
+            } else {
+                Screening::Below
+            };
+            assert_eq!(screening, expected);
+        }
+    }
+
+    #[test]
+    fn it_ignores_leading_zeros() {
+        let cash_flows: Vec<f32> = vec![0.0, 0.0, -100.0, 120.0];
+        assert_eq!(classify(cash_flows.iter(), 0.10), Screening::Above);
+        assert_eq!(classify(cash_flows.iter(), 0.30), Screening::Below);
+    }
+
+    #[test]
+    fn it_is_indeterminate_without_a_single_sign_change() {
+        let no_change: Vec<f64> = vec![100.0, 10.0];
+        let no_cash_flows: Vec<f64> = vec![];
+        let series: Vec<&[f64]> = vec![&no_change, &no_cash_flows];
+        assert_eq!(
+            screen(series, 0.05),
+            vec![Screening::Indeterminate, Screening::Indeterminate]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! A struct for valuing several series of cash flows, grouped by label, together.
+
+use num::{Float, Signed};
+use std::collections::BTreeMap;
+use std::iter::{Product, Sum};
+
+use crate::irr::bisection::functions::irr::with_hint;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::present_value::discount_factors;
+
+/// Series of cash flows keyed by a label e.g. the strategy of each portfolio.
+///
+/// # Example
+/// ```
+/// use time_value::labeled_cash_flows::LabeledCashFlows;
+///
+/// let mut labeled_cash_flows: LabeledCashFlows<&str, f64> = LabeledCashFlows::new();
+/// labeled_cash_flows.insert("buyout", vec![-100.0, 60.0, 60.0]);
+/// labeled_cash_flows.insert("venture", vec![-50.0, 0.0, 80.0]);
+///
+/// let (npvs, total) = labeled_cash_flows.npv_by_group(0.10);
+/// assert!((npvs["buyout"] - 4.1322).abs() < 0.0001);
+/// assert!((npvs["venture"] - 16.1157).abs() < 0.0001);
+/// assert!((total - 20.2479).abs() < 0.0001);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LabeledCashFlows<K, T>
+where
+    K: Ord,
+    T: Float,
+{
+    groups: BTreeMap<K, Vec<T>>,
+}
+
+impl<K, T> LabeledCashFlows<K, T>
+where
+    K: Ord + Clone,
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    pub fn new() -> LabeledCashFlows<K, T> {
+        LabeledCashFlows {
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a series of cash flows under a label, returning the series it replaced, if any.
+    pub fn insert(&mut self, label: K, cash_flows: Vec<T>) -> Option<Vec<T>> {
+        self.groups.insert(label, cash_flows)
+    }
+
+    pub fn get(&self, label: &K) -> Option<&Vec<T>> {
+        self.groups.get(label)
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Sums the cash flows of every group period by period.
+    pub fn consolidated(&self) -> Vec<T> {
+        let periods: usize = self.groups.values().map(Vec::len).max().unwrap_or(0);
+        let mut consolidated: Vec<T> = vec![T::zero(); periods];
+        for cash_flows in self.groups.values() {
+            for (period, cash_flow) in cash_flows.iter().enumerate() {
+                consolidated[period] = consolidated[period] + *cash_flow;
+            }
+        }
+        consolidated
+    }
+
+    /// Calculates the NPV of each group and of all groups together.
+    ///
+    /// The discount factors are calculated once and shared by every group.
+    pub fn npv_by_group(&self, discount_rate: T) -> (BTreeMap<K, T>, T) {
+        let periods: usize = self.groups.values().map(Vec::len).max().unwrap_or(0);
+        let discount_factors: Vec<T> = discount_factors(discount_rate, periods);
+
+        let npvs: BTreeMap<K, T> = self
+            .groups
+            .iter()
+            .map(|(label, cash_flows)| {
+                let npv: T = cash_flows
+                    .iter()
+                    .zip(discount_factors.iter())
+                    .map(|(cash_flow, discount_factor)| *cash_flow * *discount_factor)
+                    .sum();
+                (label.clone(), npv)
+            })
+            .collect();
+        let total: T = npvs.values().copied().sum();
+
+        (npvs, total)
+    }
+
+    /// Calculates the IRR of each group and of the consolidated cash flows of all groups.
+    ///
+    /// Each IRR is found by searching for initial bounds outwards from `rate_guess` and then applying the bisection method.
+    pub fn irr_by_group(
+        &self,
+        rate_guess: T,
+        iteration_limit: u32,
+    ) -> (BTreeMap<K, Irr<T>>, Irr<T>) {
+        let irrs: BTreeMap<K, Irr<T>> = self
+            .groups
+            .iter()
+            .map(|(label, cash_flows)| {
+                (
+                    label.clone(),
+                    with_hint(cash_flows.iter(), None, rate_guess, iteration_limit),
+                )
+            })
+            .collect();
+        let consolidated: Vec<T> = self.consolidated();
+        let total: Irr<T> = with_hint(consolidated.iter(), None, rate_guess, iteration_limit);
+
+        (irrs, total)
+    }
+}
+
+#[cfg(test)]
+mod labeled_cash_flows_tests {
+    use crate::labeled_cash_flows::LabeledCashFlows;
+    use crate::present_value::from_cash_flows_and_discount_rate as npv;
+
+    fn example() -> LabeledCashFlows<String, f64> {
+        let mut labeled_cash_flows: LabeledCashFlows<String, f64> = LabeledCashFlows::new();
+        labeled_cash_flows.insert(String::from("a"), vec![-100.0, 60.0, 60.0]);
+        labeled_cash_flows.insert(String::from("b"), vec![-50.0, 10.0, 10.0, 10.0, 40.0]);
+        labeled_cash_flows.insert(String::from("c"), vec![-20.0, 25.0]);
+        labeled_cash_flows
+    }
+
+    #[test]
+    fn it_works_with_no_groups() {
+        let labeled_cash_flows: LabeledCashFlows<u8, f32> = LabeledCashFlows::new();
+        let (npvs, total) = labeled_cash_flows.npv_by_group(0.10);
+        assert!(labeled_cash_flows.is_empty());
+        assert!(npvs.is_empty());
+        assert_eq!(total, 0.0);
+        assert!(labeled_cash_flows.consolidated().is_empty());
+    }
+
+    #[test]
+    fn it_matches_the_npv_of_each_group() {
+        let labeled_cash_flows: LabeledCashFlows<String, f64> = example();
+        let discount_rate: f64 = 0.08;
+        let (npvs, total) = labeled_cash_flows.npv_by_group(discount_rate);
+
+        for (label, value) in npvs.iter() {
+            let cash_flows: &Vec<f64> = labeled_cash_flows.get(label).unwrap();
+            assert!((npv(cash_flows.iter(), discount_rate) - value).abs() < 0.000_000_001);
+        }
+        let consolidated: Vec<f64> = labeled_cash_flows.consolidated();
+        assert!((npv(consolidated.iter(), discount_rate) - total).abs() < 0.000_000_001);
+    }
+
+    #[test]
+    fn it_solves_each_group_and_the_total() {
+        let labeled_cash_flows: LabeledCashFlows<String, f64> = example();
+        let (irrs, total) = labeled_cash_flows.irr_by_group(0.05, 1_000);
+
+        assert_eq!(irrs.len(), labeled_cash_flows.len());
+        assert!(irrs.values().all(|irr| irr.is_valid()));
+        assert!(total.is_valid());
+    }
+}
@@ -1,10 +1,35 @@
 //! Functions and structs related to time value analysis
 
+pub mod annuity;
+
+pub mod calendar;
+
+pub mod cash_flow_series;
+
+pub mod cash_flows;
+
+pub mod compiled_valuation;
+
+pub mod curve;
+
+pub mod dated;
+
+pub mod duration;
+
+pub mod export;
+
+#[cfg(feature = "fixed_point")]
+pub mod fixed_point;
+
 pub mod future_value;
 
 pub mod irr {
     //! Functions and structs for calculating the internal rate of return (IRR) of a series of cash flows
 
+    pub mod approximate;
+
+    pub mod auto;
+
     pub mod bisection {
         //! Items related to the bisection method
 
@@ -13,19 +38,137 @@ pub mod irr {
         pub mod functions {
             //! Functions used for the bisection method (and related methods)
 
+            pub mod all_roots;
             pub mod are_equal_enough;
             pub mod initial_bounds;
             pub mod irr;
             pub mod midpoint;
+            pub mod polish;
         }
 
         pub mod structs {
             //! Structs used with the bisection method
 
+            pub mod bracket_hint;
+            pub mod config;
+            pub mod failure_reason;
+            pub(crate) mod formatting;
             pub mod initial_bounds;
             pub mod irr;
+            pub mod iteration;
+        }
+    }
+
+    pub mod brent {
+        //! Items related to Brent's method
+
+        pub mod functions {
+            //! Functions used for Brent's method
+
+            pub mod irr;
+        }
+    }
+
+    pub mod calculate;
+
+    #[cfg(feature = "companion_matrix")]
+    pub mod companion_matrix;
+
+    pub mod comparison;
+
+    pub mod error;
+
+    pub(crate) mod evaluations;
+
+    pub mod false_position {
+        //! Items related to the modified false position method
+
+        pub mod functions {
+            //! Functions used for the modified false position method
+
+            pub mod irr;
+        }
+
+        pub mod structs {
+            //! Structs used with the modified false position method
+
+            pub mod damping;
+            pub mod irr;
+        }
+    }
+
+    pub mod halley {
+        //! Items related to Halley's method
+
+        pub mod functions {
+            //! Functions used for Halley's method
+
+            pub mod irr;
+        }
+    }
+
+    pub mod hybrid {
+        //! Items related to the hybrid Newton and bisection method
+
+        pub mod functions {
+            //! Functions used for the hybrid method
+
+            pub mod irr;
+        }
+
+        pub mod structs {
+            //! Structs used with the hybrid method
+
+            pub mod irr;
         }
     }
+
+    pub mod mirr;
+
+    pub mod multiplicity;
+
+    pub mod root_finder;
+
+    pub mod screen;
 }
 
+pub mod labeled_cash_flows;
+
+pub mod loan;
+
+pub mod model;
+
+pub mod planning;
+
+pub(crate) mod power;
+
 pub mod present_value;
+
+pub mod properties;
+
+pub mod rate;
+
+pub mod reconcile;
+
+pub mod report;
+
+pub mod returns;
+
+pub mod savings;
+
+pub mod simulation;
+
+pub mod spreadsheet;
+
+pub mod tax;
+
+pub mod templates;
+
+pub mod tenor;
+
+pub mod test_vectors;
+
+pub mod xirr;
+
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
@@ -0,0 +1,499 @@
+//! Loan amortization schedules.
+
+use num::{Float, Signed};
+use std::iter::{FromIterator, Product, Sum};
+
+use crate::annuity::pmt;
+use crate::cash_flows::scale;
+use crate::irr::root_finder::{solve_from_guess, SolveResult};
+use crate::power::{powf, powi};
+
+/// A row of an amortization schedule: the payment made at the end of `period`, split into interest and principal, and the balance outstanding after it.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::Row;
+///
+/// let row: Row<f64> = Row::new(1, 110.0, 10.0, 100.0, 900.0);
+/// assert_eq!(row.period(), 1);
+/// assert_eq!(row.interest() + row.principal(), row.payment());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Row<T>
+where
+    T: Float,
+{
+    period: usize,
+    payment: T,
+    interest: T,
+    principal: T,
+    balance: T,
+}
+
+impl<T> Row<T>
+where
+    T: Float,
+{
+    pub fn new(period: usize, payment: T, interest: T, principal: T, balance: T) -> Row<T> {
+        Row {
+            period,
+            payment,
+            interest,
+            principal,
+            balance,
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `period` instead")]
+    pub fn get_period(&self) -> usize {
+        self.period()
+    }
+
+    pub fn payment(&self) -> T {
+        self.payment
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `payment` instead")]
+    pub fn get_payment(&self) -> T {
+        self.payment()
+    }
+
+    pub fn interest(&self) -> T {
+        self.interest
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `interest` instead")]
+    pub fn get_interest(&self) -> T {
+        self.interest()
+    }
+
+    pub fn principal(&self) -> T {
+        self.principal
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `principal` instead")]
+    pub fn get_principal(&self) -> T {
+        self.principal()
+    }
+
+    pub fn balance(&self) -> T {
+        self.balance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `balance` instead")]
+    pub fn get_balance(&self) -> T {
+        self.balance()
+    }
+}
+
+/// An amortization schedule, i.e. a sequence of rows, e.g. from `amortize` or imported from another system.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::{Row, Schedule};
+///
+/// let schedule: Schedule<f64> = vec![
+///     Row::new(1, 60.0, 10.0, 50.0, 50.0),
+///     Row::new(2, 55.0, 5.0, 50.0, 0.0),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert_eq!(schedule.len(), 2);
+/// assert_eq!(schedule.rows()[1].balance(), 0.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schedule<T>
+where
+    T: Float,
+{
+    rows: Vec<Row<T>>,
+}
+
+impl<T> Schedule<T>
+where
+    T: Float,
+{
+    pub fn new(rows: Vec<Row<T>>) -> Schedule<T> {
+        Schedule { rows }
+    }
+
+    pub fn rows(&self) -> &[Row<T>] {
+        &self.rows
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rows` instead")]
+    pub fn get_rows(&self) -> &[Row<T>] {
+        self.rows()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl<T> FromIterator<Row<T>> for Schedule<T>
+where
+    T: Float,
+{
+    fn from_iter<I: IntoIterator<Item = Row<T>>>(iter: I) -> Schedule<T> {
+        Schedule::new(iter.into_iter().collect())
+    }
+}
+
+/// The schedule of a loan of `principal` repaid by level payments at the end of periods 1 to `periods`, at `rate` per period.
+///
+/// # Comments
+/// The level payment is `principal * rate / (1 - (1 + rate)^-periods)`, or `principal / periods` at a zero rate, i.e. `annuity::pmt` with the opposite sign.
+/// Each row's interest is `rate` times the previous balance and the rest of the payment repays principal, so the final balance is zero up to rounding.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::{amortize, Schedule};
+///
+/// let schedule: Schedule<f64> = amortize(1_000.0, 0.01, 12);
+/// assert_eq!(schedule.len(), 12);
+/// assert!((schedule.rows()[0].payment() - 88.848_79).abs() < 0.000_01);
+/// assert!((schedule.rows()[0].interest() - 10.0).abs() < 0.000_001);
+/// assert!(schedule.rows()[11].balance().abs() < 0.000_001);
+/// ```
+pub fn amortize<T>(principal: T, rate: T, periods: usize) -> Schedule<T>
+where
+    T: Float,
+{
+    let payment: T = -pmt(rate, periods as u32, principal, T::zero(), false);
+    let mut balance: T = principal;
+    (1..=periods)
+        .map(|period| {
+            let interest: T = balance * rate;
+            let repaid: T = payment - interest;
+            balance = balance - repaid;
+            Row::new(period, payment, interest, repaid, balance)
+        })
+        .collect()
+}
+
+/// The schedule of a loan of `principal` repaid by level payments, where the first period is a stub of `first_period_fraction` of a full period, e.g. for a loan drawn part way through a month.
+///
+/// # Comments
+/// The first row's interest is the previous balance times `(1 + rate)^first_period_fraction - 1`, and the level payment is that of `amortize` for the principal grown over the stub and discounted over a full period.
+/// A fraction of one gives the schedule of `amortize`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::{amortize_with_stub_period, Schedule};
+///
+/// let schedule: Schedule<f64> = amortize_with_stub_period(1_000.0, 0.01, 12, 0.5);
+/// assert!((schedule.rows()[0].interest() - 1_000.0 * (1.01_f64.sqrt() - 1.0)).abs() < 0.000_001);
+/// assert!((schedule.rows()[1].interest() - schedule.rows()[0].balance() * 0.01).abs() < 0.000_001);
+/// assert!(schedule.rows()[11].balance().abs() < 0.000_001);
+/// ```
+pub fn amortize_with_stub_period<T>(
+    principal: T,
+    rate: T,
+    periods: usize,
+    first_period_fraction: T,
+) -> Schedule<T>
+where
+    T: Float,
+{
+    let stub_rate: T = powf(T::one() + rate, first_period_fraction) - T::one();
+    let payment: T = -pmt(
+        rate,
+        periods as u32,
+        principal * (T::one() + stub_rate) / (T::one() + rate),
+        T::zero(),
+        false,
+    );
+    let mut balance: T = principal;
+    (1..=periods)
+        .map(|period| {
+            let interest: T = balance * if period == 1 { stub_rate } else { rate };
+            let repaid: T = payment - interest;
+            balance = balance - repaid;
+            Row::new(period, payment, interest, repaid, balance)
+        })
+        .collect()
+}
+
+/// The periodic rate implied by a schedule, and the periods whose rows are inconsistent with it.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImpliedRate<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    solved: SolveResult<T>,
+    row_rates: Vec<T>,
+    inconsistent_periods: Vec<usize>,
+}
+
+impl<T> ImpliedRate<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    /// The implied periodic rate, or NaN if none was found.
+    pub fn rate(&self) -> T {
+        self.solved.irr()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate` instead")]
+    pub fn get_rate(&self) -> T {
+        self.rate()
+    }
+
+    /// The result of the root finder, whose `npv` fields hold the NPV of the loan and its payments.
+    pub fn solved(&self) -> &SolveResult<T> {
+        &self.solved
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `solved` instead")]
+    pub fn get_solved(&self) -> &SolveResult<T> {
+        self.solved()
+    }
+
+    /// The rate implied by each row on its own, i.e. its interest divided by the balance before it.
+    pub fn row_rates(&self) -> &[T] {
+        &self.row_rates
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `row_rates` instead")]
+    pub fn get_row_rates(&self) -> &[T] {
+        self.row_rates()
+    }
+
+    /// The periods of the rows that are inconsistent with the implied rate.
+    pub fn inconsistent_periods(&self) -> &[usize] {
+        &self.inconsistent_periods
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `inconsistent_periods` instead")]
+    pub fn get_inconsistent_periods(&self) -> &[usize] {
+        self.inconsistent_periods()
+    }
+
+    /// Whether a rate was found and every row is consistent with it.
+    pub fn is_consistent(&self) -> bool {
+        self.solved.is_valid() && self.inconsistent_periods.is_empty()
+    }
+}
+
+/// Back-solves the periodic rate of an observed schedule, e.g. to audit a schedule from another system, flagging the rows inconsistent with it.
+///
+/// # Comments
+/// The amount lent is the balance before the first row, i.e. its balance plus its principal, and the rows are assumed to be in order of period.
+/// The rate is the IRR of lending that amount and receiving the payments plus the final balance, found with Brent's method after searching outwards from the average of the rates implied by each row.
+/// A row is inconsistent if its interest differs from the rate times the balance before it, or its payment differs from its interest plus principal, by more than `tolerance`, e.g. half a cent for a schedule rounded to cents.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::{amortize, implied_rate_from_schedule, ImpliedRate, Row, Schedule};
+///
+/// let schedule: Schedule<f64> = amortize(1_000.0, 0.01, 12);
+/// let implied: ImpliedRate<f64> = implied_rate_from_schedule(&schedule, 0.000_001);
+/// assert!(implied.is_consistent());
+/// assert!((implied.rate() - 0.01).abs() < 0.000_001);
+///
+/// // the fourth row charges interest of 9.00 rather than 7.61
+/// let mut rows: Vec<Row<f64>> = schedule.rows().to_vec();
+/// let row: Row<f64> = rows[3];
+/// rows[3] = Row::new(4, row.payment(), 9.0, row.payment() - 9.0, row.balance());
+/// let implied: ImpliedRate<f64> = implied_rate_from_schedule(&Schedule::new(rows), 0.005);
+/// assert_eq!(implied.inconsistent_periods(), &[4]);
+/// ```
+pub fn implied_rate_from_schedule<T>(schedule: &Schedule<T>, tolerance: T) -> ImpliedRate<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let rows: &[Row<T>] = schedule.rows();
+    let lent: T = rows
+        .first()
+        .map_or_else(T::zero, |row| row.balance() + row.principal());
+    let outstanding: T = rows.last().map_or_else(T::zero, |row| row.balance());
+    let npv = |rate: T| {
+        let growth: T = T::one() + rate;
+        let received: T = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| row.payment() / powi(growth, index as i32 + 1))
+            .sum();
+        received + outstanding / powi(growth, rows.len() as i32) - lent
+    };
+
+    let mut opening: T = lent;
+    let mut row_rates: Vec<T> = Vec::with_capacity(rows.len());
+    for row in rows {
+        row_rates.push(row.interest() / opening);
+        opening = row.balance();
+    }
+    let finite_rates: Vec<T> = row_rates
+        .iter()
+        .copied()
+        .filter(|rate| rate.is_finite())
+        .collect();
+    let guess: T = if finite_rates.is_empty() {
+        T::zero()
+    } else {
+        finite_rates.iter().copied().sum::<T>() / T::from(finite_rates.len()).unwrap()
+    };
+    let amounts: Vec<T> = rows
+        .iter()
+        .map(|row| row.payment())
+        .chain(vec![lent, outstanding])
+        .collect();
+    let solved: SolveResult<T> = solve_from_guess(npv, guess, 100, scale(amounts.iter()));
+
+    let mut opening: T = lent;
+    let mut inconsistent_periods: Vec<usize> = vec![];
+    for row in rows {
+        let interest_error: T = row.interest() - solved.irr() * opening;
+        let payment_error: T = row.payment() - row.interest() - row.principal();
+        if !(interest_error.abs() <= tolerance && payment_error.abs() <= tolerance) {
+            inconsistent_periods.push(row.period());
+        }
+        opening = row.balance();
+    }
+
+    ImpliedRate {
+        solved,
+        row_rates,
+        inconsistent_periods,
+    }
+}
+
+#[cfg(test)]
+mod amortize_tests {
+    use crate::loan::{amortize, Schedule};
+
+    #[test]
+    fn it_works_at_a_zero_rate() {
+        let schedule: Schedule<f64> = amortize(1_200.0, 0.0, 12);
+        for (index, row) in schedule.rows().iter().enumerate() {
+            assert_eq!(row.payment(), 100.0);
+            assert_eq!(row.interest(), 0.0);
+            assert_eq!(row.balance(), 1_200.0 - 100.0 * (index + 1) as f64);
+        }
+    }
+
+    #[test]
+    fn it_works_with_no_periods() {
+        assert!(amortize(1_000.0_f32, 0.05, 0).is_empty());
+    }
+
+    #[test]
+    fn it_repays_the_principal() {
+        let schedule: Schedule<f64> = amortize(250_000.0, 0.004, 360);
+        let repaid: f64 = schedule.rows().iter().map(|row| row.principal()).sum();
+        assert!((repaid - 250_000.0).abs() < 0.000_01);
+    }
+}
+
+#[cfg(test)]
+mod amortize_with_stub_period_tests {
+    use crate::loan::{amortize, amortize_with_stub_period, Schedule};
+
+    #[test]
+    fn it_matches_amortize_at_a_fraction_of_one() {
+        let stub: Schedule<f64> = amortize_with_stub_period(1_000.0, 0.01, 12, 1.0);
+        let whole: Schedule<f64> = amortize(1_000.0, 0.01, 12);
+        for (stub_row, whole_row) in stub.rows().iter().zip(whole.rows()) {
+            assert!((stub_row.payment() - whole_row.payment()).abs() < 0.000_000_001);
+            assert!((stub_row.balance() - whole_row.balance()).abs() < 0.000_000_001);
+        }
+    }
+
+    #[test]
+    fn it_repays_the_principal() {
+        let schedule: Schedule<f64> = amortize_with_stub_period(250_000.0, 0.004, 360, 0.3);
+        let repaid: f64 = schedule.rows().iter().map(|row| row.principal()).sum();
+        assert!((repaid - 250_000.0).abs() < 0.000_01);
+        assert!(schedule.rows()[0].interest() < 250_000.0 * 0.004);
+    }
+
+    #[test]
+    fn it_works_with_no_periods() {
+        assert!(amortize_with_stub_period(1_000.0_f32, 0.05, 0, 0.5).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod implied_rate_from_schedule_tests {
+    use crate::loan::{amortize, implied_rate_from_schedule, ImpliedRate, Row, Schedule};
+
+    #[test]
+    fn it_round_trips_amortize() {
+        for rate in [0.0, 0.002, 0.05].iter() {
+            let schedule: Schedule<f64> = amortize(50_000.0, *rate, 60);
+            let implied: ImpliedRate<f64> = implied_rate_from_schedule(&schedule, 0.000_001);
+
+            assert!(implied.is_consistent());
+            assert!((implied.rate() - rate).abs() < 0.000_000_1);
+        }
+    }
+
+    #[test]
+    fn it_includes_the_final_balance() {
+        // interest only for two periods, then the balloon is still outstanding
+        let schedule: Schedule<f64> = vec![
+            Row::new(1, 50.0, 50.0, 0.0, 1_000.0),
+            Row::new(2, 50.0, 50.0, 0.0, 1_000.0),
+        ]
+        .into_iter()
+        .collect();
+        let implied: ImpliedRate<f64> = implied_rate_from_schedule(&schedule, 0.000_001);
+
+        assert!(implied.is_consistent());
+        assert!((implied.rate() - 0.05).abs() < 0.000_001);
+        assert_eq!(implied.row_rates(), &[0.05, 0.05]);
+    }
+
+    #[test]
+    fn it_flags_a_payment_that_does_not_add_up() {
+        let mut rows: Vec<Row<f64>> = amortize(1_000.0, 0.01, 6).rows().to_vec();
+        let row: Row<f64> = rows[1];
+        rows[1] = Row::new(
+            2,
+            row.payment() + 1.0,
+            row.interest(),
+            row.principal(),
+            row.balance(),
+        );
+        let implied: ImpliedRate<f64> = implied_rate_from_schedule(&Schedule::new(rows), 0.01);
+
+        assert!(!implied.is_consistent());
+        assert!(implied.inconsistent_periods().contains(&2));
+    }
+
+    #[test]
+    fn it_works_with_f32() {
+        let schedule: Schedule<f32> = amortize(1_000.0, 0.01, 60);
+        let implied: ImpliedRate<f32> = implied_rate_from_schedule(&schedule, 0.01);
+
+        assert!(implied.is_consistent());
+        assert!((implied.rate() - 0.01).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_works_with_long_schedules() {
+        let schedule: Schedule<f64> = amortize(400_000.0, 0.065 / 12.0, 480);
+        let implied: ImpliedRate<f64> = implied_rate_from_schedule(&schedule, 0.000_001);
+
+        assert!(implied.is_consistent());
+        assert!((implied.rate() - 0.065 / 12.0).abs() < 0.000_000_001);
+
+        let schedule: Schedule<f32> = amortize(400_000.0, 0.065 / 12.0, 480);
+        let implied: ImpliedRate<f32> = implied_rate_from_schedule(&schedule, 1.0);
+
+        assert!(implied.is_consistent());
+        assert!((implied.rate() - 0.065 / 12.0).abs() < 0.000_01);
+    }
+}
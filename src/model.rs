@@ -0,0 +1,131 @@
+//! A builder for projecting the cash flows of a simple operating model.
+
+use num::Float;
+
+/// Projects cash flows from revenue, costs, capital expenditure and tax assumptions.
+///
+/// # Comments
+/// Period 0 is the investment date and only carries capital expenditure; operating periods run from 1 to `periods`.
+/// In operating period `t`:
+/// - revenue is `base * (1 + growth)^(t - 1)`
+/// - costs are `ratio * revenue`
+/// - tax is `rate * (revenue - costs)` when that profit is positive, and zero otherwise
+/// - the cash flow is `revenue - costs - tax - capex`, where capex is the capex schedule entry for the period, or zero
+///
+/// # Example with f64
+/// ```
+/// use time_value::model::Model;
+///
+/// let cash_flows: Vec<f64> = Model::new(3)
+///     .revenue(100.0, 0.10)
+///     .costs(0.60)
+///     .capex(vec![-150.0, 0.0, -10.0])
+///     .tax(0.25)
+///     .build();
+///
+/// assert_eq!(cash_flows.len(), 4);
+/// assert_eq!(cash_flows[0], -150.0);
+/// assert!((cash_flows[1] - 30.0).abs() < 0.000_001);
+/// assert!((cash_flows[2] - 23.0).abs() < 0.000_001);
+/// assert!((cash_flows[3] - 36.3).abs() < 0.000_001);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Model<T>
+where
+    T: Float,
+{
+    periods: usize,
+    revenue_base: T,
+    revenue_growth: T,
+    cost_ratio: T,
+    capex: Vec<T>,
+    tax_rate: T,
+}
+
+impl<T> Model<T>
+where
+    T: Float,
+{
+    /// Starts a model with `periods` operating periods and no revenue, costs, capex or tax.
+    pub fn new(periods: usize) -> Model<T> {
+        Model {
+            periods,
+            revenue_base: T::zero(),
+            revenue_growth: T::zero(),
+            cost_ratio: T::zero(),
+            capex: vec![],
+            tax_rate: T::zero(),
+        }
+    }
+
+    /// Revenue of `base` in the first operating period, growing at `growth` per period thereafter.
+    pub fn revenue(mut self, base: T, growth: T) -> Model<T> {
+        self.revenue_base = base;
+        self.revenue_growth = growth;
+        self
+    }
+
+    /// Costs as a ratio of revenue.
+    pub fn costs(mut self, ratio: T) -> Model<T> {
+        self.cost_ratio = ratio;
+        self
+    }
+
+    /// Capital expenditure per period starting at period 0, as signed cash flows i.e. spending is negative.
+    pub fn capex(mut self, schedule: Vec<T>) -> Model<T> {
+        self.capex = schedule;
+        self
+    }
+
+    /// Tax as a rate of positive operating profit.
+    pub fn tax(mut self, rate: T) -> Model<T> {
+        self.tax_rate = rate;
+        self
+    }
+
+    /// Projects the cash flows for periods `0..=periods`.
+    pub fn build(&self) -> Vec<T> {
+        let capex = |period: usize| self.capex.get(period).copied().unwrap_or_else(T::zero);
+        let growth: T = T::one() + self.revenue_growth;
+
+        let mut cash_flows: Vec<T> = vec![capex(0)];
+        let mut revenue: T = self.revenue_base;
+        for period in 1..=self.periods {
+            let profit: T = revenue - self.cost_ratio * revenue;
+            let tax: T = self.tax_rate * profit.max(T::zero());
+            cash_flows.push(profit - tax + capex(period));
+            revenue = revenue * growth;
+        }
+        cash_flows
+    }
+}
+
+#[cfg(test)]
+mod model_tests {
+    use crate::model::Model;
+
+    #[test]
+    fn it_works_with_no_assumptions() {
+        let cash_flows: Vec<f32> = Model::new(5).build();
+        assert_eq!(cash_flows, vec![0.0; 6]);
+    }
+
+    #[test]
+    fn it_does_not_tax_losses() {
+        let cash_flows: Vec<f64> = Model::new(2)
+            .revenue(100.0, 0.0)
+            .costs(1.5)
+            .tax(0.30)
+            .build();
+        assert_eq!(cash_flows, vec![0.0, -50.0, -50.0]);
+    }
+
+    #[test]
+    fn it_ignores_capex_beyond_the_horizon() {
+        let cash_flows: Vec<f64> = Model::new(1)
+            .revenue(10.0, 0.0)
+            .capex(vec![-5.0, -1.0, -100.0])
+            .build();
+        assert_eq!(cash_flows, vec![-5.0, 9.0]);
+    }
+}
@@ -0,0 +1,415 @@
+//! Functions for goal-based financial planning.
+
+use num::{Float, Signed};
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::root_finder::{Brent, RootFinder, SolveResult};
+use crate::power::powi;
+
+/// The annual return above which a required return is flagged as implausible for a diversified portfolio.
+pub const PLAUSIBLE_RETURN: f64 = 0.12;
+
+/// Why a required return deserves a second look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// No return between -99% and 1,000% a year reaches the goal.
+    GoalUnreachable,
+    /// The goal is reached even with a negative return, so the savings alone suffice.
+    NegativeReturnSuffices,
+    /// The required return is above `PLAUSIBLE_RETURN`.
+    AbovePlausibleReturn,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Warning::GoalUnreachable => write!(f, "no plausible return reaches the goal"),
+            Warning::NegativeReturnSuffices => {
+                write!(f, "the goal is reached even with a negative return")
+            }
+            Warning::AbovePlausibleReturn => {
+                write!(f, "the required return is above what is plausible")
+            }
+        }
+    }
+}
+
+/// The annual return needed to reach a goal, with a warning if it deserves a second look.
+#[derive(Debug)]
+pub struct RequiredReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    solved: SolveResult<T>,
+    warning: Option<Warning>,
+}
+
+impl<T> RequiredReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    /// The required annual return, or NaN if the goal is unreachable.
+    pub fn rate(&self) -> T {
+        self.solved.irr()
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `rate` instead")]
+    pub fn get_rate(&self) -> T {
+        self.rate()
+    }
+
+    /// The result of the root finder, whose `npv` fields hold the difference between the future value and the goal.
+    pub fn solved(&self) -> &SolveResult<T> {
+        &self.solved
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `solved` instead")]
+    pub fn get_solved(&self) -> &SolveResult<T> {
+        self.solved()
+    }
+
+    pub fn warning(&self) -> Option<Warning> {
+        self.warning
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `warning` instead")]
+    pub fn get_warning(&self) -> Option<Warning> {
+        self.warning()
+    }
+}
+
+/// Solves for the annual return at which `current_assets` and `contributions` grow to `goal_fv` in `horizon` years.
+///
+/// # Comments
+/// Contributions are made at the end of years `1..=horizon`, one per entry; years without an entry contribute nothing and entries beyond the horizon are ignored.
+/// The future value increases with the return, so the return is found with Brent's method between -99% and 1,000%.
+///
+/// # Example with f64
+/// ```
+/// use time_value::planning::{required_return, RequiredReturn, Warning};
+///
+/// // 50,000 saved and 10,000 a year for 20 years, aiming for 600,000
+/// let contributions: Vec<f64> = vec![10_000.0; 20];
+/// let required: RequiredReturn<f64> =
+///     required_return(50_000.0, contributions.iter(), 600_000.0, 20);
+/// assert!((required.rate() - 0.0696).abs() < 0.000_1);
+/// assert_eq!(required.warning(), None);
+///
+/// let required: RequiredReturn<f64> =
+///     required_return(50_000.0, contributions.iter(), 5_000_000.0, 20);
+/// assert_eq!(required.warning(), Some(Warning::AbovePlausibleReturn));
+/// ```
+pub fn required_return<T>(
+    current_assets: T,
+    contributions: Iter<T>,
+    goal_fv: T,
+    horizon: usize,
+) -> RequiredReturn<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let future_value = |rate: T| {
+        let growth: T = T::one() + rate;
+        let contributed: T = contributions
+            .clone()
+            .take(horizon)
+            .enumerate()
+            .map(|(year, contribution)| *contribution * powi(growth, (horizon - year - 1) as i32))
+            .sum();
+        current_assets * powi(growth, horizon as i32) + contributed
+    };
+
+    let solved: SolveResult<T> = Brent::new(100).solve(
+        |rate| future_value(rate) - goal_fv,
+        T::from(-0.99).unwrap(),
+        T::from(10.0).unwrap(),
+    );
+    let warning: Option<Warning> = if !solved.is_valid() {
+        Some(Warning::GoalUnreachable)
+    } else if solved.irr() < T::zero() {
+        Some(Warning::NegativeReturnSuffices)
+    } else if T::from(PLAUSIBLE_RETURN).unwrap() < solved.irr() {
+        Some(Warning::AbovePlausibleReturn)
+    } else {
+        None
+    };
+
+    RequiredReturn { solved, warning }
+}
+
+#[cfg(test)]
+mod required_return_tests {
+    use crate::future_value::from_pv_and_expected_rates as fv;
+    use crate::planning::{required_return, RequiredReturn, Warning};
+
+    #[test]
+    fn it_round_trips_without_contributions() {
+        let contributions: Vec<f64> = vec![];
+        let goal: f64 = fv(1_000.0, [0.07; 10].iter());
+        let required: RequiredReturn<f64> =
+            required_return(1_000.0, contributions.iter(), goal, 10);
+
+        assert!((required.rate() - 0.07).abs() < 0.000_001);
+        assert_eq!(required.warning(), None);
+    }
+
+    #[test]
+    fn it_warns_when_savings_alone_suffice() {
+        let contributions: Vec<f64> = vec![100.0; 5];
+        let required: RequiredReturn<f64> =
+            required_return(1_000.0, contributions.iter(), 1_200.0, 5);
+
+        assert!(required.rate() < 0.0);
+        assert_eq!(required.warning(), Some(Warning::NegativeReturnSuffices));
+    }
+
+    #[test]
+    fn it_warns_when_the_goal_is_unreachable() {
+        let contributions: Vec<f64> = vec![];
+        let required: RequiredReturn<f64> = required_return(0.0, contributions.iter(), 1_000.0, 5);
+
+        assert!(required.rate().is_nan());
+        assert_eq!(required.warning(), Some(Warning::GoalUnreachable));
+    }
+}
+
+/// An amount needed at the end of a year, with a priority for funding it ahead of other goals.
+///
+/// # Comments
+/// Goals with a lower `priority` are funded first, and goals with the same priority in the order given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Goal<T>
+where
+    T: Float,
+{
+    year: usize,
+    amount: T,
+    priority: u32,
+}
+
+impl<T> Goal<T>
+where
+    T: Float,
+{
+    pub fn new(year: usize, amount: T, priority: u32) -> Goal<T> {
+        Goal {
+            year,
+            amount,
+            priority,
+        }
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `year` instead")]
+    pub fn get_year(&self) -> usize {
+        self.year()
+    }
+
+    pub fn amount(&self) -> T {
+        self.amount
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `amount` instead")]
+    pub fn get_amount(&self) -> T {
+        self.amount()
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `priority` instead")]
+    pub fn get_priority(&self) -> u32 {
+        self.priority()
+    }
+}
+
+/// How much of a goal can be funded, and the earliest year it could be funded in full.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoalStatus<T>
+where
+    T: Float,
+{
+    goal: Goal<T>,
+    funded: T,
+    earliest_feasible_year: Option<usize>,
+}
+
+impl<T> GoalStatus<T>
+where
+    T: Float,
+{
+    pub fn goal(&self) -> Goal<T> {
+        self.goal
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `goal` instead")]
+    pub fn get_goal(&self) -> Goal<T> {
+        self.goal()
+    }
+
+    /// The amount that can be withdrawn for the goal in its year, after the goals funded before it.
+    pub fn funded(&self) -> T {
+        self.funded
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `funded` instead")]
+    pub fn get_funded(&self) -> T {
+        self.funded()
+    }
+
+    /// The share of the goal that can be funded, between 0 and 1.
+    pub fn funded_ratio(&self) -> T {
+        if self.goal.amount <= T::zero() {
+            T::one()
+        } else {
+            self.funded / self.goal.amount
+        }
+    }
+
+    pub fn is_funded(&self) -> bool {
+        self.goal.amount <= self.funded
+    }
+
+    /// The earliest year by `horizon` in which the goal could be funded in full after the goals funded before it, or `None` if there is none.
+    pub fn earliest_feasible_year(&self) -> Option<usize> {
+        self.earliest_feasible_year
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `earliest_feasible_year` instead")]
+    pub fn get_earliest_feasible_year(&self) -> Option<usize> {
+        self.earliest_feasible_year()
+    }
+}
+
+/// Funds several goals from current assets and contributions in order of priority, reporting the status of each.
+///
+/// # Comments
+/// Assets grow at `expected_return` a year, contribution `t` of `contributions` is added at the end of year `t + 1`, and the goals are withdrawn at the end of their years.
+/// Each goal in turn is funded with the largest withdrawal that keeps the balance non-negative up to `horizon`, or the last goal's year if later, given the withdrawals of the goals funded before it.
+/// The earliest feasible year of a goal is found by seeking the first year from 0 in which that withdrawal covers the goal, so it may be before the year of the goal.
+/// The statuses are in the order of `goals`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::planning::{plan_goals, Goal, GoalStatus};
+///
+/// // 10,000 saved and 5,000 a year at 5%: a 20,000 car in 3 years comes before a 40,000 deposit in 5 years
+/// let contributions: Vec<f64> = vec![5_000.0; 10];
+/// let goals: Vec<Goal<f64>> = vec![Goal::new(5, 40_000.0, 2), Goal::new(3, 20_000.0, 1)];
+/// let statuses: Vec<GoalStatus<f64>> =
+///     plan_goals(10_000.0, contributions.iter(), 0.05, goals.iter(), 10);
+///
+/// assert!(statuses[1].is_funded());
+/// assert_eq!(statuses[1].earliest_feasible_year(), Some(2));
+///
+/// // 10,000 * 1.05^5 + 5,000 * 5.5256 - 20,000 * 1.05^2 = 18,341
+/// assert!(!statuses[0].is_funded());
+/// assert!((statuses[0].funded() - 18_341.0).abs() < 1.0);
+/// assert_eq!(statuses[0].earliest_feasible_year(), Some(9));
+/// ```
+pub fn plan_goals<T>(
+    current_assets: T,
+    contributions: Iter<T>,
+    expected_return: T,
+    goals: Iter<Goal<T>>,
+    horizon: usize,
+) -> Vec<GoalStatus<T>>
+where
+    T: Float,
+{
+    let goals: &[Goal<T>] = goals.as_slice();
+    let horizon: usize = goals.iter().map(Goal::year).fold(horizon, usize::max);
+    let contributions: &[T] = contributions.as_slice();
+    let growth: T = T::one() + expected_return;
+
+    let mut order: Vec<usize> = (0..goals.len()).collect();
+    order.sort_by_key(|&index| goals[index].priority);
+
+    let mut withdrawals: Vec<T> = vec![T::zero(); horizon + 1];
+    let mut statuses: Vec<Option<GoalStatus<T>>> = vec![None; goals.len()];
+    for index in order {
+        let goal: Goal<T> = goals[index];
+        let balances: Vec<T> = balances(current_assets, contributions, &withdrawals, growth);
+        let capacity = |year: usize| {
+            (year..=horizon)
+                .map(|later| balances[later] / powi(growth, (later - year) as i32))
+                .fold(T::infinity(), T::min)
+                .max(T::zero())
+        };
+
+        let funded: T = goal.amount.min(capacity(goal.year)).max(T::zero());
+        let earliest_feasible_year: Option<usize> =
+            (0..=horizon).find(|&year| goal.amount <= capacity(year));
+        withdrawals[goal.year] = withdrawals[goal.year] + funded;
+        statuses[index] = Some(GoalStatus {
+            goal,
+            funded,
+            earliest_feasible_year,
+        });
+    }
+
+    statuses.into_iter().flatten().collect()
+}
+
+/// The balance at the end of years `0..withdrawals.len()` after contributions and withdrawals.
+fn balances<T>(current_assets: T, contributions: &[T], withdrawals: &[T], growth: T) -> Vec<T>
+where
+    T: Float,
+{
+    let mut balance: T = current_assets;
+    withdrawals
+        .iter()
+        .enumerate()
+        .map(|(year, withdrawal)| {
+            if 0 < year {
+                let contribution: T = contributions.get(year - 1).copied().unwrap_or_else(T::zero);
+                balance = balance * growth + contribution;
+            }
+            balance = balance - *withdrawal;
+            balance
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod plan_goals_tests {
+    use crate::planning::{plan_goals, Goal, GoalStatus};
+
+    #[test]
+    fn it_funds_higher_priorities_first() {
+        let contributions: Vec<f64> = vec![100.0; 4];
+        let goals: Vec<Goal<f64>> = vec![Goal::new(4, 300.0, 2), Goal::new(4, 300.0, 1)];
+        let statuses: Vec<GoalStatus<f64>> =
+            plan_goals(0.0, contributions.iter(), 0.0, goals.iter(), 4);
+
+        assert!(statuses[1].is_funded());
+        assert_eq!(statuses[1].earliest_feasible_year(), Some(3));
+        assert!((statuses[0].funded_ratio() - 1.0 / 3.0).abs() < 0.000_001);
+        assert_eq!(statuses[0].earliest_feasible_year(), None);
+    }
+
+    #[test]
+    fn it_keeps_later_withdrawals_funded() {
+        // an early, low priority goal cannot use the money set aside for a later, high priority one
+        let contributions: Vec<f64> = vec![100.0; 4];
+        let goals: Vec<Goal<f64>> = vec![Goal::new(2, 200.0, 2), Goal::new(4, 300.0, 1)];
+        let statuses: Vec<GoalStatus<f64>> =
+            plan_goals(0.0, contributions.iter(), 0.0, goals.iter(), 4);
+
+        assert!(statuses[1].is_funded());
+        assert!((statuses[0].funded() - 100.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_works_without_goals() {
+        let contributions: Vec<f32> = vec![100.0; 4];
+        let goals: Vec<Goal<f32>> = vec![];
+        assert!(plan_goals(0.0, contributions.iter(), 0.05, goals.iter(), 4).is_empty());
+    }
+}
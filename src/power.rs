@@ -0,0 +1,198 @@
+//! Powers used when discounting, with an optional portable implementation.
+//!
+//! `Float::powi` lowers to a compiler intrinsic, and `Float::powf` to the platform's math library, whose rounding may differ between targets and optimisation levels.
+//! With the `deterministic` feature, integer powers are instead computed by squaring and multiplying, and other powers from series for the logarithm and exponential, which only use IEEE 754 arithmetic and so give bit-identical results on every target.
+
+use num::Float;
+
+/// Raises `base` to the integer power `exponent`.
+#[cfg(not(feature = "deterministic"))]
+pub(crate) fn powi<T>(base: T, exponent: i32) -> T
+where
+    T: Float,
+{
+    base.powi(exponent)
+}
+
+/// Raises `base` to the integer power `exponent`.
+#[cfg(feature = "deterministic")]
+pub(crate) fn powi<T>(base: T, exponent: i32) -> T
+where
+    T: Float,
+{
+    let mut remaining: u32 = exponent.unsigned_abs();
+    let mut square: T = base;
+    let mut power: T = T::one();
+    while 0 < remaining {
+        if remaining & 1 == 1 {
+            power = power * square;
+        }
+        square = square * square;
+        remaining >>= 1;
+    }
+    if exponent < 0 {
+        T::one() / power
+    } else {
+        power
+    }
+}
+
+/// Raises `base` to the power `exponent`, which need not be whole.
+#[cfg(not(feature = "deterministic"))]
+pub(crate) fn powf<T>(base: T, exponent: T) -> T
+where
+    T: Float,
+{
+    base.powf(exponent)
+}
+
+/// Raises `base` to the power `exponent`, which need not be whole.
+///
+/// Whole exponents use `powi`; infinite or NaN arguments, and zero or negative bases, use `Float::powf`, whose results there are exact.
+#[cfg(feature = "deterministic")]
+pub(crate) fn powf<T>(base: T, exponent: T) -> T
+where
+    T: Float,
+{
+    if !base.is_finite() || !exponent.is_finite() || base <= T::zero() {
+        return base.powf(exponent);
+    }
+    if exponent.fract() == T::zero() && exponent.abs() <= T::from(i32::MAX).unwrap() {
+        return powi(base, exponent.to_i32().unwrap());
+    }
+    exp(exponent * ln(base))
+}
+
+/// The natural logarithm of a positive, finite `x`.
+#[cfg(feature = "deterministic")]
+fn ln<T>(x: T) -> T
+where
+    T: Float,
+{
+    // x = m * 2^k with m in [1/sqrt(2), sqrt(2)), scaling by two being exact
+    let two: T = T::from(2.0).unwrap();
+    let sqrt_two: T = T::from(std::f64::consts::SQRT_2).unwrap();
+    let mut m: T = x;
+    let mut k: i32 = 0;
+    while sqrt_two <= m {
+        m = m / two;
+        k += 1;
+    }
+    while m * sqrt_two < T::one() {
+        m = m * two;
+        k -= 1;
+    }
+    // ln(m) = 2 * atanh(s) = 2 * (s + s^3 / 3 + s^5 / 5 + ...)
+    let s: T = (m - T::one()) / (m + T::one());
+    let s_squared: T = s * s;
+    let mut power: T = s;
+    let mut sum: T = s;
+    let mut denominator: T = T::one();
+    loop {
+        power = power * s_squared;
+        denominator = denominator + two;
+        let term: T = power / denominator;
+        if term.abs() <= sum.abs() * T::epsilon() {
+            break;
+        }
+        sum = sum + term;
+    }
+    two * sum + T::from(k).unwrap() * T::from(std::f64::consts::LN_2).unwrap()
+}
+
+/// The exponential of a finite `y`.
+#[cfg(feature = "deterministic")]
+fn exp<T>(y: T) -> T
+where
+    T: Float,
+{
+    // y = r + k * ln(2) with |r| <= ln(2) / 2
+    let ln_two: T = T::from(std::f64::consts::LN_2).unwrap();
+    let k: T = (y / ln_two).round();
+    if T::from(i32::MAX).unwrap() < k.abs() {
+        return if k < T::zero() {
+            T::zero()
+        } else {
+            T::infinity()
+        };
+    }
+    let r: T = y - k * ln_two;
+    // exp(r) = 1 + r + r^2 / 2! + ...
+    let mut term: T = T::one();
+    let mut sum: T = T::one();
+    let mut n: T = T::zero();
+    loop {
+        n = n + T::one();
+        term = term * r / n;
+        if term.abs() <= sum.abs() * T::epsilon() {
+            break;
+        }
+        sum = sum + term;
+    }
+    sum * powi(T::from(2.0).unwrap(), k.to_i32().unwrap())
+}
+
+#[cfg(test)]
+mod powi_tests {
+    use crate::power::powi;
+
+    #[test]
+    fn it_works_with_a_zero_exponent() {
+        assert_eq!(powi(1.1_f64, 0), 1.0);
+        assert_eq!(powi(0.0_f32, 0), 1.0);
+    }
+
+    #[test]
+    fn it_is_exact_for_powers_of_two() {
+        assert_eq!(powi(2.0_f64, 10), 1_024.0);
+        assert_eq!(powi(2.0_f64, -3), 0.125);
+        assert_eq!(powi(-2.0_f32, 3), -8.0);
+    }
+
+    #[test]
+    fn it_agrees_with_the_standard_library() {
+        for exponent in -360..=360 {
+            let expected: f64 = 1.004_f64.powi(exponent);
+            assert!((powi(1.004_f64, exponent) - expected).abs() <= expected * 0.000_000_000_001);
+        }
+    }
+}
+
+#[cfg(test)]
+mod powf_tests {
+    use crate::power::powf;
+
+    #[test]
+    fn it_works_with_whole_exponents() {
+        assert_eq!(powf(1.1_f64, 0.0), 1.0);
+        assert_eq!(powf(2.0_f64, 10.0), 1_024.0);
+        assert_eq!(powf(2.0_f32, -3.0), 0.125);
+    }
+
+    #[test]
+    fn it_works_at_the_edges() {
+        assert_eq!(powf(0.0_f64, 0.5), 0.0);
+        assert_eq!(powf(0.0_f64, -0.5), f64::INFINITY);
+        assert!(powf(-1.1_f64, 0.5).is_nan());
+        assert!(powf(f64::NAN, 0.5).is_nan());
+        assert_eq!(powf(1.1_f64, f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn it_agrees_with_the_standard_library() {
+        for base in [0.001_f64, 0.5, 0.95, 1.0, 1.004, 1.37, 2.5, 1_000.0].iter() {
+            for exponent in -400..=400 {
+                let exponent: f64 = exponent as f64 / 7.0;
+                let expected: f64 = base.powf(exponent);
+                let calculated: f64 = powf(*base, exponent);
+                assert!(
+                    (calculated - expected).abs() <= expected * 0.000_000_000_001,
+                    "{}^{}",
+                    base,
+                    exponent
+                );
+            }
+        }
+        assert!((powf(1.08_f32, 0.25) - 1.08_f32.powf(0.25)).abs() <= f32::EPSILON * 2.0);
+    }
+}
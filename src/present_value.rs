@@ -1,9 +1,12 @@
 //! Functions for calculating present values.
 
 use num::Float;
+use std::borrow::Borrow;
 use std::iter::{Product, Sum};
 use std::slice::Iter;
 
+use crate::power::{powf, powi};
+
 #[allow(dead_code)]
 /// Converts a single value to a present value.
 ///
@@ -20,7 +23,7 @@ use std::slice::Iter;
 /// let period: usize = 1;
 /// let discount_rate: f32 = 0.20;
 /// let expected_value: f32 = 4.167;
-/// let value: f32 = present_value(&cash_flow, period, &discount_rate);
+/// let value: f32 = present_value(cash_flow, period, discount_rate);
 /// assert!(abs(value - expected_value) < 0.001);
 /// ```
 ///
@@ -37,20 +40,21 @@ use std::slice::Iter;
 /// let period: usize = 2;
 /// let discount_rate: f64 = 0.10;
 /// let expected_value: f64 = 8.264;
-/// let value: f64 = present_value(&cash_flow, period, &discount_rate);
+/// let value: f64 = present_value(cash_flow, period, discount_rate);
 /// assert!(abs(value - expected_value) < 0.001);
 /// ```
-pub fn present_value<T>(cash_flow: &T, period: usize, discount_rate: &T) -> T
+pub fn present_value<T>(cash_flow: T, period: usize, discount_rate: T) -> T
 where
     T: Float + Product<T>,
 {
     let period: i32 = -(period as i32);
-    let discount: T = T::one() + *discount_rate;
-    let discount_factor: T = discount.powi(period);
-    *cash_flow * discount_factor
+    let discount: T = T::one() + discount_rate;
+    let discount_factor: T = powi(discount, period);
+    cash_flow * discount_factor
 }
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod present_value_tests {
     use crate::present_value::present_value;
     use num::abs;
@@ -61,7 +65,7 @@ mod present_value_tests {
         let period: usize = 0;
         let discount_rate: f32 = 0.20;
         for cash_flow in cash_flows {
-            assert_eq!(cash_flow, present_value(&cash_flow, period, &discount_rate));
+            assert_eq!(cash_flow, present_value(cash_flow, period, discount_rate));
         }
     }
 
@@ -73,7 +77,7 @@ mod present_value_tests {
         let expected_present_values: Vec<f32> = vec![0.00, 0.833, -0.833, 1028.806, -1028.806];
         let precision: f32 = 0.001;
         for index in 0..cash_flows.len() {
-            let actual_pv: f32 = present_value(&cash_flows[index], period, &discount_rate);
+            let actual_pv: f32 = present_value(cash_flows[index], period, discount_rate);
             let expected_pv: f32 = expected_present_values[index];
             assert!(abs(actual_pv - expected_pv) <= precision);
         }
@@ -87,7 +91,7 @@ mod present_value_tests {
         let expected_present_values: Vec<f32> = vec![0.00, 0.6944, -0.6944, 857.338, -857.338];
         let precision: f32 = 0.001;
         for index in 0..cash_flows.len() {
-            let actual_pv: f32 = present_value(&cash_flows[index], period, &discount_rate);
+            let actual_pv: f32 = present_value(cash_flows[index], period, discount_rate);
             let expected_pv: f32 = expected_present_values[index];
             assert!(abs(actual_pv - expected_pv) <= precision);
         }
@@ -108,7 +112,7 @@ mod present_value_tests {
 /// let discount_rate: f32 = 0.10;
 /// assert_eq!(
 ///     cash_flows[0],
-///    from_cash_flows_and_discount_rate(cash_flows.iter(), &discount_rate)
+///    from_cash_flows_and_discount_rate(cash_flows.iter(), discount_rate)
 /// )
 /// ```
 ///
@@ -122,23 +126,72 @@ mod present_value_tests {
 ///
 /// let cash_flows: Vec<f64> = vec![10.0, 10.0, 10.0];
 /// let discount_rate: f64 = 0.10;
-/// let value: f64 = from_cash_flows_and_discount_rate(cash_flows.iter(), &discount_rate);
+/// let value: f64 = from_cash_flows_and_discount_rate(cash_flows.iter(), discount_rate);
 /// let expected_value: f64 = 27.35;
 /// assert!(abs(value - expected_value) < 0.01);
 /// ```
-pub fn from_cash_flows_and_discount_rate<T>(cash_flows: Iter<T>, discount_rate: &T) -> T
+///
+/// # Example with other collections
+/// Any collection or iterator of cash flows, or references to them, can be used.
+/// ```
+/// use std::collections::VecDeque;
+/// use time_value::present_value::from_cash_flows_and_discount_rate;
+///
+/// let cash_flows: Vec<f64> = vec![10.0, 10.0, 10.0];
+/// let mut deque: VecDeque<f64> = VecDeque::new();
+/// deque.extend(cash_flows.iter());
+///
+/// let value: f64 = from_cash_flows_and_discount_rate(cash_flows.iter(), 0.10);
+/// assert_eq!(from_cash_flows_and_discount_rate(&deque, 0.10), value);
+/// assert_eq!(from_cash_flows_and_discount_rate(&cash_flows[..], 0.10), value);
+/// assert_eq!(from_cash_flows_and_discount_rate(vec![10.0; 3], 0.10), value);
+/// ```
+pub fn from_cash_flows_and_discount_rate<T, I, B>(cash_flows: I, discount_rate: T) -> T
 where
     T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
 {
-    cash_flows
+    let npv: T = cash_flows
+        .into_iter()
         .enumerate()
         .map(|(period, cash_flow)| {
-            crate::present_value::present_value(cash_flow, period, discount_rate)
+            crate::present_value::present_value(*cash_flow.borrow(), period, discount_rate)
         })
-        .sum()
+        .sum();
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        discount_rate = crate::trace::value(discount_rate),
+        npv = crate::trace::value(npv),
+        "present value"
+    );
+    npv
+}
+
+/// Converts a slice of cash flows and a discount rate into a present value.
+///
+/// # Comments
+/// This is `from_cash_flows_and_discount_rate` over the slice, including its `tracing` event; the solvers' `_from_slice` variants use it so that no iterator is cloned per NPV evaluation.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{from_cash_flows_and_discount_rate, from_slice_and_discount_rate};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+/// assert_eq!(
+///     from_slice_and_discount_rate(&cash_flows, 0.10),
+///     from_cash_flows_and_discount_rate(cash_flows.iter(), 0.10)
+/// );
+/// ```
+pub fn from_slice_and_discount_rate<T>(cash_flows: &[T], discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    from_cash_flows_and_discount_rate(cash_flows, discount_rate)
 }
 
 #[cfg(test)]
+#[allow(clippy::excessive_precision)]
 mod from_cash_flows_and_discount_rate_tests {
     use crate::present_value::from_cash_flows_and_discount_rate;
     use num::abs;
@@ -149,8 +202,7 @@ mod from_cash_flows_and_discount_rate_tests {
         let discount_rate: f32 = 0.20;
         let precision: f32 = 0.001;
         let expected_value: f32 = 119.2137;
-        let actual_value: f32 =
-            from_cash_flows_and_discount_rate(cash_flows.iter(), &discount_rate);
+        let actual_value: f32 = from_cash_flows_and_discount_rate(cash_flows.iter(), discount_rate);
         assert!(abs(expected_value - actual_value) <= precision);
     }
 
@@ -160,8 +212,785 @@ mod from_cash_flows_and_discount_rate_tests {
         let discount_rate: f32 = 0.30;
         let precision: f32 = 0.001;
         let expected_value: f32 = -419.1275;
-        let actual_value: f32 =
-            from_cash_flows_and_discount_rate(cash_flows.iter(), &discount_rate);
+        let actual_value: f32 = from_cash_flows_and_discount_rate(cash_flows.iter(), discount_rate);
         assert!(abs(expected_value - actual_value) <= precision);
     }
 }
+
+/// When the first of a series of cash flows occurs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Convention {
+    /// The first cash flow is at period 0 and is not discounted, as elsewhere in this crate.
+    PeriodZero,
+    /// The first cash flow is at the end of period 1 and is discounted by one period, as in the `NPV` function of Excel and LibreOffice.
+    Spreadsheet,
+}
+
+/// Converts a series of cash flows and a discount rate into a present value, with the first cash flow at the time given by `convention`.
+///
+/// # Comments
+/// With `Convention::Spreadsheet` the result is that of `from_cash_flows_and_discount_rate` discounted by one more period, so a spreadsheet's `NPV(rate, values)` is matched; a spreadsheet's `NPV(rate, values) + initial` is `PeriodZero` with the initial cash flow first.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{from_cash_flows_with_convention, Convention};
+///
+/// // =NPV(10%, -10000, 3000, 4200, 6800) is 1,188.44
+/// let cash_flows: Vec<f64> = vec![-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+/// let npv: f64 = from_cash_flows_with_convention(cash_flows.iter(), 0.10, Convention::Spreadsheet);
+/// assert!((npv - 1_188.44).abs() < 0.005);
+///
+/// let npv: f64 = from_cash_flows_with_convention(cash_flows.iter(), 0.10, Convention::PeriodZero);
+/// assert!((npv - 1_188.44 * 1.1).abs() < 0.005);
+/// ```
+pub fn from_cash_flows_with_convention<T, I, B>(
+    cash_flows: I,
+    discount_rate: T,
+    convention: Convention,
+) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
+{
+    let npv: T = from_cash_flows_and_discount_rate(cash_flows, discount_rate);
+    match convention {
+        Convention::PeriodZero => npv,
+        Convention::Spreadsheet => npv / (T::one() + discount_rate),
+    }
+}
+
+#[cfg(test)]
+mod from_cash_flows_with_convention_tests {
+    use crate::present_value::{
+        from_cash_flows_and_discount_rate, from_cash_flows_with_convention, Convention,
+    };
+
+    #[test]
+    fn it_matches_a_leading_zero_with_the_spreadsheet_convention() {
+        let cash_flows: Vec<f64> = vec![-500.0, 100.0, 200.0, 300.0];
+        let mut shifted: Vec<f64> = vec![0.0];
+        shifted.extend(cash_flows.iter());
+
+        let spreadsheet: f64 =
+            from_cash_flows_with_convention(cash_flows.iter(), 0.07, Convention::Spreadsheet);
+        let period_zero: f64 = from_cash_flows_and_discount_rate(shifted.iter(), 0.07);
+        assert!((spreadsheet - period_zero).abs() < 0.000_000_1);
+    }
+
+    #[test]
+    fn it_matches_from_cash_flows_and_discount_rate_at_period_zero() {
+        let cash_flows: Vec<f32> = vec![-500.0, 100.0, 2.0, 3.0, 4.0];
+        assert_eq!(
+            from_cash_flows_with_convention(cash_flows.iter(), 0.30, Convention::PeriodZero),
+            from_cash_flows_and_discount_rate(cash_flows.iter(), 0.30)
+        );
+    }
+}
+
+/// Converts a series of cash flows and a discount rate into a present value, where the first period after the cash flow at period 0 is a stub of `first_period_fraction` of a full period, e.g. for a valuation date part way through a year.
+///
+/// # Comments
+/// The cash flow at index 0 is not discounted and the one at index `t` is discounted over `t - 1 + first_period_fraction` periods, so a fraction of one gives the result of `from_cash_flows_and_discount_rate` and a fraction of a half discounts mid-period.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{from_cash_flows_and_discount_rate, from_cash_flows_with_stub_period};
+///
+/// // valued three months into the year, the next cash flow is nine months away
+/// let cash_flows: Vec<f64> = vec![0.0, 110.0, 121.0];
+/// let value: f64 = from_cash_flows_with_stub_period(cash_flows.iter(), 0.10, 0.75);
+/// assert!((value - 200.0 * 1.10_f64.powf(0.25)).abs() < 0.000_001);
+///
+/// let whole_period: f64 = from_cash_flows_with_stub_period(cash_flows.iter(), 0.10, 1.0);
+/// assert!((whole_period - from_cash_flows_and_discount_rate(cash_flows.iter(), 0.10)).abs() < 0.000_001);
+/// ```
+pub fn from_cash_flows_with_stub_period<T, I, B>(
+    cash_flows: I,
+    discount_rate: T,
+    first_period_fraction: T,
+) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
+{
+    let mut cash_flows = cash_flows.into_iter();
+    let first: T = match cash_flows.next() {
+        Some(cash_flow) => *cash_flow.borrow(),
+        None => return T::zero(),
+    };
+    // the rest are discounted over whole periods to the end of the stub, and then over the stub
+    let at_end_of_stub: T = from_cash_flows_and_discount_rate(cash_flows, discount_rate);
+    first + at_end_of_stub / powf(T::one() + discount_rate, first_period_fraction)
+}
+
+#[cfg(test)]
+mod from_cash_flows_with_stub_period_tests {
+    use crate::present_value::{
+        from_cash_flows_and_discount_rate, from_cash_flows_with_stub_period,
+    };
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f64> = vec![];
+        assert_eq!(
+            from_cash_flows_with_stub_period(cash_flows.iter(), 0.10, 0.5),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_does_not_discount_the_first_cash_flow() {
+        let cash_flows: Vec<f32> = vec![-100.0];
+        assert_eq!(
+            from_cash_flows_with_stub_period(cash_flows.iter(), 0.10, 0.5),
+            -100.0
+        );
+    }
+
+    #[test]
+    fn it_discounts_each_later_cash_flow_over_the_stub() {
+        let cash_flows: Vec<f64> = vec![-100.0, 50.0, 60.0, 70.0];
+        let fraction: f64 = 0.4;
+        let expected: f64 = cash_flows
+            .iter()
+            .enumerate()
+            .map(|(period, cash_flow)| match period {
+                0 => *cash_flow,
+                _ => cash_flow / 1.08_f64.powf(period as f64 - 1.0 + fraction),
+            })
+            .sum();
+        let value: f64 = from_cash_flows_with_stub_period(cash_flows.iter(), 0.08, fraction);
+
+        assert!((value - expected).abs() < 0.000_000_001);
+        assert!(value > from_cash_flows_and_discount_rate(cash_flows.iter(), 0.08));
+    }
+}
+
+#[allow(dead_code)]
+/// Calculates the derivative of the NPV of a series of cash flows with respect to the discount rate.
+///
+/// # Comments
+/// The derivative is `sum(-t * cash_flow_t * (1 + discount_rate)^(-t - 1))`; dividing its negative by the NPV gives the modified duration of the series.
+///
+/// # Example with f64
+/// Assumptions
+/// - Cash flows: EUR -100.00 in period 0 and EUR 121.00 in period 2
+/// - Discount rate: 10.00%
+/// ```
+/// use time_value::present_value::{from_cash_flows_and_discount_rate as npv, npv_derivative};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 0.0, 121.0];
+/// let derivative: f64 = npv_derivative(cash_flows.iter(), 0.10);
+/// assert!((derivative - -2.0 * 121.0 / 1.331).abs() < 0.000_001);
+///
+/// let bump: f64 = 0.000_001;
+/// let finite_difference: f64 =
+///     (npv(cash_flows.iter(), 0.10 + bump) - npv(cash_flows.iter(), 0.10 - bump)) / (2.0 * bump);
+/// assert!((derivative - finite_difference).abs() < 0.000_01);
+/// ```
+pub fn npv_derivative<T, I, B>(cash_flows: I, discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
+{
+    let discount: T = T::one() + discount_rate;
+    cash_flows
+        .into_iter()
+        .map(|cash_flow| *cash_flow.borrow())
+        .enumerate()
+        .skip(1)
+        .map(|(period, cash_flow)| {
+            -T::from(period).unwrap() * cash_flow * powi(discount, -(period as i32) - 1)
+        })
+        .sum()
+}
+
+/// Calculates the derivative of the NPV like `npv_derivative` for a slice of cash flows, indexing the slice directly.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{npv_derivative, npv_derivative_from_slice};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+/// assert_eq!(
+///     npv_derivative_from_slice(&cash_flows, 0.10),
+///     npv_derivative(cash_flows.iter(), 0.10)
+/// );
+/// ```
+pub fn npv_derivative_from_slice<T>(cash_flows: &[T], discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let discount: T = T::one() + discount_rate;
+    (1..cash_flows.len())
+        .map(|period| {
+            -T::from(period).unwrap() * cash_flows[period] * powi(discount, -(period as i32) - 1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod npv_derivative_tests {
+    use crate::present_value::{npv_derivative, npv_derivative_from_slice};
+
+    #[test]
+    fn it_works_with_a_single_cash_flow() {
+        let cash_flows: Vec<f32> = vec![-100.0];
+        assert_eq!(npv_derivative(cash_flows.iter(), 0.05), 0.0);
+    }
+
+    #[test]
+    fn it_is_negative_for_inflows() {
+        let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+        assert!(npv_derivative(cash_flows.iter(), 0.05) < 0.0);
+    }
+
+    #[test]
+    fn it_works_with_owned_cash_flows() {
+        let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+        assert_eq!(
+            npv_derivative(cash_flows.clone(), 0.05),
+            npv_derivative(cash_flows.iter(), 0.05)
+        );
+    }
+
+    #[test]
+    fn it_matches_the_slice_variant() {
+        let no_cash_flows: Vec<f32> = vec![];
+        let cash_flows: Vec<f32> = vec![-100.0, 30.0, 40.0, 50.0];
+        assert_eq!(npv_derivative_from_slice(&no_cash_flows, 0.05), 0.0);
+        assert_eq!(
+            npv_derivative_from_slice(&cash_flows, 0.05),
+            npv_derivative(cash_flows.iter(), 0.05)
+        );
+    }
+}
+
+#[allow(dead_code)]
+/// Calculates the second derivative of the NPV of a series of cash flows with respect to the discount rate.
+///
+/// # Comments
+/// The second derivative is `sum(t * (t + 1) * cash_flow_t * (1 + discount_rate)^(-t - 2))`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{npv_derivative, npv_second_derivative};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+/// let bump: f64 = 0.000_001;
+/// let finite_difference: f64 = (npv_derivative(cash_flows.iter(), 0.10 + bump)
+///     - npv_derivative(cash_flows.iter(), 0.10 - bump))
+///     / (2.0 * bump);
+/// assert!((npv_second_derivative(cash_flows.iter(), 0.10) - finite_difference).abs() < 0.000_1);
+/// ```
+pub fn npv_second_derivative<T, I, B>(cash_flows: I, discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
+{
+    let discount: T = T::one() + discount_rate;
+    cash_flows
+        .into_iter()
+        .map(|cash_flow| *cash_flow.borrow())
+        .enumerate()
+        .skip(1)
+        .map(|(period, cash_flow)| {
+            let t: T = T::from(period).unwrap();
+            t * (t + T::one()) * cash_flow * powi(discount, -(period as i32) - 2)
+        })
+        .sum()
+}
+
+/// Calculates the second derivative of the NPV like `npv_second_derivative` for a slice of cash flows, indexing the slice directly.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{npv_second_derivative, npv_second_derivative_from_slice};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+/// assert_eq!(
+///     npv_second_derivative_from_slice(&cash_flows, 0.10),
+///     npv_second_derivative(cash_flows.iter(), 0.10)
+/// );
+/// ```
+pub fn npv_second_derivative_from_slice<T>(cash_flows: &[T], discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let discount: T = T::one() + discount_rate;
+    (1..cash_flows.len())
+        .map(|period| {
+            let t: T = T::from(period).unwrap();
+            t * (t + T::one()) * cash_flows[period] * powi(discount, -(period as i32) - 2)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod npv_second_derivative_tests {
+    use crate::present_value::{npv_second_derivative, npv_second_derivative_from_slice};
+
+    #[test]
+    fn it_works_with_a_single_cash_flow() {
+        let cash_flows: Vec<f32> = vec![-100.0];
+        assert_eq!(npv_second_derivative(cash_flows.iter(), 0.05), 0.0);
+    }
+
+    #[test]
+    fn it_is_positive_for_inflows() {
+        let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+        assert!(0.0 < npv_second_derivative(cash_flows.iter(), 0.05));
+    }
+
+    #[test]
+    fn it_matches_the_slice_variant() {
+        let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+        assert_eq!(
+            npv_second_derivative_from_slice(&cash_flows, 0.05),
+            npv_second_derivative(cash_flows.iter(), 0.05)
+        );
+    }
+}
+
+#[allow(dead_code)]
+/// Converts a series of sparse cash flows, given as `(period, cash flow)` pairs, and a discount rate into a present value.
+///
+/// # Comments
+/// Periods without a cash flow are skipped, so e.g. a cash flow in year 0 and year 30 takes two pairs rather than a 31 element series of mostly zeros.
+///
+/// # Example with f64
+/// Assumptions
+/// - Cash flows: EUR -100.00 in period 0 and EUR 121.00 in period 2
+/// - Discount rate: 10.00%
+/// ```
+/// use time_value::present_value::from_sparse_cash_flows_and_discount_rate;
+/// use num::abs;
+///
+/// let cash_flows: Vec<(u32, f64)> = vec![(0, -100.0), (2, 121.0)];
+/// let discount_rate: f64 = 0.10;
+/// let value: f64 = from_sparse_cash_flows_and_discount_rate(cash_flows.iter(), discount_rate);
+/// assert!(abs(value) < 0.000_001);
+/// ```
+pub fn from_sparse_cash_flows_and_discount_rate<T, I, B>(cash_flows: I, discount_rate: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<(u32, T)>,
+{
+    cash_flows
+        .into_iter()
+        .map(|pair| {
+            let (period, cash_flow): (u32, T) = *pair.borrow();
+            crate::present_value::present_value(cash_flow, period as usize, discount_rate)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod from_sparse_cash_flows_and_discount_rate_tests {
+    use crate::present_value::{
+        from_cash_flows_and_discount_rate, from_sparse_cash_flows_and_discount_rate,
+    };
+
+    #[test]
+    fn it_matches_the_dense_present_value() {
+        let dense: Vec<f64> = vec![-500.0, 0.0, 0.0, 100.0, 0.0, 0.0, 0.0, 400.0, 0.0];
+        let sparse: Vec<(u32, f64)> = vec![(0, -500.0), (3, 100.0), (7, 400.0)];
+        let discount_rate: f64 = 0.07;
+        assert!(
+            (from_cash_flows_and_discount_rate(dense.iter(), discount_rate)
+                - from_sparse_cash_flows_and_discount_rate(sparse.iter(), discount_rate))
+            .abs()
+                < 0.000_000_001
+        );
+    }
+
+    #[test]
+    fn it_works_with_unordered_and_repeated_periods() {
+        let sparse: Vec<(u32, f32)> = vec![(2, 60.5), (0, -100.0), (2, 60.5)];
+        let discount_rate: f32 = 0.10;
+        assert!(
+            from_sparse_cash_flows_and_discount_rate(sparse.iter(), discount_rate).abs() < 0.001
+        );
+    }
+}
+
+#[allow(dead_code)]
+/// Converts a series of cash flows into a present value, treating the last cash flow as growing at `growth` in perpetuity.
+///
+/// # Comments
+/// The last cash flow `cash_flow_n` is replaced by itself plus its Gordon growth terminal value `cash_flow_n * (1 + growth) / (discount_rate - growth)`.
+/// The terminal value only converges for discount rates above `growth`; at or below it the present value is infinite with the sign of the last cash flow, which keeps the NPV monotone for the IRR solvers.
+///
+/// # Example with f64
+/// Assumptions
+/// - Cash flows: EUR -100.00 in period 0 and EUR 5.00 in period 1, growing at 5.00% in perpetuity
+/// - Discount rate: 10.00%
+/// ```
+/// use time_value::present_value::from_cash_flows_with_terminal_growth;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 5.0];
+/// let value: f64 = from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.05, 0.10);
+/// // the terminal value in period 1 is 5.00 * 1.05 / 0.05 = 105.00
+/// assert!((value - (-100.0 + (5.0 + 105.0) / 1.10)).abs() < 0.000_001);
+/// ```
+pub fn from_cash_flows_with_terminal_growth<T, I, B>(
+    cash_flows: I,
+    growth: T,
+    discount_rate: T,
+) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    let last: Option<(usize, T)> = cash_flows
+        .clone()
+        .into_iter()
+        .map(|cash_flow| *cash_flow.borrow())
+        .enumerate()
+        .last();
+    let terminal_value: T = match last {
+        Some((_, cash_flow)) if cash_flow.is_zero() => T::zero(),
+        Some((period, cash_flow)) => {
+            if discount_rate <= growth {
+                return T::infinity() * cash_flow.signum();
+            }
+            let terminal_value: T = cash_flow * (T::one() + growth) / (discount_rate - growth);
+            present_value(terminal_value, period, discount_rate)
+        }
+        None => T::zero(),
+    };
+    from_cash_flows_and_discount_rate(cash_flows, discount_rate) + terminal_value
+}
+
+/// Converts a series of cash flows into a present value, treating the last cash flow as growing at `growth` in perpetuity, where the first period is a stub of `first_period_fraction` of a full period.
+///
+/// # Comments
+/// This is `from_cash_flows_with_terminal_growth` with the discounting of `from_cash_flows_with_stub_period`, e.g. for a DCF valued part way through a year.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{
+///     from_cash_flows_with_terminal_growth, from_cash_flows_with_terminal_growth_and_stub_period,
+/// };
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 5.0, 6.0];
+/// let value: f64 = from_cash_flows_with_terminal_growth_and_stub_period(cash_flows.iter(), 0.02, 0.10, 0.5);
+/// let whole_period: f64 = from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.02, 0.10);
+/// assert!((value + 100.0 - (whole_period + 100.0) * 1.10_f64.powf(0.5)).abs() < 0.000_001);
+/// ```
+pub fn from_cash_flows_with_terminal_growth_and_stub_period<T, I, B>(
+    cash_flows: I,
+    growth: T,
+    discount_rate: T,
+    first_period_fraction: T,
+) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B> + Clone,
+    B: Borrow<T>,
+{
+    let first: T = match cash_flows.clone().into_iter().next() {
+        Some(cash_flow) => *cash_flow.borrow(),
+        None => return T::zero(),
+    };
+    let rest = cash_flows
+        .into_iter()
+        .skip(1)
+        .map(|cash_flow| *cash_flow.borrow());
+    let rest: Vec<T> = rest.collect();
+    let at_end_of_stub: T =
+        from_cash_flows_with_terminal_growth(rest.iter(), growth, discount_rate);
+    first + at_end_of_stub / powf(T::one() + discount_rate, first_period_fraction)
+}
+
+#[cfg(test)]
+mod from_cash_flows_with_terminal_growth_tests {
+    use crate::present_value::from_cash_flows_with_terminal_growth;
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f64> = vec![];
+        assert_eq!(
+            from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.02, 0.10),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_values_a_growing_perpetuity() {
+        // a perpetuity starting at 10 in period 1 and growing at 2% is worth 10 / (0.10 - 0.02) = 125 at 10%
+        let cash_flows: Vec<f64> = vec![0.0, 10.0];
+        let value: f64 = from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.02, 0.10);
+        assert!((value - 125.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_diverges_at_or_below_the_growth_rate() {
+        let cash_flows: Vec<f32> = vec![-100.0, 10.0];
+        assert_eq!(
+            from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.05, 0.05),
+            f32::INFINITY
+        );
+        assert_eq!(
+            from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.05, 0.01),
+            f32::INFINITY
+        );
+    }
+}
+
+#[cfg(test)]
+mod from_cash_flows_with_terminal_growth_and_stub_period_tests {
+    use crate::present_value::{
+        from_cash_flows_with_terminal_growth, from_cash_flows_with_terminal_growth_and_stub_period,
+    };
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f64> = vec![];
+        assert_eq!(
+            from_cash_flows_with_terminal_growth_and_stub_period(
+                cash_flows.iter(),
+                0.02,
+                0.10,
+                0.5
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn it_matches_whole_periods_at_a_fraction_of_one() {
+        let cash_flows: Vec<f64> = vec![-100.0, 5.0, 6.0];
+        assert!(
+            (from_cash_flows_with_terminal_growth_and_stub_period(
+                cash_flows.iter(),
+                0.02,
+                0.10,
+                1.0
+            ) - from_cash_flows_with_terminal_growth(cash_flows.iter(), 0.02, 0.10))
+            .abs()
+                < 0.000_000_001
+        );
+    }
+
+    #[test]
+    fn it_values_a_growing_perpetuity_mid_period() {
+        // a perpetuity paying 10 at the end of the stub and growing at 2% is worth 125 one period before its first payment
+        let cash_flows: Vec<f64> = vec![0.0, 10.0];
+        let value: f64 = from_cash_flows_with_terminal_growth_and_stub_period(
+            cash_flows.iter(),
+            0.02,
+            0.10,
+            0.5,
+        );
+        assert!((value - 125.0 * 1.10_f64.powf(0.5)).abs() < 0.000_001);
+    }
+}
+
+#[allow(dead_code)]
+/// Calculates the discount factors for periods `0..periods` at a single discount rate.
+///
+/// # Comments
+/// The factors are exactly those used by the other functions in this module, so other systems (SQL, spreadsheets) applying them discount identically.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::discount_factors;
+/// use num::abs;
+///
+/// let factors: Vec<f64> = discount_factors(0.10, 3);
+/// assert_eq!(factors.len(), 3);
+/// assert_eq!(factors[0], 1.0);
+/// assert!(abs(factors[2] - 0.8264) < 0.0001);
+/// ```
+pub fn discount_factors<T>(discount_rate: T, periods: usize) -> Vec<T>
+where
+    T: Float + Product<T>,
+{
+    (0..periods)
+        .map(|period| crate::present_value::present_value(T::one(), period, discount_rate))
+        .collect()
+}
+
+#[allow(dead_code)]
+/// Calculates a discount factor for each period from a curve of spot rates, where the rate at index `t` applies to period `t`.
+///
+/// # Example with f32
+/// ```
+/// use time_value::present_value::discount_factors_from_spot_rates;
+/// use num::abs;
+///
+/// let spot_rates: Vec<f32> = vec![0.01, 0.02, 0.03];
+/// let factors: Vec<f32> = discount_factors_from_spot_rates(spot_rates.iter());
+/// assert_eq!(factors[0], 1.0);
+/// assert!(abs(factors[1] - 1.0 / 1.02) < 0.0001);
+/// assert!(abs(factors[2] - 1.0 / 1.0609) < 0.0001);
+/// ```
+pub fn discount_factors_from_spot_rates<T>(spot_rates: Iter<T>) -> Vec<T>
+where
+    T: Float + Product<T>,
+{
+    spot_rates
+        .enumerate()
+        .map(|(period, spot_rate)| {
+            crate::present_value::present_value(T::one(), period, *spot_rate)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod discount_factors_tests {
+    use crate::present_value::{
+        discount_factors, discount_factors_from_spot_rates, from_cash_flows_and_discount_rate,
+    };
+
+    #[test]
+    fn it_works_with_no_periods() {
+        assert!(discount_factors(0.10_f32, 0).is_empty());
+    }
+
+    #[test]
+    fn it_reproduces_the_npv() {
+        let cash_flows: Vec<f64> = vec![-500.0, 100.0, 2.0, 3.0, 4.0];
+        let factors: Vec<f64> = discount_factors(0.30, cash_flows.len());
+        let npv: f64 = cash_flows
+            .iter()
+            .zip(factors.iter())
+            .map(|(cash_flow, factor)| cash_flow * factor)
+            .sum();
+        assert_eq!(
+            npv,
+            from_cash_flows_and_discount_rate(cash_flows.iter(), 0.30)
+        );
+    }
+
+    #[test]
+    fn it_matches_a_flat_curve() {
+        let spot_rates: Vec<f64> = vec![0.07; 12];
+        assert_eq!(
+            discount_factors_from_spot_rates(spot_rates.iter()),
+            discount_factors(0.07, spot_rates.len())
+        );
+    }
+}
+
+#[allow(dead_code)]
+/// Calculates the NPV of a series of cash flows at `steps + 1` evenly spaced rates from `rate_min` to `rate_max`, as `(rate, npv)` pairs.
+///
+/// # Comments
+/// Trailing zero cash flows are dropped once, and each NPV is then evaluated with Horner's rule in the discount `1 / (1 + rate)`, so no rate raises the discount to every period from scratch.
+/// With no steps only `rate_min` is evaluated.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{from_cash_flows_and_discount_rate, npv_profile};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// let profile: Vec<(f64, f64)> = npv_profile(cash_flows.iter(), 0.0, 0.3, 3);
+/// assert_eq!(profile.len(), 4);
+/// for (rate, npv) in profile.iter() {
+///     assert!((npv - from_cash_flows_and_discount_rate(cash_flows.iter(), *rate)).abs() < 0.000_000_001);
+/// }
+/// // the NPV crosses zero at the IRRs of 10% and 20%
+/// assert!(profile[1].1.abs() < 0.000_000_001);
+/// assert!(profile[2].1.abs() < 0.000_000_001);
+/// ```
+pub fn npv_profile<T, I, B>(cash_flows: I, rate_min: T, rate_max: T, steps: usize) -> Vec<(T, T)>
+where
+    T: Float + Product<T> + Sum<T>,
+    I: IntoIterator<Item = B>,
+    B: Borrow<T>,
+{
+    let cash_flows: Vec<T> = cash_flows
+        .into_iter()
+        .map(|cash_flow| *cash_flow.borrow())
+        .collect();
+    npv_profile_from_slice(&cash_flows, rate_min, rate_max, steps)
+}
+
+/// Calculates the NPV profile like `npv_profile` for a slice of cash flows, which is evaluated in place rather than collected first.
+///
+/// # Example with f64
+/// ```
+/// use time_value::present_value::{npv_profile, npv_profile_from_slice};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// assert_eq!(
+///     npv_profile_from_slice(&cash_flows, 0.0, 0.3, 3),
+///     npv_profile(cash_flows.iter(), 0.0, 0.3, 3)
+/// );
+/// ```
+pub fn npv_profile_from_slice<T>(
+    cash_flows: &[T],
+    rate_min: T,
+    rate_max: T,
+    steps: usize,
+) -> Vec<(T, T)>
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let length: usize = cash_flows
+        .iter()
+        .rposition(|cash_flow| !cash_flow.is_zero())
+        .map_or(0, |last| last + 1);
+    let cash_flows: &[T] = &cash_flows[..length];
+
+    let step: T = if steps == 0 {
+        T::zero()
+    } else {
+        (rate_max - rate_min) / T::from(steps).unwrap()
+    };
+    (0..=steps)
+        .map(|index| {
+            let rate: T = rate_min + T::from(index).unwrap() * step;
+            let discount: T = T::one() / (T::one() + rate);
+            let npv: T = cash_flows
+                .iter()
+                .rev()
+                .fold(T::zero(), |acc, cash_flow| acc * discount + *cash_flow);
+            (rate, npv)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod npv_profile_tests {
+    use crate::present_value::{from_cash_flows_and_discount_rate, npv_profile};
+
+    #[test]
+    fn it_works_with_no_steps() {
+        let cash_flows: Vec<f32> = vec![-100.0, 110.0];
+        let profile: Vec<(f32, f32)> = npv_profile(cash_flows.iter(), 0.10, 0.50, 0);
+        assert_eq!(profile.len(), 1);
+        assert_eq!(profile[0].0, 0.10);
+    }
+
+    #[test]
+    fn it_works_with_trailing_zeros() {
+        let cash_flows: Vec<f64> = vec![-500.0, 100.0, 2.0, 3.0, 4.0, 0.0, 0.0];
+        let profile: Vec<(f64, f64)> = npv_profile(cash_flows.iter(), -0.5, 1.0, 15);
+        assert_eq!(profile.len(), 16);
+        assert_eq!(profile[15].0, 1.0);
+        for (rate, npv) in profile.iter() {
+            let expected: f64 = from_cash_flows_and_discount_rate(cash_flows.iter(), *rate);
+            assert!((npv - expected).abs() < 0.000_000_001);
+        }
+    }
+
+    #[test]
+    fn it_works_with_a_single_pass_iterator() {
+        let cash_flows: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let doubled = cash_flows.iter().map(|cash_flow| 2.0 * cash_flow);
+        let profile: Vec<(f64, f64)> = npv_profile(doubled, 0.0, 0.2, 2);
+        for (rate, npv) in profile.iter() {
+            let expected: f64 = 2.0 * from_cash_flows_and_discount_rate(cash_flows.iter(), *rate);
+            assert!((npv - expected).abs() < 0.000_000_001);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Invariant checkers for time value calculations.
+//!
+//! Each checker returns `true` when the invariant holds, so the same checks can be used in this
+//! crate's tests and in downstream tests of code built on top of it.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::future_value::from_pv_and_expected_rates as fv;
+use crate::present_value::from_cash_flows_and_discount_rate as npv;
+use crate::present_value::present_value as pv;
+
+/// Checks that the NPV of a series of cash flows at a candidate IRR is within `tolerance` of zero.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection as irr;
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::properties::npv_at_irr_is_zero;
+///
+/// let cash_flows: Vec<f32> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let calculated_irr: Irr<f32> = irr(cash_flows.iter(), 0.05, 0.18, 100);
+/// assert!(npv_at_irr_is_zero(cash_flows.iter(), calculated_irr.irr(), 0.001));
+/// assert!(!npv_at_irr_is_zero(cash_flows.iter(), 0.05, 0.001));
+/// ```
+pub fn npv_at_irr_is_zero<T>(cash_flows: Iter<T>, irr: T, tolerance: T) -> bool
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    npv(cash_flows, irr).abs() <= tolerance
+}
+
+/// Checks that the NPV of a series of cash flows does not increase as the discount rate increases.
+///
+/// The rates are checked in the order given, so they should be sorted in ascending order and be greater than -1.
+///
+/// # Comments
+/// This holds for conventional cash flows, i.e. an outflow in period 0 followed only by inflows; it need not hold otherwise.
+///
+/// # Example with f64
+/// ```
+/// use time_value::properties::npv_is_monotone_in_rate;
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 30.0, 40.0, 50.0];
+/// let rates: Vec<f64> = vec![-0.5, 0.0, 0.05, 0.10, 1.0, 10.0];
+/// assert!(npv_is_monotone_in_rate(cash_flows.iter(), rates.iter()));
+///
+/// let unconventional: Vec<f64> = vec![-100.0, 230.0, -132.0];
+/// assert!(!npv_is_monotone_in_rate(unconventional.iter(), rates.iter()));
+/// ```
+pub fn npv_is_monotone_in_rate<T>(cash_flows: Iter<T>, rates: Iter<T>) -> bool
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let npvs: Vec<T> = rates.map(|rate| npv(cash_flows.clone(), *rate)).collect();
+    npvs.windows(2).all(|pair| pair[1] <= pair[0])
+}
+
+/// Checks that discounting a cash flow to the present and compounding it back at the same rate returns the original cash flow, within `tolerance`.
+///
+/// # Example with f32
+/// ```
+/// use time_value::properties::pv_fv_round_trip;
+///
+/// let cash_flow: f32 = 1234.56;
+/// let period: usize = 10;
+/// let rate: f32 = 0.07;
+/// assert!(pv_fv_round_trip(cash_flow, period, rate, 0.001));
+/// ```
+pub fn pv_fv_round_trip<T>(cash_flow: T, period: usize, rate: T, tolerance: T) -> bool
+where
+    T: Float + Product<T>,
+{
+    let rates: Vec<T> = vec![rate; period];
+    let round_trip: T = fv(pv(cash_flow, period, rate), rates.iter());
+    (round_trip - cash_flow).abs() <= tolerance
+}
+
+#[cfg(test)]
+mod npv_is_monotone_in_rate_tests {
+    use crate::properties::npv_is_monotone_in_rate;
+
+    #[test]
+    fn it_works_with_conventional_cash_flows() {
+        let cash_flows: Vec<f32> = vec![-100.0, 0.0, 20.0, 20.0, 20.0, 20.0, 20.0, 20.0];
+        let rates: Vec<f32> = (0..50).map(|step| -0.9 + step as f32 * 0.1).collect();
+        assert!(npv_is_monotone_in_rate(cash_flows.iter(), rates.iter()));
+    }
+
+    #[test]
+    fn it_works_with_no_rates() {
+        let cash_flows: Vec<f32> = vec![-100.0, 20.0];
+        let rates: Vec<f32> = vec![];
+        assert!(npv_is_monotone_in_rate(cash_flows.iter(), rates.iter()));
+    }
+}
+
+#[cfg(test)]
+mod pv_fv_round_trip_tests {
+    use crate::properties::pv_fv_round_trip;
+
+    #[test]
+    fn it_works_at_zero() {
+        let cash_flow: f64 = -42.0;
+        assert!(pv_fv_round_trip(cash_flow, 0, 0.25, 0.0));
+    }
+
+    #[test]
+    fn it_works_with_many_periods() {
+        let cash_flows: Vec<f64> = vec![0.0, 1.0, -1.0, 1234.56789, -1234.56789];
+        for cash_flow in cash_flows.iter() {
+            for period in 0..40 {
+                assert!(pv_fv_round_trip(*cash_flow, period, 0.08, 0.000_001));
+            }
+        }
+    }
+}
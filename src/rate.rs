@@ -0,0 +1,627 @@
+//! Wrappers for expressing decimal rates as percentages or basis points.
+//!
+//! Rates are decimals throughout the crate i.e. `0.125` is 12.5%; these types make the unit explicit when presenting them.
+//! `Rate` pairs a nominal annual rate with how often it compounds, and with the `parse` feature can be read from strings such as `"5.25% s.a."`.
+
+use num::Float;
+use std::fmt::{Display, Error, Formatter};
+
+#[cfg(feature = "parse")]
+use std::str::FromStr;
+
+use crate::power::powi;
+use crate::tenor::Tenor;
+
+/// A rate expressed in percent.
+///
+/// # Example with f64
+/// ```
+/// use time_value::rate::{BasisPoints, Percent};
+///
+/// let rate: Percent<f64> = Percent::from_decimal(0.125);
+/// assert_eq!(rate.value(), 12.5);
+/// assert_eq!(rate.to_decimal(), 0.125);
+/// assert_eq!(format!("{}", rate), "12.5%");
+/// assert_eq!(format!("{:.2}", rate), "12.50%");
+/// assert_eq!(BasisPoints::from(rate).value(), 1250.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Percent<T>(T)
+where
+    T: Float;
+
+impl<T> Percent<T>
+where
+    T: Float,
+{
+    pub fn new(percent: T) -> Percent<T> {
+        Percent(percent)
+    }
+
+    pub fn from_decimal(rate: T) -> Percent<T> {
+        Percent(rate * T::from(100.0).unwrap())
+    }
+
+    pub fn value(&self) -> T {
+        self.0
+    }
+
+    pub fn to_decimal(&self) -> T {
+        self.0 / T::from(100.0).unwrap()
+    }
+}
+
+impl<T> From<BasisPoints<T>> for Percent<T>
+where
+    T: Float,
+{
+    fn from(basis_points: BasisPoints<T>) -> Percent<T> {
+        Percent(basis_points.value() / T::from(100.0).unwrap())
+    }
+}
+
+impl<T> Display for Percent<T>
+where
+    T: Float + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}%", precision, self.value()),
+            None => write!(f, "{}%", self.value()),
+        }
+    }
+}
+
+/// A rate expressed in basis points, where 1 basis point is 0.01%.
+///
+/// # Example with f32
+/// ```
+/// use time_value::rate::{BasisPoints, Percent};
+///
+/// let spread: BasisPoints<f32> = BasisPoints::from_decimal(0.0025);
+/// assert_eq!(spread.value(), 25.0);
+/// assert_eq!(format!("{}", spread), "25 bps");
+/// assert_eq!(Percent::from(spread).value(), 0.25);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct BasisPoints<T>(T)
+where
+    T: Float;
+
+impl<T> BasisPoints<T>
+where
+    T: Float,
+{
+    pub fn new(basis_points: T) -> BasisPoints<T> {
+        BasisPoints(basis_points)
+    }
+
+    pub fn from_decimal(rate: T) -> BasisPoints<T> {
+        BasisPoints(rate * T::from(10_000.0).unwrap())
+    }
+
+    pub fn value(&self) -> T {
+        self.0
+    }
+
+    pub fn to_decimal(&self) -> T {
+        self.0 / T::from(10_000.0).unwrap()
+    }
+}
+
+impl<T> From<Percent<T>> for BasisPoints<T>
+where
+    T: Float,
+{
+    fn from(percent: Percent<T>) -> BasisPoints<T> {
+        BasisPoints(percent.value() * T::from(100.0).unwrap())
+    }
+}
+
+impl<T> Display for BasisPoints<T>
+where
+    T: Float + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} bps", precision, self.value()),
+            None => write!(f, "{} bps", self.value()),
+        }
+    }
+}
+
+/// How often a nominal rate compounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Frequency {
+    Annual,
+    SemiAnnual,
+    Quarterly,
+    Monthly,
+    Weekly,
+    Daily,
+    Continuous,
+}
+
+impl Frequency {
+    /// The number of compounding periods in a year, which is `None` for continuous compounding.
+    pub fn periods_per_year(&self) -> Option<u32> {
+        match self {
+            Frequency::Annual => Some(1),
+            Frequency::SemiAnnual => Some(2),
+            Frequency::Quarterly => Some(4),
+            Frequency::Monthly => Some(12),
+            Frequency::Weekly => Some(52),
+            Frequency::Daily => Some(365),
+            Frequency::Continuous => None,
+        }
+    }
+
+    /// The length of a compounding period, which is `None` for continuous compounding.
+    pub fn tenor(&self) -> Option<Tenor> {
+        match self {
+            Frequency::Annual => Some(Tenor::from_years(1)),
+            Frequency::SemiAnnual => Some(Tenor::from_months(6)),
+            Frequency::Quarterly => Some(Tenor::from_months(3)),
+            Frequency::Monthly => Some(Tenor::from_months(1)),
+            Frequency::Weekly => Some(Tenor::from_weeks(1)),
+            Frequency::Daily => Some(Tenor::from_days(1)),
+            Frequency::Continuous => None,
+        }
+    }
+
+    /// The number of periods of this frequency in one period of `coarser`, e.g. 3 months in a quarter, if it is a whole number.
+    ///
+    /// # Comments
+    /// Periods are counted from `periods_per_year`, so there are 52 weeks and 365 days in a year, but a month is not a whole number of weeks or days.
+    ///
+    /// # Example
+    /// ```
+    /// use time_value::rate::{Frequency, FrequencyError};
+    ///
+    /// assert_eq!(Frequency::Monthly.periods_in(Frequency::Quarterly), Ok(3));
+    /// assert_eq!(Frequency::Weekly.periods_in(Frequency::Annual), Ok(52));
+    /// assert_eq!(
+    ///     Frequency::Weekly.periods_in(Frequency::Monthly),
+    ///     Err(FrequencyError::NotAMultiple(Frequency::Weekly, Frequency::Monthly))
+    /// );
+    /// ```
+    pub fn periods_in(&self, coarser: Frequency) -> Result<u32, FrequencyError> {
+        match (self.periods_per_year(), coarser.periods_per_year()) {
+            (Some(finer), Some(coarse)) if finer % coarse == 0 => Ok(finer / coarse),
+            (Some(_), Some(_)) => Err(FrequencyError::NotAMultiple(*self, coarser)),
+            _ => Err(FrequencyError::Continuous),
+        }
+    }
+}
+
+/// The reason a conversion between frequencies would not be exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrequencyError {
+    /// Continuous compounding has no periods to convert.
+    Continuous,
+    /// A period of the second frequency is not a whole number of periods of the first.
+    NotAMultiple(Frequency, Frequency),
+    /// A series ends with this many periods, fewer than make up a whole period of the coarser frequency.
+    PartialPeriod(usize),
+}
+
+impl Display for FrequencyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            FrequencyError::Continuous => write!(f, "continuous compounding has no periods"),
+            FrequencyError::NotAMultiple(finer, coarser) => write!(
+                f,
+                "a {} period is not a whole number of {} periods",
+                coarser, finer
+            ),
+            FrequencyError::PartialPeriod(periods) => write!(
+                f,
+                "the series ends with {} periods short of a whole period",
+                periods
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FrequencyError {}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let name: &str = match self {
+            Frequency::Annual => "annual",
+            Frequency::SemiAnnual => "semi-annual",
+            Frequency::Quarterly => "quarterly",
+            Frequency::Monthly => "monthly",
+            Frequency::Weekly => "weekly",
+            Frequency::Daily => "daily",
+            Frequency::Continuous => "continuous",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A nominal annual rate, as a decimal, and how often it compounds.
+///
+/// # Example with f64
+/// ```
+/// use time_value::rate::{Frequency, Rate};
+///
+/// let rate: Rate<f64> = Rate::new(0.0525, Frequency::SemiAnnual);
+/// assert!((rate.effective_annual() - 0.053_189).abs() < 0.000_001);
+/// assert_eq!(format!("{}", rate), "5.25% semi-annual");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rate<T>
+where
+    T: Float,
+{
+    rate: T,
+    frequency: Frequency,
+}
+
+impl<T> Rate<T>
+where
+    T: Float,
+{
+    pub fn new(rate: T, frequency: Frequency) -> Rate<T> {
+        Rate { rate, frequency }
+    }
+
+    pub fn rate(&self) -> T {
+        self.rate
+    }
+
+    pub fn frequency(&self) -> Frequency {
+        self.frequency
+    }
+
+    /// The annual rate with the same growth compounded once a year, i.e. `(1 + rate / m)^m - 1` for `m` periods per year, or `e^rate - 1` for continuous compounding.
+    pub fn effective_annual(&self) -> T {
+        match self.frequency.periods_per_year() {
+            Some(periods) => {
+                let periods: T = T::from(periods).unwrap();
+                powi(T::one() + self.rate / periods, periods.to_i32().unwrap()) - T::one()
+            }
+            None => self.rate.exp_m1(),
+        }
+    }
+
+    /// The nominal rate compounding at `frequency` with the same growth, so the conversion is exact rather than e.g. dividing an annual rate by 12.
+    ///
+    /// # Example with f64
+    /// ```
+    /// use time_value::rate::{Frequency, Rate};
+    ///
+    /// let annual: Rate<f64> = Rate::new(0.12, Frequency::Annual);
+    /// let monthly: Rate<f64> = annual.to_frequency(Frequency::Monthly);
+    /// assert!((monthly.rate() - 0.113_866).abs() < 0.000_001);
+    /// assert!((monthly.effective_annual() - 0.12).abs() < 1e-12);
+    /// ```
+    pub fn to_frequency(&self, frequency: Frequency) -> Rate<T> {
+        let log_growth: T = match self.frequency.periods_per_year() {
+            Some(periods) => {
+                let periods: T = T::from(periods).unwrap();
+                periods * (self.rate / periods).ln_1p()
+            }
+            None => self.rate,
+        };
+        let rate: T = match frequency.periods_per_year() {
+            Some(periods) => {
+                let periods: T = T::from(periods).unwrap();
+                periods * (log_growth / periods).exp_m1()
+            }
+            None => log_growth,
+        };
+        Rate::new(rate, frequency)
+    }
+}
+
+impl<T> Display for Rate<T>
+where
+    T: Float + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let percent: Percent<T> = Percent::from_decimal(self.rate);
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {}", precision, percent, self.frequency),
+            None => write!(f, "{} {}", percent, self.frequency),
+        }
+    }
+}
+
+/// The reason a string is not a frequency: it is not one of the names `Frequency::from_str` accepts.
+#[cfg(feature = "parse")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFrequencyError {
+    input: String,
+}
+
+#[cfg(feature = "parse")]
+impl ParseFrequencyError {
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Display for ParseFrequencyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "unknown frequency '{}', expected annual, semi-annual, quarterly, monthly, weekly, daily or continuous",
+            self.input
+        )
+    }
+}
+
+#[cfg(feature = "parse")]
+impl std::error::Error for ParseFrequencyError {}
+
+/// Parses the name of a frequency or a common abbreviation, ignoring case and surrounding whitespace.
+///
+/// # Comments
+/// The accepted names are:
+/// - annual: `annual`, `annually`, `yearly`, `a`, `p.a.`, `pa`
+/// - semi-annual: `semi-annual`, `semi-annually`, `semiannual`, `semiannually`, `s.a.`, `sa`
+/// - quarterly: `quarterly`, `q`
+/// - monthly: `monthly`, `m`
+/// - weekly: `weekly`, `w`
+/// - daily: `daily`, `d`
+/// - continuous: `continuous`, `continuously`, `cc`
+///
+/// # Example
+/// ```
+/// use time_value::rate::Frequency;
+///
+/// assert_eq!("Quarterly".parse(), Ok(Frequency::Quarterly));
+/// assert_eq!("s.a.".parse(), Ok(Frequency::SemiAnnual));
+/// assert!("fortnightly".parse::<Frequency>().is_err());
+/// ```
+#[cfg(feature = "parse")]
+impl FromStr for Frequency {
+    type Err = ParseFrequencyError;
+
+    fn from_str(s: &str) -> Result<Frequency, ParseFrequencyError> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "annual" | "annually" | "yearly" | "a" | "p.a." | "pa" => Ok(Frequency::Annual),
+            "semi-annual" | "semi-annually" | "semiannual" | "semiannually" | "s.a." | "sa" => {
+                Ok(Frequency::SemiAnnual)
+            }
+            "quarterly" | "q" => Ok(Frequency::Quarterly),
+            "monthly" | "m" => Ok(Frequency::Monthly),
+            "weekly" | "w" => Ok(Frequency::Weekly),
+            "daily" | "d" => Ok(Frequency::Daily),
+            "continuous" | "continuously" | "cc" => Ok(Frequency::Continuous),
+            _ => Err(ParseFrequencyError {
+                input: s.trim().to_string(),
+            }),
+        }
+    }
+}
+
+/// The reason a string is not a rate.
+#[cfg(feature = "parse")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRateError {
+    /// The string is empty or only whitespace.
+    Empty,
+    /// The amount, without its unit, is not a number.
+    InvalidNumber(String),
+    /// The text after the amount is not a frequency.
+    InvalidFrequency(ParseFrequencyError),
+}
+
+#[cfg(feature = "parse")]
+impl Display for ParseRateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            ParseRateError::Empty => write!(f, "the rate is empty"),
+            ParseRateError::InvalidNumber(number) => write!(
+                f,
+                "'{}' is not a number, expected e.g. 5.25%, 525 bps or 0.0525",
+                number
+            ),
+            ParseRateError::InvalidFrequency(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "parse")]
+impl std::error::Error for ParseRateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseRateError::InvalidFrequency(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Parses an amount as a percentage, e.g. `5.25%`, in basis points, e.g. `525 bps` or `525bp`, or as a decimal, e.g. `0.0525`, optionally followed by whitespace and a frequency; see `Frequency::from_str`.
+///
+/// # Comments
+/// Without a frequency the rate compounds annually.
+///
+/// # Example with f64
+/// ```
+/// use time_value::rate::{Frequency, Rate};
+///
+/// let rate: Rate<f64> = "5.25% s.a.".parse().unwrap();
+/// assert_eq!(rate.frequency(), Frequency::SemiAnnual);
+/// assert!((rate.rate() - 0.0525).abs() < 1e-12);
+///
+/// let rate: Rate<f64> = "150 bps quarterly".parse().unwrap();
+/// assert!((rate.rate() - 0.015).abs() < 1e-12);
+///
+/// let rate: Rate<f64> = "0.04".parse().unwrap();
+/// assert_eq!(rate, Rate::new(0.04, Frequency::Annual));
+///
+/// let error = "5.25% fortnightly".parse::<Rate<f64>>().unwrap_err();
+/// assert!(format!("{}", error).starts_with("unknown frequency 'fortnightly'"));
+/// ```
+#[cfg(feature = "parse")]
+impl<T> FromStr for Rate<T>
+where
+    T: Float + FromStr,
+{
+    type Err = ParseRateError;
+
+    fn from_str(s: &str) -> Result<Rate<T>, ParseRateError> {
+        let trimmed: &str = s.trim();
+        if trimmed.is_empty() {
+            return Err(ParseRateError::Empty);
+        }
+        let amount_end: usize = trimmed
+            .find(|character: char| !(character.is_ascii_digit() || "+-.eE".contains(character)))
+            .unwrap_or(trimmed.len());
+        let (amount, rest): (&str, &str) = trimmed.split_at(amount_end);
+        let invalid_number = || ParseRateError::InvalidNumber(trimmed.to_string());
+        let amount: T = amount.parse().map_err(|_| invalid_number())?;
+
+        let rest: &str = rest.trim_start();
+        let lowercase: String = rest.to_ascii_lowercase();
+        let (rate, rest): (T, &str) = if let Some(stripped) = rest.strip_prefix('%') {
+            (Percent::new(amount).to_decimal(), stripped)
+        } else if lowercase.starts_with("bps") {
+            (BasisPoints::new(amount).to_decimal(), &rest[3..])
+        } else if lowercase.starts_with("bp") {
+            (BasisPoints::new(amount).to_decimal(), &rest[2..])
+        } else {
+            (amount, rest)
+        };
+
+        let frequency: Frequency = if rest.trim().is_empty() {
+            Frequency::Annual
+        } else {
+            rest.parse().map_err(ParseRateError::InvalidFrequency)?
+        };
+        Ok(Rate::new(rate, frequency))
+    }
+}
+
+#[cfg(test)]
+mod rate_tests {
+    use crate::rate::{BasisPoints, Frequency, FrequencyError, Percent, Rate};
+
+    #[test]
+    fn it_round_trips_decimals() {
+        let rates: Vec<f64> = vec![0.0, 0.01, -0.025, 0.145, 1.0];
+        for rate in rates {
+            assert!((Percent::from_decimal(rate).to_decimal() - rate).abs() < 1e-12);
+            assert!((BasisPoints::from_decimal(rate).to_decimal() - rate).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_converts_between_units() {
+        let percent: Percent<f64> = Percent::new(1.25);
+        let basis_points: BasisPoints<f64> = BasisPoints::from(percent);
+        assert_eq!(basis_points, BasisPoints::new(125.0));
+        assert_eq!(Percent::from(basis_points), percent);
+    }
+
+    #[test]
+    fn it_compounds_at_each_frequency() {
+        let effective: Vec<f64> = vec![
+            Frequency::Annual,
+            Frequency::SemiAnnual,
+            Frequency::Quarterly,
+            Frequency::Monthly,
+            Frequency::Weekly,
+            Frequency::Daily,
+            Frequency::Continuous,
+        ]
+        .into_iter()
+        .map(|frequency| Rate::new(0.12, frequency).effective_annual())
+        .collect();
+        assert!((effective[0] - 0.12).abs() < 1e-12);
+        assert!((effective[3] - 0.126_825).abs() < 0.000_001);
+        assert!((effective[6] - 0.127_497).abs() < 0.000_001);
+        assert!(effective.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn it_converts_between_frequencies_exactly() {
+        let frequencies: Vec<Frequency> = vec![
+            Frequency::Annual,
+            Frequency::Quarterly,
+            Frequency::Daily,
+            Frequency::Continuous,
+        ];
+        let rate: Rate<f64> = Rate::new(0.08, Frequency::SemiAnnual);
+        for frequency in frequencies {
+            let converted: Rate<f64> = rate.to_frequency(frequency);
+            assert_eq!(converted.frequency(), frequency);
+            assert!((converted.effective_annual() - rate.effective_annual()).abs() < 1e-12);
+            assert!((converted.to_frequency(Frequency::SemiAnnual).rate() - 0.08).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_only_counts_whole_periods() {
+        assert_eq!(Frequency::Quarterly.periods_in(Frequency::Annual), Ok(4));
+        assert_eq!(Frequency::Daily.periods_in(Frequency::Daily), Ok(1));
+        assert_eq!(
+            Frequency::Annual.periods_in(Frequency::Monthly),
+            Err(FrequencyError::NotAMultiple(
+                Frequency::Annual,
+                Frequency::Monthly
+            ))
+        );
+        assert_eq!(
+            Frequency::Continuous.periods_in(Frequency::Annual),
+            Err(FrequencyError::Continuous)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod parse_tests {
+    use crate::rate::{Frequency, ParseRateError, Rate};
+
+    #[test]
+    fn it_round_trips_through_display() {
+        let rates: Vec<Rate<f64>> = vec![
+            Rate::new(0.0525, Frequency::SemiAnnual),
+            Rate::new(-0.005, Frequency::Continuous),
+            Rate::new(0.1, Frequency::Weekly),
+        ];
+        for rate in rates {
+            let parsed: Rate<f64> = format!("{}", rate).parse().unwrap();
+            assert_eq!(parsed.frequency(), rate.frequency());
+            assert!((parsed.rate() - rate.rate()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_accepts_units_with_or_without_spaces() {
+        let rates: Vec<Rate<f32>> = vec!["2.5%m", "250bp monthly", " 250 BPS  M ", "0.025 m"]
+            .into_iter()
+            .map(|rate| rate.parse().unwrap())
+            .collect();
+        for rate in rates {
+            assert_eq!(rate.frequency(), Frequency::Monthly);
+            assert!((rate.rate() - 0.025).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn it_rejects_malformed_rates() {
+        assert_eq!("  ".parse::<Rate<f64>>(), Err(ParseRateError::Empty));
+        assert_eq!(
+            "five percent".parse::<Rate<f64>>(),
+            Err(ParseRateError::InvalidNumber("five percent".to_string()))
+        );
+        assert_eq!(
+            "5..2%".parse::<Rate<f64>>(),
+            Err(ParseRateError::InvalidNumber("5..2%".to_string()))
+        );
+        match "5% biweekly".parse::<Rate<f64>>() {
+            Err(ParseRateError::InvalidFrequency(error)) => {
+                assert_eq!(error.input(), "biweekly")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
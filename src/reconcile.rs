@@ -0,0 +1,394 @@
+//! Row-by-row reconciliation of two amortization schedules, e.g. when migrating from another servicing system.
+
+use num::Float;
+use std::collections::BTreeMap;
+
+use crate::loan::{Row, Schedule};
+
+/// The differences, `b - a`, between the rows of two schedules for the same period.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RowDifference<T>
+where
+    T: Float,
+{
+    period: usize,
+    payment: T,
+    interest: T,
+    principal: T,
+    balance: T,
+}
+
+impl<T> RowDifference<T>
+where
+    T: Float,
+{
+    pub fn new(a: &Row<T>, b: &Row<T>) -> RowDifference<T> {
+        RowDifference {
+            period: a.period(),
+            payment: b.payment() - a.payment(),
+            interest: b.interest() - a.interest(),
+            principal: b.principal() - a.principal(),
+            balance: b.balance() - a.balance(),
+        }
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `period` instead")]
+    pub fn get_period(&self) -> usize {
+        self.period()
+    }
+
+    pub fn payment(&self) -> T {
+        self.payment
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `payment` instead")]
+    pub fn get_payment(&self) -> T {
+        self.payment()
+    }
+
+    pub fn interest(&self) -> T {
+        self.interest
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `interest` instead")]
+    pub fn get_interest(&self) -> T {
+        self.interest()
+    }
+
+    pub fn principal(&self) -> T {
+        self.principal
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `principal` instead")]
+    pub fn get_principal(&self) -> T {
+        self.principal()
+    }
+
+    pub fn balance(&self) -> T {
+        self.balance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `balance` instead")]
+    pub fn get_balance(&self) -> T {
+        self.balance()
+    }
+
+    /// Whether every difference is at most `tolerance` in absolute value; a NaN difference never is.
+    pub fn is_within(&self, tolerance: T) -> bool {
+        [self.payment, self.interest, self.principal, self.balance]
+            .iter()
+            .all(|difference| is_within(*difference, tolerance))
+    }
+}
+
+/// Whether `difference` is at most `tolerance` in absolute value, which is false for NaN.
+fn is_within<T>(difference: T, tolerance: T) -> bool
+where
+    T: Float,
+{
+    difference.abs() <= tolerance
+}
+
+/// Summary statistics of the differences in one column, e.g. the payments, over the matched rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSummary<T>
+where
+    T: Float,
+{
+    total: T,
+    max_abs: T,
+    mismatches: usize,
+}
+
+impl<T> ColumnSummary<T>
+where
+    T: Float,
+{
+    fn from_differences<I: Iterator<Item = T>>(differences: I, tolerance: T) -> ColumnSummary<T> {
+        differences.fold(
+            ColumnSummary {
+                total: T::zero(),
+                max_abs: T::zero(),
+                mismatches: 0,
+            },
+            |summary, difference| ColumnSummary {
+                total: summary.total + difference,
+                max_abs: summary.max_abs.max(difference.abs()),
+                mismatches: summary.mismatches + usize::from(!is_within(difference, tolerance)),
+            },
+        )
+    }
+
+    /// The sum of the differences, i.e. the net amount by which the column of `b` exceeds that of `a`.
+    pub fn total(&self) -> T {
+        self.total
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `total` instead")]
+    pub fn get_total(&self) -> T {
+        self.total()
+    }
+
+    pub fn max_abs(&self) -> T {
+        self.max_abs
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `max_abs` instead")]
+    pub fn get_max_abs(&self) -> T {
+        self.max_abs()
+    }
+
+    /// The number of rows whose difference is greater than the tolerance.
+    pub fn mismatches(&self) -> usize {
+        self.mismatches
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `mismatches` instead")]
+    pub fn get_mismatches(&self) -> usize {
+        self.mismatches()
+    }
+}
+
+/// The result of `schedules`: a difference for every period in both schedules, the rows of periods in only one of them, and a summary of each column.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reconciliation<T>
+where
+    T: Float,
+{
+    tolerance: T,
+    differences: Vec<RowDifference<T>>,
+    only_in_a: Vec<Row<T>>,
+    only_in_b: Vec<Row<T>>,
+    payment: ColumnSummary<T>,
+    interest: ColumnSummary<T>,
+    principal: ColumnSummary<T>,
+    balance: ColumnSummary<T>,
+}
+
+impl<T> Reconciliation<T>
+where
+    T: Float,
+{
+    pub fn tolerance(&self) -> T {
+        self.tolerance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `tolerance` instead")]
+    pub fn get_tolerance(&self) -> T {
+        self.tolerance()
+    }
+
+    /// The differences for the periods in both schedules, in order of period.
+    pub fn differences(&self) -> &[RowDifference<T>] {
+        &self.differences
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `differences` instead")]
+    pub fn get_differences(&self) -> &[RowDifference<T>] {
+        self.differences()
+    }
+
+    /// The differences with a column outside the tolerance.
+    pub fn mismatches(&self) -> Vec<RowDifference<T>> {
+        self.differences
+            .iter()
+            .filter(|difference| !difference.is_within(self.tolerance))
+            .copied()
+            .collect()
+    }
+
+    pub fn only_in_a(&self) -> &[Row<T>] {
+        &self.only_in_a
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `only_in_a` instead")]
+    pub fn get_only_in_a(&self) -> &[Row<T>] {
+        self.only_in_a()
+    }
+
+    pub fn only_in_b(&self) -> &[Row<T>] {
+        &self.only_in_b
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `only_in_b` instead")]
+    pub fn get_only_in_b(&self) -> &[Row<T>] {
+        self.only_in_b()
+    }
+
+    pub fn payment(&self) -> ColumnSummary<T> {
+        self.payment
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `payment` instead")]
+    pub fn get_payment(&self) -> ColumnSummary<T> {
+        self.payment()
+    }
+
+    pub fn interest(&self) -> ColumnSummary<T> {
+        self.interest
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `interest` instead")]
+    pub fn get_interest(&self) -> ColumnSummary<T> {
+        self.interest()
+    }
+
+    pub fn principal(&self) -> ColumnSummary<T> {
+        self.principal
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `principal` instead")]
+    pub fn get_principal(&self) -> ColumnSummary<T> {
+        self.principal()
+    }
+
+    pub fn balance(&self) -> ColumnSummary<T> {
+        self.balance
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `balance` instead")]
+    pub fn get_balance(&self) -> ColumnSummary<T> {
+        self.balance()
+    }
+
+    /// Whether the schedules have the same periods and every difference is within the tolerance.
+    pub fn is_reconciled(&self) -> bool {
+        self.only_in_a.is_empty()
+            && self.only_in_b.is_empty()
+            && self
+                .differences
+                .iter()
+                .all(|difference| difference.is_within(self.tolerance))
+    }
+}
+
+/// Reconciles schedule `b` against schedule `a`, matching their rows by period.
+///
+/// # Comments
+/// Differences are `b - a`, so with `a` the legacy schedule a positive interest difference means that `b` charges more interest.
+/// A column of a row is a mismatch if its difference is greater than `tolerance` in absolute value.
+/// If a schedule has several rows for a period, only the last is reconciled.
+///
+/// # Example with f64
+/// ```
+/// use time_value::loan::{amortize, Row, Schedule};
+/// use time_value::reconcile;
+///
+/// let legacy: Schedule<f64> = amortize(1_000.0, 0.01, 12);
+/// // the imported schedule rounds to cents and stops after 11 periods
+/// let imported: Schedule<f64> = legacy
+///     .rows()
+///     .iter()
+///     .take(11)
+///     .map(|row| {
+///         let cents = |value: f64| (value * 100.0).round() / 100.0;
+///         Row::new(
+///             row.period(),
+///             cents(row.payment()),
+///             cents(row.interest()),
+///             cents(row.principal()),
+///             cents(row.balance()),
+///         )
+///     })
+///     .collect();
+///
+/// let reconciliation = reconcile::schedules(&legacy, &imported, 0.005);
+/// assert!(!reconciliation.is_reconciled());
+/// assert!(reconciliation.mismatches().is_empty());
+/// assert_eq!(reconciliation.only_in_a().len(), 1);
+/// assert!(reconciliation.payment().max_abs() <= 0.005);
+/// ```
+pub fn schedules<T>(a: &Schedule<T>, b: &Schedule<T>, tolerance: T) -> Reconciliation<T>
+where
+    T: Float,
+{
+    let rows_a: BTreeMap<usize, &Row<T>> = a.rows().iter().map(|row| (row.period(), row)).collect();
+    let mut rows_b: BTreeMap<usize, &Row<T>> =
+        b.rows().iter().map(|row| (row.period(), row)).collect();
+
+    let mut differences: Vec<RowDifference<T>> = vec![];
+    let mut only_in_a: Vec<Row<T>> = vec![];
+    for (period, row_a) in rows_a {
+        match rows_b.remove(&period) {
+            Some(row_b) => differences.push(RowDifference::new(row_a, row_b)),
+            None => only_in_a.push(*row_a),
+        }
+    }
+    let only_in_b: Vec<Row<T>> = rows_b.into_values().copied().collect();
+
+    let summary = |column: fn(&RowDifference<T>) -> T| {
+        ColumnSummary::from_differences(differences.iter().map(column), tolerance)
+    };
+    Reconciliation {
+        tolerance,
+        payment: summary(RowDifference::payment),
+        interest: summary(RowDifference::interest),
+        principal: summary(RowDifference::principal),
+        balance: summary(RowDifference::balance),
+        differences,
+        only_in_a,
+        only_in_b,
+    }
+}
+
+#[cfg(test)]
+mod schedules_tests {
+    use crate::loan::{amortize, Row, Schedule};
+    use crate::reconcile::{schedules, Reconciliation};
+
+    #[test]
+    fn it_reconciles_a_schedule_with_itself() {
+        let schedule: Schedule<f64> = amortize(10_000.0, 0.005, 24);
+        let reconciliation: Reconciliation<f64> = schedules(&schedule, &schedule, 0.0);
+
+        assert!(reconciliation.is_reconciled());
+        assert_eq!(reconciliation.differences().len(), 24);
+        assert_eq!(reconciliation.balance().total(), 0.0);
+    }
+
+    #[test]
+    fn it_summarises_the_differences() {
+        let a: Schedule<f64> = vec![
+            Row::new(1, 100.0, 10.0, 90.0, 910.0),
+            Row::new(2, 100.0, 9.1, 90.9, 819.1),
+        ]
+        .into_iter()
+        .collect();
+        let b: Schedule<f64> = vec![
+            Row::new(2, 100.0, 9.0, 91.0, 819.0),
+            Row::new(1, 100.0, 10.0, 90.0, 910.0),
+            Row::new(3, 100.0, 8.19, 91.81, 727.19),
+        ]
+        .into_iter()
+        .collect();
+        let reconciliation: Reconciliation<f64> = schedules(&a, &b, 0.01);
+
+        assert!(!reconciliation.is_reconciled());
+        assert_eq!(reconciliation.only_in_b(), &[b.rows()[2]]);
+        assert_eq!(reconciliation.mismatches().len(), 1);
+        assert_eq!(reconciliation.mismatches()[0].period(), 2);
+        assert_eq!(reconciliation.payment().mismatches(), 0);
+        assert_eq!(reconciliation.interest().mismatches(), 1);
+        assert!((reconciliation.interest().total() + 0.1).abs() < 0.000_001);
+        assert!((reconciliation.principal().max_abs() - 0.1).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_counts_nan_as_a_mismatch() {
+        let a: Schedule<f32> = vec![Row::new(1, 1.0, 0.0, 1.0, 0.0)].into_iter().collect();
+        let b: Schedule<f32> = vec![Row::new(1, f32::NAN, 0.0, 1.0, 0.0)]
+            .into_iter()
+            .collect();
+        let reconciliation: Reconciliation<f32> = schedules(&a, &b, f32::INFINITY);
+
+        assert!(!reconciliation.is_reconciled());
+        assert_eq!(reconciliation.payment().mismatches(), 1);
+    }
+}
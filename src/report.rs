@@ -0,0 +1,151 @@
+//! Functions for rendering calculation results as Markdown or HTML tables.
+
+use num::{Float, Signed};
+use std::fmt::{Debug, Display};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::irr::bisection::structs::irr::Irr;
+use crate::present_value::present_value as pv;
+
+/// The output format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Renders the present value of each cash flow, followed by their total, the NPV.
+///
+/// # Example with f64
+/// ```
+/// use time_value::report::{npv_breakdown, Format};
+///
+/// let cash_flows: Vec<f64> = vec![-100.0, 110.0];
+/// let report: String = npv_breakdown(cash_flows.iter(), 0.10, Format::Markdown);
+/// assert_eq!(
+///     report,
+///     "| period | cash flow | present value |\n\
+///      | --- | --- | --- |\n\
+///      | 0 | -100 | -100 |\n\
+///      | 1 | 110 | 100 |\n\
+///      | NPV |  | 0 |\n"
+/// );
+/// ```
+pub fn npv_breakdown<T>(cash_flows: Iter<T>, discount_rate: T, format: Format) -> String
+where
+    T: Float + Product<T> + Display,
+{
+    let mut npv: T = T::zero();
+    let mut rows: Vec<Vec<String>> = vec![];
+    for (period, cash_flow) in cash_flows.enumerate() {
+        let present_value: T = pv(*cash_flow, period, discount_rate);
+        npv = npv + present_value;
+        rows.push(vec![
+            period.to_string(),
+            cash_flow.to_string(),
+            present_value.to_string(),
+        ]);
+    }
+    rows.push(vec![String::from("NPV"), String::new(), npv.to_string()]);
+
+    render(&["period", "cash flow", "present value"], &rows, format)
+}
+
+/// Renders the diagnostics of an IRR calculation.
+///
+/// # Example with f32
+/// ```
+/// use time_value::irr::bisection::functions::irr::bisection as irr;
+/// use time_value::report::{irr_diagnostics, Format};
+///
+/// let cash_flows: Vec<f32> = vec![-100.0, 50.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0];
+/// let report: String = irr_diagnostics(&irr(cash_flows.iter(), 0.05, 0.18, 100), Format::Html);
+/// assert!(report.starts_with("<table>"));
+/// assert!(report.contains("<tr><td>is_valid</td><td>true</td></tr>"));
+/// ```
+pub fn irr_diagnostics<T>(irr: &Irr<T>, format: Format) -> String
+where
+    T: Float + Product<T> + Sum<T> + Signed + Display,
+{
+    let rows: Vec<Vec<String>> = vec![
+        vec![String::from("irr"), irr.irr().to_string()],
+        vec![String::from("npv"), irr.npv().to_string()],
+        vec![String::from("rate_low"), irr.rate_low().to_string()],
+        vec![String::from("npv_rate_low"), irr.npv_rate_low().to_string()],
+        vec![String::from("rate_high"), irr.rate_high().to_string()],
+        vec![
+            String::from("npv_rate_high"),
+            irr.npv_rate_high().to_string(),
+        ],
+        vec![
+            String::from("iteration_limit"),
+            irr.iteration_limit().to_string(),
+        ],
+        vec![
+            String::from("iterations_run"),
+            irr.iterations_run().to_string(),
+        ],
+        vec![
+            String::from("npv_evaluations"),
+            irr.npv_evaluations().to_string(),
+        ],
+        vec![String::from("is_valid"), irr.is_valid().to_string()],
+        vec![String::from("is_polished"), irr.is_polished().to_string()],
+        vec![
+            String::from("failure_reason"),
+            irr.failure_reason()
+                .map_or(String::from("none"), |failure_reason| {
+                    failure_reason.to_string()
+                }),
+        ],
+    ];
+
+    render(&["field", "value"], &rows, format)
+}
+
+fn render(header: &[&str], rows: &[Vec<String>], format: Format) -> String {
+    match format {
+        Format::Markdown => {
+            let mut table: String = format!("| {} |\n", header.join(" | "));
+            table.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+            for row in rows {
+                table.push_str(&format!("| {} |\n", row.join(" | ")));
+            }
+            table
+        }
+        Format::Html => {
+            let mut table: String = String::from("<table>\n");
+            table.push_str(&format!("<tr><th>{}</th></tr>\n", header.join("</th><th>")));
+            for row in rows {
+                table.push_str(&format!("<tr><td>{}</td></tr>\n", row.join("</td><td>")));
+            }
+            table.push_str("</table>\n");
+            table
+        }
+    }
+}
+
+#[cfg(test)]
+mod npv_breakdown_tests {
+    use crate::report::{npv_breakdown, Format};
+
+    #[test]
+    fn it_works_with_no_cash_flows() {
+        let cash_flows: Vec<f32> = vec![];
+        assert_eq!(
+            npv_breakdown(cash_flows.iter(), 0.10, Format::Html),
+            "<table>\n\
+             <tr><th>period</th><th>cash flow</th><th>present value</th></tr>\n\
+             <tr><td>NPV</td><td></td><td>0</td></tr>\n\
+             </table>\n"
+        );
+    }
+
+    #[test]
+    fn it_has_a_row_per_cash_flow() {
+        let cash_flows: Vec<f64> = vec![-500.0, 100.0, 2.0, 3.0, 4.0];
+        let report: String = npv_breakdown(cash_flows.iter(), 0.30, Format::Markdown);
+        assert_eq!(report.lines().count(), 2 + cash_flows.len() + 1);
+    }
+}
@@ -0,0 +1,415 @@
+//! Functions for summarising series of period returns, such as the expected rates used with `future_value::from_pv_and_expected_rates`, and for the returns of portfolios.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::cash_flow_series::{CashFlowSeries, Tag};
+use crate::irr::bisection::functions::irr::bisection_with_config;
+use crate::irr::bisection::structs::config::BisectionConfig;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::power::powf;
+
+/// Calculates the annualised mean of a series of period returns, compounded as in `future_value::from_pv_and_expected_rates`.
+///
+/// # Comments
+/// The mean is geometric, `(prod(1 + r_t))^(periods_per_year / n) - 1`, so growing a present value at it for `n / periods_per_year` years gives the same future value as the series.
+/// The mean of no returns is NaN.
+///
+/// # Example with f64
+/// ```
+/// use time_value::returns::annualized_mean;
+///
+/// // four quarters of 2% compound to 8.24% a year
+/// let period_returns: Vec<f64> = vec![0.02, 0.02, 0.02, 0.02];
+/// assert!((annualized_mean(period_returns.iter(), 4.0) - 0.082_432_16).abs() < 0.000_000_01);
+/// ```
+pub fn annualized_mean<T>(period_returns: Iter<T>, periods_per_year: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    if period_returns.len() == 0 {
+        return T::nan();
+    }
+    let periods: T = T::from(period_returns.len()).unwrap();
+    let growth: T = period_returns.map(|r| T::one() + *r).product();
+    powf(growth, periods_per_year / periods) - T::one()
+}
+
+/// Calculates the annualised sample standard deviation of a series of period returns.
+///
+/// # Comments
+/// The period standard deviation uses `n - 1` degrees of freedom and is scaled by `sqrt(periods_per_year)`, which assumes the returns are independent.
+/// The standard deviation of fewer than two returns is NaN.
+///
+/// # Example with f64
+/// ```
+/// use time_value::returns::annualized_stdev;
+///
+/// let period_returns: Vec<f64> = vec![0.01, 0.03, 0.01, 0.03];
+/// // the monthly standard deviation is 0.011547, or 0.04 a year
+/// assert!((annualized_stdev(period_returns.iter(), 12.0) - 0.04).abs() < 0.000_000_1);
+/// ```
+pub fn annualized_stdev<T>(period_returns: Iter<T>, periods_per_year: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    if period_returns.len() < 2 {
+        return T::nan();
+    }
+    let periods: T = T::from(period_returns.len()).unwrap();
+    let mean: T = period_returns.clone().copied().sum::<T>() / periods;
+    let variance: T =
+        period_returns.map(|r| (*r - mean) * (*r - mean)).sum::<T>() / (periods - T::one());
+    (variance * periods_per_year).sqrt()
+}
+
+/// Calculates a Sharpe-like ratio of a series of period returns: the annualised excess return over `risk_free` per unit of annualised standard deviation.
+///
+/// # Comments
+/// `risk_free` is an annual rate; the excess return is `annualized_mean - risk_free` and the risk is `annualized_stdev`.
+/// Because the mean is geometric, the ratio is lower than one computed from arithmetic means when returns are volatile.
+///
+/// # Example with f64
+/// ```
+/// use time_value::returns::sharpe_like;
+///
+/// let period_returns: Vec<f64> = vec![0.01, 0.03, 0.01, 0.03];
+/// let ratio: f64 = sharpe_like(period_returns.iter(), 0.02, 12.0);
+/// assert!((ratio - 6.18).abs() < 0.01);
+/// ```
+pub fn sharpe_like<T>(period_returns: Iter<T>, risk_free: T, periods_per_year: T) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    (annualized_mean(period_returns.clone(), periods_per_year) - risk_free)
+        / annualized_stdev(period_returns, periods_per_year)
+}
+
+/// Nets the cash flows of several accounts into the net cash flow of the portfolio in each period, for use with the solvers.
+///
+/// # Comments
+/// With `external_only`, cash flows tagged `Tag::Transfer` are dropped, so only flows into and out of the portfolio remain even if a transfer is recorded by one account only.
+/// Otherwise every cash flow is kept, so a transfer nets out only if both of its legs are recorded, and a transfer to or from an account outside `accounts` counts as an external flow.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+/// use time_value::returns::portfolio_cash_flows;
+///
+/// let mut savings: CashFlowSeries<f64> = CashFlowSeries::new();
+/// savings.push(CashFlow::new(0, -100.0).with_tag(Tag::Contribution));
+/// savings.push(CashFlow::new(1, 40.0).with_tag(Tag::Transfer));
+/// let mut brokerage: CashFlowSeries<f64> = CashFlowSeries::new();
+/// brokerage.push(CashFlow::new(1, -40.0).with_tag(Tag::Transfer));
+/// // a transfer to an account outside the portfolio
+/// brokerage.push(CashFlow::new(2, 10.0).with_tag(Tag::Transfer));
+///
+/// let accounts: Vec<CashFlowSeries<f64>> = vec![savings, brokerage];
+/// assert_eq!(portfolio_cash_flows(&accounts, true), vec![-100.0]);
+/// assert_eq!(portfolio_cash_flows(&accounts, false), vec![-100.0, 0.0, 10.0]);
+/// ```
+pub fn portfolio_cash_flows<T>(accounts: &[CashFlowSeries<T>], external_only: bool) -> Vec<T>
+where
+    T: Float,
+{
+    accounts
+        .iter()
+        .flat_map(|account| account.cash_flows())
+        .filter(|cash_flow| !external_only || cash_flow.tag() != Some(&Tag::Transfer))
+        .cloned()
+        .collect::<CashFlowSeries<T>>()
+        .by_period()
+}
+
+/// Calculates the money-weighted return of a portfolio of accounts, i.e. the IRR of its net cash flows from `portfolio_cash_flows`.
+///
+/// # Assumptions
+/// Each account's cash flows are from the investor's point of view, so contributions are negative and distributions positive, and the value of the account at the end is included as a positive cash flow in the last period.
+///
+/// # Comments
+/// The IRR is found with `bisection_with_config` and the default `BisectionConfig`, which searches for the bracket outwards from 10%.
+///
+/// # Example with f64
+/// ```
+/// use time_value::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::returns::portfolio_mwr;
+///
+/// let mut savings: CashFlowSeries<f64> = CashFlowSeries::new();
+/// savings.push(CashFlow::new(0, -1_000.0).with_tag(Tag::Contribution));
+/// savings.push(CashFlow::new(1, 500.0).with_tag(Tag::Transfer));
+/// savings.push(CashFlow::new(2, 550.0).with_tag(Tag::Distribution));
+/// let mut brokerage: CashFlowSeries<f64> = CashFlowSeries::new();
+/// brokerage.push(CashFlow::new(1, -500.0).with_tag(Tag::Transfer));
+/// brokerage.push(CashFlow::new(2, 600.0).with_tag(Tag::Distribution));
+///
+/// // 1,000 grows to 1,150 over two periods
+/// let accounts: Vec<CashFlowSeries<f64>> = vec![savings, brokerage];
+/// let calculated_irr: Irr<f64> = portfolio_mwr(&accounts, true);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.072_38).abs() < 0.000_01);
+/// ```
+pub fn portfolio_mwr<T>(accounts: &[CashFlowSeries<T>], external_only: bool) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let cash_flows: Vec<T> = portfolio_cash_flows(accounts, external_only);
+    bisection_with_config(cash_flows.iter(), &BisectionConfig::new())
+}
+
+/// One portfolio's values and external cash flow over a period, for use with `composite_return`.
+///
+/// # Comments
+/// Unlike the cash flows of `portfolio_mwr`, the external flow is from the portfolio's point of view, so a contribution is positive and a withdrawal negative.
+/// `flow_weight` is the share of the period for which the flow was invested, e.g. 0.5 for a flow in the middle of the period.
+///
+/// # Example with f64
+/// ```
+/// use time_value::returns::PortfolioPeriod;
+///
+/// // 1,000 at the start, 100 contributed mid-period and 1,155 at the end
+/// let period: PortfolioPeriod<f64> = PortfolioPeriod::new(1_000.0, 100.0, 1_155.0, 0.5);
+/// assert_eq!(period.capital(), 1_050.0);
+/// assert!((period.dietz_return() - 0.052_38).abs() < 0.000_01);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PortfolioPeriod<T>
+where
+    T: Float,
+{
+    beginning_value: T,
+    external_flow: T,
+    ending_value: T,
+    flow_weight: T,
+}
+
+impl<T> PortfolioPeriod<T>
+where
+    T: Float,
+{
+    pub fn new(
+        beginning_value: T,
+        external_flow: T,
+        ending_value: T,
+        flow_weight: T,
+    ) -> PortfolioPeriod<T> {
+        PortfolioPeriod {
+            beginning_value,
+            external_flow,
+            ending_value,
+            flow_weight,
+        }
+    }
+
+    pub fn beginning_value(&self) -> T {
+        self.beginning_value
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `beginning_value` instead")]
+    pub fn get_beginning_value(&self) -> T {
+        self.beginning_value()
+    }
+
+    pub fn external_flow(&self) -> T {
+        self.external_flow
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `external_flow` instead")]
+    pub fn get_external_flow(&self) -> T {
+        self.external_flow()
+    }
+
+    pub fn ending_value(&self) -> T {
+        self.ending_value
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `ending_value` instead")]
+    pub fn get_ending_value(&self) -> T {
+        self.ending_value()
+    }
+
+    pub fn flow_weight(&self) -> T {
+        self.flow_weight
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `flow_weight` instead")]
+    pub fn get_flow_weight(&self) -> T {
+        self.flow_weight()
+    }
+
+    /// The capital invested over the period, `beginning_value + flow_weight * external_flow`.
+    pub fn capital(&self) -> T {
+        self.beginning_value + self.flow_weight * self.external_flow
+    }
+
+    /// The gain over the period, `ending_value - beginning_value - external_flow`.
+    pub fn gain(&self) -> T {
+        self.ending_value - self.beginning_value - self.external_flow
+    }
+
+    /// The modified Dietz return, i.e. the gain divided by the capital.
+    pub fn dietz_return(&self) -> T {
+        self.gain() / self.capital()
+    }
+}
+
+/// Calculates the asset-weighted return of a composite of portfolios over one period, as in the GIPS standards.
+///
+/// # Comments
+/// Each portfolio's modified Dietz return is weighted by its capital, i.e. its beginning value adjusted for external flows, so the composite return is the total gain divided by the total capital.
+/// Composite returns for consecutive periods can be linked geometrically e.g. with `annualized_mean`.
+/// The return of a composite with no capital is NaN.
+///
+/// # Example with f64
+/// ```
+/// use time_value::returns::{composite_return, PortfolioPeriod};
+///
+/// let portfolios: Vec<PortfolioPeriod<f64>> = vec![
+///     // 10% on 1,000
+///     PortfolioPeriod::new(1_000.0, 0.0, 1_100.0, 0.5),
+///     // 2% on 3,000, of which 1,000 was contributed at the start of the period
+///     PortfolioPeriod::new(2_000.0, 1_000.0, 3_060.0, 1.0),
+/// ];
+/// assert!((composite_return(portfolios.iter()) - 0.04).abs() < 0.000_001);
+/// ```
+pub fn composite_return<T>(portfolios: Iter<PortfolioPeriod<T>>) -> T
+where
+    T: Float,
+{
+    let (gain, capital): (T, T) = portfolios
+        .fold((T::zero(), T::zero()), |(gain, capital), portfolio| {
+            (gain + portfolio.gain(), capital + portfolio.capital())
+        });
+    if capital.is_zero() {
+        T::nan()
+    } else {
+        gain / capital
+    }
+}
+
+#[cfg(test)]
+mod annualized_mean_tests {
+    use crate::future_value::from_pv_and_expected_rates as fv;
+    use crate::returns::annualized_mean;
+
+    #[test]
+    fn it_works_with_no_returns() {
+        let period_returns: Vec<f64> = vec![];
+        assert!(annualized_mean(period_returns.iter(), 12.0).is_nan());
+    }
+
+    #[test]
+    fn it_reproduces_the_future_value() {
+        let period_returns: Vec<f64> = vec![0.10, -0.05, 0.20];
+        let mean: f64 = annualized_mean(period_returns.iter(), 1.0);
+        let constant: Vec<f64> = vec![mean; 3];
+        assert!((fv(100.0, period_returns.iter()) - fv(100.0, constant.iter())).abs() < 0.000_001);
+    }
+}
+
+#[cfg(test)]
+mod annualized_stdev_tests {
+    use crate::returns::annualized_stdev;
+
+    #[test]
+    fn it_works_with_one_return() {
+        let period_returns: Vec<f32> = vec![0.01];
+        assert!(annualized_stdev(period_returns.iter(), 12.0).is_nan());
+    }
+
+    #[test]
+    fn it_is_zero_for_constant_returns() {
+        let period_returns: Vec<f64> = vec![0.01; 6];
+        assert_eq!(annualized_stdev(period_returns.iter(), 12.0), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod portfolio_mwr_tests {
+    use crate::cash_flow_series::{CashFlow, CashFlowSeries, Tag};
+    use crate::irr::bisection::functions::irr::bisection;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::returns::{portfolio_cash_flows, portfolio_mwr};
+
+    fn account(cash_flows: Vec<CashFlow<f64>>) -> CashFlowSeries<f64> {
+        cash_flows.into_iter().collect()
+    }
+
+    #[test]
+    fn it_nets_out_transfers_with_both_legs() {
+        let accounts: Vec<CashFlowSeries<f64>> = vec![
+            account(vec![
+                CashFlow::new(0, -100.0),
+                CashFlow::new(1, 30.0).with_tag(Tag::Transfer),
+                CashFlow::new(3, 50.0),
+            ]),
+            account(vec![
+                CashFlow::new(1, -30.0).with_tag(Tag::Transfer),
+                CashFlow::new(3, 70.0),
+            ]),
+        ];
+        let external: Irr<f64> = portfolio_mwr(&accounts, true);
+        let all: Irr<f64> = portfolio_mwr(&accounts, false);
+
+        assert!(external.approx_eq(&all, 0.000_001));
+        let cash_flows: Vec<f64> = vec![-100.0, 0.0, 0.0, 120.0];
+        let expected: Irr<f64> = bisection(cash_flows.iter(), 0.0, 0.5, 100);
+        assert!((external.irr() - expected.irr()).abs() < 0.000_1);
+    }
+
+    #[test]
+    fn it_counts_a_one_sided_transfer_only_if_asked() {
+        let accounts: Vec<CashFlowSeries<f64>> = vec![account(vec![
+            CashFlow::new(0, -100.0),
+            CashFlow::new(1, -100.0).with_tag(Tag::Transfer),
+            CashFlow::new(2, 220.0),
+        ])];
+
+        assert!(portfolio_mwr(&accounts, true).irr() > 0.4);
+        assert!(portfolio_mwr(&accounts, false).irr() < 0.1);
+    }
+
+    #[test]
+    fn it_works_with_no_accounts() {
+        let accounts: Vec<CashFlowSeries<f64>> = vec![];
+        assert!(portfolio_cash_flows(&accounts, false).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod composite_return_tests {
+    use crate::returns::{composite_return, PortfolioPeriod};
+
+    #[test]
+    fn it_works_with_no_portfolios() {
+        let portfolios: Vec<PortfolioPeriod<f64>> = vec![];
+        assert!(composite_return(portfolios.iter()).is_nan());
+    }
+
+    #[test]
+    fn it_weights_by_capital() {
+        let portfolios: Vec<PortfolioPeriod<f64>> = vec![
+            PortfolioPeriod::new(1_000.0, 200.0, 1_320.0, 0.5),
+            PortfolioPeriod::new(500.0, -100.0, 420.0, 0.25),
+        ];
+        let weighted: f64 = portfolios
+            .iter()
+            .map(|portfolio| portfolio.capital() * portfolio.dietz_return())
+            .sum::<f64>()
+            / portfolios
+                .iter()
+                .map(|portfolio| portfolio.capital())
+                .sum::<f64>();
+
+        assert!((composite_return(portfolios.iter()) - weighted).abs() < 0.000_000_001);
+        assert!((composite_return(portfolios.iter()) - 140.0 / 1_575.0).abs() < 0.000_000_001);
+    }
+
+    #[test]
+    fn it_is_the_return_of_a_single_portfolio() {
+        let portfolios: Vec<PortfolioPeriod<f32>> =
+            vec![PortfolioPeriod::new(100.0, 0.0, 95.0, 0.5)];
+        assert!((composite_return(portfolios.iter()) + 0.05).abs() < 0.000_001);
+    }
+}
@@ -0,0 +1,212 @@
+//! A builder for projecting the contributions to, and balance of, a savings plan.
+
+use num::Float;
+use std::slice::Iter;
+
+/// An employer contribution matching a share of the employee contribution, up to a share of salary.
+///
+/// # Example with f64
+/// ```
+/// use time_value::savings::EmployerMatch;
+///
+/// // 50% of contributions up to 6% of salary
+/// let employer_match: EmployerMatch<f64> = EmployerMatch::new(0.50, 0.06);
+/// assert_eq!(employer_match.matched(4_000.0, 50_000.0), 1_500.0);
+/// assert_eq!(employer_match.matched(2_000.0, 50_000.0), 1_000.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmployerMatch<T>
+where
+    T: Float,
+{
+    match_rate: T,
+    salary_cap: T,
+}
+
+impl<T> EmployerMatch<T>
+where
+    T: Float,
+{
+    /// Matches `match_rate` of the employee contribution, counting contributions up to `salary_cap` of salary.
+    pub fn new(match_rate: T, salary_cap: T) -> EmployerMatch<T> {
+        EmployerMatch {
+            match_rate,
+            salary_cap,
+        }
+    }
+
+    pub fn match_rate(&self) -> T {
+        self.match_rate
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `match_rate` instead")]
+    pub fn get_match_rate(&self) -> T {
+        self.match_rate()
+    }
+
+    pub fn salary_cap(&self) -> T {
+        self.salary_cap
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `salary_cap` instead")]
+    pub fn get_salary_cap(&self) -> T {
+        self.salary_cap()
+    }
+
+    /// The employer contribution for an employee contribution and salary.
+    pub fn matched(&self, contribution: T, salary: T) -> T {
+        self.match_rate * contribution.min(self.salary_cap * salary)
+    }
+}
+
+/// Projects the contributions to a savings plan from salary, contribution rate and employer match assumptions.
+///
+/// # Comments
+/// Contributions are made at the end of periods 1 to `periods`. In period `t`:
+/// - salary is `base * (1 + growth)^(t - 1)`
+/// - the contribution rate is `rate + escalation * (t - 1)`, up to `max_rate`
+/// - the employee contribution is the contribution rate times salary
+/// - the employer contribution is given by the `EmployerMatch`, if any
+///
+/// # Example with f64
+/// ```
+/// use time_value::savings::{EmployerMatch, SavingsPlan};
+///
+/// let plan: SavingsPlan<f64> = SavingsPlan::new(3)
+///     .salary(50_000.0, 0.0)
+///     .contribution(0.04, 0.01, 0.10)
+///     .employer_match(EmployerMatch::new(0.50, 0.05));
+///
+/// // 2,000 + 1,000, then 2,500 + 1,250, then 3,000 + 1,250
+/// let contributions: Vec<f64> = plan.contributions();
+/// assert!((contributions[0] - 3_000.0).abs() < 0.000_001);
+/// assert!((contributions[1] - 3_750.0).abs() < 0.000_001);
+/// assert!((contributions[2] - 4_250.0).abs() < 0.000_001);
+///
+/// let rates: Vec<f64> = vec![0.0, 0.0, 0.0];
+/// assert!((plan.balances(1_000.0, rates.iter())[3] - 12_000.0).abs() < 0.000_001);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavingsPlan<T>
+where
+    T: Float,
+{
+    periods: usize,
+    salary_base: T,
+    salary_growth: T,
+    contribution_rate: T,
+    contribution_escalation: T,
+    contribution_max_rate: T,
+    employer_match: Option<EmployerMatch<T>>,
+}
+
+impl<T> SavingsPlan<T>
+where
+    T: Float,
+{
+    /// Starts a plan with `periods` contribution periods and no salary, contributions or employer match.
+    pub fn new(periods: usize) -> SavingsPlan<T> {
+        SavingsPlan {
+            periods,
+            salary_base: T::zero(),
+            salary_growth: T::zero(),
+            contribution_rate: T::zero(),
+            contribution_escalation: T::zero(),
+            contribution_max_rate: T::zero(),
+            employer_match: None,
+        }
+    }
+
+    /// Salary of `base` in the first period, growing at `growth` per period thereafter.
+    pub fn salary(mut self, base: T, growth: T) -> SavingsPlan<T> {
+        self.salary_base = base;
+        self.salary_growth = growth;
+        self
+    }
+
+    /// Contributions of `rate` of salary in the first period, escalating by `escalation` of salary per period up to `max_rate`.
+    pub fn contribution(mut self, rate: T, escalation: T, max_rate: T) -> SavingsPlan<T> {
+        self.contribution_rate = rate;
+        self.contribution_escalation = escalation;
+        self.contribution_max_rate = max_rate;
+        self
+    }
+
+    /// An employer match of the employee contributions.
+    pub fn employer_match(mut self, employer_match: EmployerMatch<T>) -> SavingsPlan<T> {
+        self.employer_match = Some(employer_match);
+        self
+    }
+
+    /// Projects the total contributions for periods `1..=periods`.
+    pub fn contributions(&self) -> Vec<T> {
+        let growth: T = T::one() + self.salary_growth;
+        let mut salary: T = self.salary_base;
+        let mut rate: T = self.contribution_rate;
+        (1..=self.periods)
+            .map(|_| {
+                let contribution: T = rate.min(self.contribution_max_rate) * salary;
+                let matched: T = self.employer_match.map_or(T::zero(), |employer_match| {
+                    employer_match.matched(contribution, salary)
+                });
+                salary = salary * growth;
+                rate = rate + self.contribution_escalation;
+                contribution + matched
+            })
+            .collect()
+    }
+
+    /// Projects the balance for periods `0..=periods`, starting from `present_value` and growing at `expected_rates` as in `future_value::from_pv_and_expected_rates`.
+    ///
+    /// # Comments
+    /// Periods without an expected rate grow at zero.
+    pub fn balances(&self, present_value: T, expected_rates: Iter<T>) -> Vec<T> {
+        let rates: &[T] = expected_rates.as_slice();
+        let mut balance: T = present_value;
+        let mut balances: Vec<T> = vec![balance];
+        for (period, contribution) in self.contributions().into_iter().enumerate() {
+            let rate: T = rates.get(period).copied().unwrap_or_else(T::zero);
+            balance = balance * (T::one() + rate) + contribution;
+            balances.push(balance);
+        }
+        balances
+    }
+}
+
+#[cfg(test)]
+mod savings_plan_tests {
+    use crate::savings::{EmployerMatch, SavingsPlan};
+
+    #[test]
+    fn it_works_with_no_assumptions() {
+        let plan: SavingsPlan<f32> = SavingsPlan::new(3);
+        let rates: Vec<f32> = vec![0.05; 3];
+        assert_eq!(plan.contributions(), vec![0.0; 3]);
+        assert_eq!(plan.balances(0.0, rates.iter()), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn it_caps_the_escalation() {
+        let contributions: Vec<f64> = SavingsPlan::new(4)
+            .salary(100.0, 0.0)
+            .contribution(0.08, 0.02, 0.10)
+            .contributions();
+        for (contribution, expected) in contributions.iter().zip([8.0, 10.0, 10.0, 10.0].iter()) {
+            assert!((contribution - expected).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn it_grows_the_balance_before_contributing() {
+        let plan: SavingsPlan<f64> = SavingsPlan::new(2)
+            .salary(1_000.0, 0.10)
+            .contribution(0.10, 0.0, 0.10)
+            .employer_match(EmployerMatch::new(1.0, 0.03));
+        let rates: Vec<f64> = vec![0.10, 0.10];
+        let balances: Vec<f64> = plan.balances(1_000.0, rates.iter());
+
+        // 1,000 * 1.1 + 100 + 30, then 1,230 * 1.1 + 110 + 33
+        assert!((balances[1] - 1_230.0).abs() < 0.000_001);
+        assert!((balances[2] - 1_496.0).abs() < 0.000_001);
+    }
+}
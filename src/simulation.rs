@@ -0,0 +1,336 @@
+//! Functions for simulating distributions of future values.
+//!
+//! Simulations are driven by a small seeded generator, so the same seed gives the same distribution on every platform.
+
+use num::Float;
+use std::iter::{Product, Sum};
+use std::slice::Iter;
+
+use crate::future_value::from_pv_and_expected_rates as fv;
+
+/// How historical returns are resampled into a path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resampling {
+    /// Each period's return is drawn independently, which discards any autocorrelation.
+    Iid,
+    /// Blocks of this many consecutive returns are drawn, wrapping around the end of the history, which keeps short-term autocorrelation.
+    Block(usize),
+}
+
+/// A normal distribution of one period's return.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalReturn<T>
+where
+    T: Float,
+{
+    mean: T,
+    stdev: T,
+}
+
+impl<T> NormalReturn<T>
+where
+    T: Float,
+{
+    pub fn new(mean: T, stdev: T) -> NormalReturn<T> {
+        NormalReturn { mean, stdev }
+    }
+
+    pub fn mean(&self) -> T {
+        self.mean
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `mean` instead")]
+    pub fn get_mean(&self) -> T {
+        self.mean()
+    }
+
+    pub fn stdev(&self) -> T {
+        self.stdev
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `stdev` instead")]
+    pub fn get_stdev(&self) -> T {
+        self.stdev()
+    }
+}
+
+/// The simulated future values of every path, sorted in increasing order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FvDistribution<T>
+where
+    T: Float,
+{
+    future_values: Vec<T>,
+}
+
+impl<T> FvDistribution<T>
+where
+    T: Float + Sum<T>,
+{
+    fn new(mut future_values: Vec<T>) -> FvDistribution<T> {
+        future_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        FvDistribution { future_values }
+    }
+
+    pub fn future_values(&self) -> &[T] {
+        &self.future_values
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `future_values` instead")]
+    pub fn get_future_values(&self) -> &[T] {
+        self.future_values()
+    }
+
+    /// The mean future value, or NaN if there are no paths.
+    pub fn mean(&self) -> T {
+        self.future_values.iter().copied().sum::<T>() / T::from(self.future_values.len()).unwrap()
+    }
+
+    /// The future value at `probability` between 0 and 1, interpolating linearly between paths, or NaN if there are no paths.
+    pub fn percentile(&self, probability: T) -> T {
+        let last: usize = match self.future_values.len() {
+            0 => return T::nan(),
+            len => len - 1,
+        };
+        let position: T = probability.max(T::zero()).min(T::one()) * T::from(last).unwrap();
+        let below: usize = position.floor().to_usize().unwrap();
+        let above: usize = position.ceil().to_usize().unwrap();
+        let weight: T = position - position.floor();
+        self.future_values[below] * (T::one() - weight) + self.future_values[above] * weight
+    }
+}
+
+/// Simulates future values by resampling historical period returns, i.e. bootstrapping.
+///
+/// # Comments
+/// Each of `n_paths` paths grows `pv` over `horizon` periods with returns drawn from `historical_returns` as set by `resampling`.
+/// Without historical returns and with a positive horizon there is nothing to draw from, so there are no paths.
+///
+/// # Example with f64
+/// ```
+/// use time_value::simulation::{bootstrap_fv, FvDistribution, Resampling};
+///
+/// let historical_returns: Vec<f64> = vec![0.12, -0.08, 0.05, 0.21, -0.15, 0.09, 0.03, 0.14];
+/// let distribution: FvDistribution<f64> =
+///     bootstrap_fv(100.0, historical_returns.iter(), 10, 1_000, Resampling::Block(3), 42);
+/// assert_eq!(distribution.future_values().len(), 1_000);
+/// assert!(distribution.percentile(0.05) < distribution.percentile(0.50));
+/// assert!(distribution.percentile(0.50) < distribution.percentile(0.95));
+/// ```
+pub fn bootstrap_fv<T>(
+    pv: T,
+    historical_returns: Iter<T>,
+    horizon: usize,
+    n_paths: usize,
+    resampling: Resampling,
+    seed: u64,
+) -> FvDistribution<T>
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let history: &[T] = historical_returns.as_slice();
+    if history.is_empty() && 0 < horizon {
+        return FvDistribution::new(vec![]);
+    }
+
+    let block: usize = match resampling {
+        Resampling::Iid => 1,
+        Resampling::Block(block) => block.max(1),
+    };
+    let mut generator: SplitMix64 = SplitMix64::new(seed);
+    let mut path: Vec<T> = Vec::with_capacity(horizon);
+    let future_values: Vec<T> = (0..n_paths)
+        .map(|_| {
+            path.clear();
+            while path.len() < horizon {
+                let start: usize = generator.next_index(history.len());
+                let remaining: usize = block.min(horizon - path.len());
+                path.extend((start..start + remaining).map(|i| history[i % history.len()]));
+            }
+            fv(pv, path.iter())
+        })
+        .collect();
+
+    FvDistribution::new(future_values)
+}
+
+/// Simulates future values when the distribution of returns changes by period, e.g. along a target-date glidepath.
+///
+/// # Comments
+/// `distributions` holds one distribution per period, so its length is the horizon; each of `n_paths` paths grows `pv` with one draw from each.
+/// Draws are independent across periods and are generated with the Box-Muller transform.
+///
+/// # Example with f64
+/// ```
+/// use time_value::simulation::{glidepath_fv, FvDistribution, NormalReturn};
+///
+/// // 20 years of 7% +/- 15%, derisking to 4% +/- 5% over the last 10
+/// let mut glidepath: Vec<NormalReturn<f64>> = vec![NormalReturn::new(0.07, 0.15); 20];
+/// glidepath.extend((1..=10).map(|year| {
+///     let step: f64 = year as f64 / 10.0;
+///     NormalReturn::new(0.07 - 0.03 * step, 0.15 - 0.10 * step)
+/// }));
+/// let distribution: FvDistribution<f64> = glidepath_fv(100.0, glidepath.iter(), 2_000, 42);
+/// assert!(distribution.percentile(0.10) < distribution.percentile(0.90));
+/// ```
+pub fn glidepath_fv<T>(
+    pv: T,
+    distributions: Iter<NormalReturn<T>>,
+    n_paths: usize,
+    seed: u64,
+) -> FvDistribution<T>
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    let mut generator: SplitMix64 = SplitMix64::new(seed);
+    let mut path: Vec<T> = Vec::with_capacity(distributions.len());
+    let future_values: Vec<T> = (0..n_paths)
+        .map(|_| {
+            path.clear();
+            path.extend(distributions.clone().map(|distribution| {
+                distribution.mean()
+                    + distribution.stdev() * T::from(generator.next_standard_normal()).unwrap()
+            }));
+            fv(pv, path.iter())
+        })
+        .collect();
+
+    FvDistribution::new(future_values)
+}
+
+/// The SplitMix64 generator, which is fast, seedable and passes standard statistical test suites.
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z: u64 = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniform draw from `[0, 1)` with 53 bits of precision.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// A draw from the standard normal distribution, by the Box-Muller transform.
+    pub(crate) fn next_standard_normal(&mut self) -> f64 {
+        // 1 - u is in (0, 1], so its logarithm is finite
+        let radius: f64 = (-2.0 * (1.0 - self.next_f64()).ln()).sqrt();
+        radius * (2.0 * std::f64::consts::PI * self.next_f64()).cos()
+    }
+
+    /// A uniform draw from `0..n`, for positive `n`.
+    pub(crate) fn next_index(&mut self, n: usize) -> usize {
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod bootstrap_fv_tests {
+    use crate::simulation::{bootstrap_fv, FvDistribution, Resampling};
+
+    #[test]
+    fn it_works_without_historical_returns() {
+        let historical_returns: Vec<f64> = vec![];
+        let no_paths: FvDistribution<f64> =
+            bootstrap_fv(100.0, historical_returns.iter(), 5, 10, Resampling::Iid, 1);
+        let no_horizon: FvDistribution<f64> =
+            bootstrap_fv(100.0, historical_returns.iter(), 0, 10, Resampling::Iid, 1);
+
+        assert!(no_paths.future_values().is_empty());
+        assert!(no_paths.percentile(0.5).is_nan());
+        assert_eq!(no_horizon.percentile(0.5), 100.0);
+    }
+
+    #[test]
+    fn it_is_reproducible_with_a_seed() {
+        let historical_returns: Vec<f64> = vec![0.12, -0.08, 0.05, 0.21, -0.15];
+        let first: FvDistribution<f64> =
+            bootstrap_fv(100.0, historical_returns.iter(), 20, 50, Resampling::Iid, 7);
+        let second: FvDistribution<f64> =
+            bootstrap_fv(100.0, historical_returns.iter(), 20, 50, Resampling::Iid, 7);
+        let other_seed: FvDistribution<f64> =
+            bootstrap_fv(100.0, historical_returns.iter(), 20, 50, Resampling::Iid, 8);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other_seed);
+    }
+
+    #[test]
+    fn it_resamples_the_whole_history_as_one_block() {
+        // a block as long as the horizon is a rotation of the history, whose product does not depend on the start
+        let historical_returns: Vec<f64> = vec![0.10, -0.05, 0.20, 0.00];
+        let distribution: FvDistribution<f64> = bootstrap_fv(
+            100.0,
+            historical_returns.iter(),
+            4,
+            20,
+            Resampling::Block(4),
+            3,
+        );
+        let expected: f64 = 100.0 * 1.10 * 0.95 * 1.20;
+
+        for future_value in distribution.future_values() {
+            assert!((future_value - expected).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn it_interpolates_percentiles() {
+        let historical_returns: Vec<f32> = vec![0.0, 1.0];
+        let distribution: FvDistribution<f32> = bootstrap_fv(
+            1.0,
+            historical_returns.iter(),
+            1,
+            1_000,
+            Resampling::Iid,
+            11,
+        );
+
+        assert_eq!(distribution.percentile(0.0), 1.0);
+        assert_eq!(distribution.percentile(1.0), 2.0);
+        assert!((distribution.mean() - 1.5).abs() < 0.1);
+    }
+}
+
+#[cfg(test)]
+mod glidepath_fv_tests {
+    use crate::simulation::{glidepath_fv, FvDistribution, NormalReturn};
+
+    #[test]
+    fn it_is_deterministic_without_volatility() {
+        let glidepath: Vec<NormalReturn<f64>> =
+            vec![NormalReturn::new(0.10, 0.0), NormalReturn::new(0.05, 0.0)];
+        let distribution: FvDistribution<f64> = glidepath_fv(100.0, glidepath.iter(), 10, 1);
+
+        assert!((distribution.percentile(0.0) - 115.5).abs() < 0.000_001);
+        assert!((distribution.percentile(1.0) - 115.5).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn it_draws_from_each_period_distribution() {
+        // a single period's simulated returns should have about the requested mean and standard deviation
+        let glidepath: Vec<NormalReturn<f64>> = vec![NormalReturn::new(0.05, 0.10)];
+        let distribution: FvDistribution<f64> = glidepath_fv(1.0, glidepath.iter(), 20_000, 5);
+        let returns: Vec<f64> = distribution
+            .future_values()
+            .iter()
+            .map(|fv| fv - 1.0)
+            .collect();
+        let mean: f64 = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance: f64 =
+            returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>() / returns.len() as f64;
+
+        assert!((mean - 0.05).abs() < 0.005);
+        assert!((variance.sqrt() - 0.10).abs() < 0.005);
+    }
+}
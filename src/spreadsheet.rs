@@ -0,0 +1,263 @@
+//! Functions with the arguments and sign conventions of the financial functions of Excel and LibreOffice, to ease porting spreadsheets.
+//!
+//! As in a spreadsheet, money paid out is negative and money received is positive, `due` is `true` where a spreadsheet's `type` is 1, i.e. for payments at the start of each period, and dates are serial day numbers.
+//! Where a spreadsheet would return `#NUM!` these functions return NaN.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::annuity::{fv_with_growth, pmt_with_growth, pv_with_growth, rate_with_growth};
+use crate::cash_flows::scale;
+use crate::irr::root_finder::{solve_from_guess, SolveResult};
+use crate::power::powf;
+use crate::present_value::{from_cash_flows_with_convention, Convention};
+use crate::xirr::xirr_from_days;
+
+/// Searches outwards from `guess` for a root of the NPV `f` of amounts of the size of `scale` with `root_finder::solve_from_guess`, returning NaN if it fails.
+fn solve<T, F>(f: F, guess: T, scale: T) -> T
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+    F: Fn(T) -> T,
+{
+    valid_or_nan(solve_from_guess(f, guess, 100, scale))
+}
+
+fn valid_or_nan<T>(solved: SolveResult<T>) -> T
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    if solved.is_valid() {
+        solved.irr()
+    } else {
+        T::nan()
+    }
+}
+
+/// The growth `(1 + rate)^nper`, for a number of periods that need not be whole.
+fn growth<T>(rate: T, nper: T) -> T
+where
+    T: Float,
+{
+    powf(T::one() + rate, nper)
+}
+
+/// `NPV(rate, value1, value2, ...)`: the value one period before the first of `values`; see `Convention::Spreadsheet`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::npv;
+///
+/// let values: Vec<f64> = vec![-10_000.0, 3_000.0, 4_200.0, 6_800.0];
+/// assert!((npv(0.10, &values) - 1_188.44).abs() < 0.005);
+/// ```
+pub fn npv<T>(rate: T, values: &[T]) -> T
+where
+    T: Float + Product<T> + Sum<T>,
+{
+    from_cash_flows_with_convention(values.iter(), rate, Convention::Spreadsheet)
+}
+
+/// `IRR(values, guess)`: the IRR of cash flows at regular periods.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::irr;
+///
+/// let values: Vec<f64> = vec![-70_000.0, 12_000.0, 15_000.0, 18_000.0, 21_000.0, 26_000.0];
+/// assert!((irr(&values, 0.10) - 0.086_63).abs() < 0.000_01);
+/// ```
+pub fn irr<T>(values: &[T], guess: T) -> T
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    solve(
+        |rate| from_cash_flows_with_convention(values.iter(), rate, Convention::PeriodZero),
+        guess,
+        scale(values.iter()),
+    )
+}
+
+/// `XIRR(values, dates, guess)`: the annual IRR of cash flows on the serial day numbers `dates`, discounting over years of 365 days from the first date; see `xirr::xirr`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::xirr;
+///
+/// // 2008-01-01, 2008-03-01, 2008-10-30, 2009-02-15 and 2009-04-01
+/// let values: Vec<f64> = vec![-10_000.0, 2_750.0, 4_250.0, 3_250.0, 2_750.0];
+/// let dates: Vec<f64> = vec![39_448.0, 39_508.0, 39_751.0, 39_859.0, 39_904.0];
+/// assert!((xirr(&values, &dates, 0.10) - 0.373_362_5).abs() < 0.000_001);
+/// ```
+pub fn xirr<T>(values: &[T], dates: &[T], guess: T) -> T
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    if values.len() != dates.len() {
+        return T::nan();
+    }
+    let cash_flows: Vec<(T, T)> = dates.iter().copied().zip(values.iter().copied()).collect();
+    valid_or_nan(xirr_from_days(&cash_flows, guess))
+}
+
+/// `PV(rate, nper, pmt, fv, type)`: the present value of `nper` payments of `pmt` and a final value of `fv`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::pv;
+///
+/// // the cost of an annuity paying 500 a month for 20 years at 8% a year
+/// let value: f64 = pv(0.08 / 12.0, 240.0, 500.0, 0.0, false);
+/// assert!((value - -59_777.15).abs() < 0.005);
+/// ```
+pub fn pv<T>(rate: T, nper: T, pmt: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    pv_with_growth(rate, nper, growth(rate, nper), pmt, fv, due)
+}
+
+/// `FV(rate, nper, pmt, pv, type)`: the value after `nper` periods of `pv` and payments of `pmt`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::fv;
+///
+/// // 500 now and 200 at the start of each month for 10 months, at 6% a year
+/// let value: f64 = fv(0.06 / 12.0, 10.0, -200.0, -500.0, true);
+/// assert!((value - 2_581.40).abs() < 0.005);
+/// ```
+pub fn fv<T>(rate: T, nper: T, pmt: T, pv: T, due: bool) -> T
+where
+    T: Float,
+{
+    fv_with_growth(rate, nper, growth(rate, nper), pmt, pv, due)
+}
+
+/// `PMT(rate, nper, pv, fv, type)`: the level payment over `nper` periods that repays `pv` and leaves `fv`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::pmt;
+///
+/// // a loan of 10,000 over 10 months at 8% a year
+/// let payment: f64 = pmt(0.08 / 12.0, 10.0, 10_000.0, 0.0, false);
+/// assert!((payment - -1_037.03).abs() < 0.005);
+/// ```
+pub fn pmt<T>(rate: T, nper: T, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    pmt_with_growth(rate, nper, growth(rate, nper), pv, fv, due)
+}
+
+/// `NPER(rate, pmt, pv, fv, type)`: the number of payments of `pmt` that take `pv` to `fv`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::nper;
+///
+/// let periods: f64 = nper(0.12 / 12.0, -100.0, -1_000.0, 10_000.0, true);
+/// assert!((periods - 59.673_866).abs() < 0.000_001);
+/// ```
+pub fn nper<T>(rate: T, pmt: T, pv: T, fv: T, due: bool) -> T
+where
+    T: Float,
+{
+    if rate.is_zero() {
+        return -(pv + fv) / pmt;
+    }
+    let payment: T = if due { pmt * (T::one() + rate) } else { pmt };
+    ((payment - fv * rate) / (payment + pv * rate)).ln() / rate.ln_1p()
+}
+
+/// `RATE(nper, pmt, pv, fv, type, guess)`: the rate per period at which `nper` payments of `pmt` take `pv` to `fv`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::spreadsheet::rate;
+///
+/// // a loan of 8,000 repaid by 48 monthly payments of 200
+/// let monthly: f64 = rate(48.0, -200.0, 8_000.0, 0.0, false, 0.10);
+/// assert!((monthly - 0.007_701_5).abs() < 0.000_000_1);
+/// ```
+pub fn rate<T>(nper: T, pmt: T, pv: T, fv: T, due: bool, guess: T) -> T
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    valid_or_nan(rate_with_growth(
+        nper,
+        |rate| growth(rate, nper),
+        pmt,
+        pv,
+        fv,
+        due,
+        guess,
+    ))
+}
+
+#[cfg(test)]
+mod spreadsheet_tests {
+    use crate::spreadsheet::{fv, irr, nper, npv, pmt, pv, rate, xirr};
+
+    #[test]
+    fn it_round_trips_the_annuity_functions() {
+        for due in [false, true].iter() {
+            let payment: f64 = pmt(0.005, 360.0, 250_000.0, -10_000.0, *due);
+            assert!((pv(0.005, 360.0, payment, -10_000.0, *due) - 250_000.0).abs() < 0.000_001);
+            assert!((fv(0.005, 360.0, payment, 250_000.0, *due) - -10_000.0).abs() < 0.000_001);
+            assert!((nper(0.005, payment, 250_000.0, -10_000.0, *due) - 360.0).abs() < 0.000_001);
+            assert!(
+                (rate(360.0, payment, 250_000.0, -10_000.0, *due, 0.01) - 0.005).abs()
+                    < 0.000_000_1
+            );
+        }
+    }
+
+    #[test]
+    fn it_works_at_a_zero_rate() {
+        assert_eq!(pmt(0.0, 10.0, 1_000.0, 0.0, false), -100.0);
+        assert_eq!(pv(0.0, 10.0, -100.0, 0.0, true), 1_000.0);
+        assert_eq!(fv(0.0, 10.0, -100.0, 0.0, false), 1_000.0);
+        assert_eq!(nper(0.0, -100.0, 1_000.0, 0.0, false), 10.0);
+    }
+
+    #[test]
+    fn it_matches_npv_with_irr() {
+        let values: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        assert!(npv(irr(&values, 0.10), &values).abs() < 0.001);
+    }
+
+    #[test]
+    fn it_returns_nan_where_a_spreadsheet_returns_num() {
+        let values: Vec<f64> = vec![100.0, 60.0];
+        assert!(irr(&values, 0.10).is_nan());
+        assert!(xirr(&values, &[1.0], 0.10).is_nan());
+        assert!(xirr(&[] as &[f64], &[], 0.10).is_nan());
+    }
+
+    #[test]
+    fn it_accepts_a_guess_that_is_already_a_root() {
+        assert!((irr(&[-100.0, 110.0001], 0.10_f64) - 0.100_001).abs() < 0.000_000_001);
+        assert!(
+            (rate(1.0, 0.0, -100.0, 110.0001, false, 0.10_f64) - 0.100_001).abs() < 0.000_000_001
+        );
+        assert!(
+            (xirr(&[-100.0, 110.0001], &[0.0, 365.0], 0.10_f64) - 0.100_001).abs() < 0.000_000_001
+        );
+        assert_eq!(irr(&[-100.0, 110.0], 0.10), 0.10);
+    }
+
+    #[test]
+    fn it_does_not_depend_on_the_size_of_the_amounts() {
+        assert!((irr(&[-0.01, 0.0111], 0.10_f64) - 0.11).abs() < 0.000_000_001);
+        assert!((irr(&[-1.0e9, 1.11e9], 0.10_f64) - 0.11).abs() < 0.000_000_001);
+        assert!((irr(&[-0.01_f32, 0.0111], 0.10) - 0.11).abs() < 0.000_01);
+    }
+
+    #[test]
+    fn it_matches_irr_with_xirr_on_whole_years() {
+        let values: Vec<f64> = vec![-100.0, 60.0, 60.0];
+        let dates: Vec<f64> = vec![0.0, 365.0, 730.0];
+        assert!((xirr(&values, &dates, 0.10) - irr(&values, 0.10)).abs() < 0.000_001);
+    }
+}
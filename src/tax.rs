@@ -0,0 +1,201 @@
+//! Functions for the IRR of cash flows after investor-level taxes on distributions.
+
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::irr::bisection::functions::irr::with_hint;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::labeled_cash_flows::LabeledCashFlows;
+
+/// The tax treatment of a series of cash flows, for use as the label of a `LabeledCashFlows`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Distribution {
+    /// Capital invested by the investor, which is not taxed.
+    Contribution,
+    /// Income e.g. interest or dividends, taxed at the income rate.
+    Income,
+    /// Capital returned to the investor, which is not taxed.
+    ReturnOfCapital,
+    /// Realised gains, taxed at the gains rate.
+    Gain,
+}
+
+/// The investor-level tax rates applied to distributions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TaxRates<T>
+where
+    T: Float,
+{
+    income: T,
+    gains: T,
+}
+
+impl<T> TaxRates<T>
+where
+    T: Float,
+{
+    pub fn new(income: T, gains: T) -> TaxRates<T> {
+        TaxRates { income, gains }
+    }
+
+    pub fn income(&self) -> T {
+        self.income
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `income` instead")]
+    pub fn get_income(&self) -> T {
+        self.income()
+    }
+
+    pub fn gains(&self) -> T {
+        self.gains
+    }
+
+    #[deprecated(since = "0.9.0", note = "use `gains` instead")]
+    pub fn get_gains(&self) -> T {
+        self.gains()
+    }
+
+    /// The tax rate applied to a type of distribution.
+    pub fn rate(&self, distribution: Distribution) -> T {
+        match distribution {
+            Distribution::Income => self.income,
+            Distribution::Gain => self.gains,
+            Distribution::Contribution | Distribution::ReturnOfCapital => T::zero(),
+        }
+    }
+}
+
+/// Consolidates the cash flows of each type of distribution after tax, period by period.
+///
+/// # Comments
+/// Tax is paid in the period of the distribution, and only on positive cash flows: losses are assumed not to give rise to a tax credit.
+///
+/// # Example with f64
+/// ```
+/// use time_value::labeled_cash_flows::LabeledCashFlows;
+/// use time_value::tax::{after_tax, Distribution, TaxRates};
+///
+/// let mut cash_flows: LabeledCashFlows<Distribution, f64> = LabeledCashFlows::new();
+/// cash_flows.insert(Distribution::Contribution, vec![-100.0]);
+/// cash_flows.insert(Distribution::Income, vec![0.0, 10.0, 10.0]);
+/// cash_flows.insert(Distribution::ReturnOfCapital, vec![0.0, 0.0, 100.0]);
+/// cash_flows.insert(Distribution::Gain, vec![0.0, 0.0, 20.0]);
+///
+/// // income taxed at 40% and gains at 20%
+/// let rates: TaxRates<f64> = TaxRates::new(0.40, 0.20);
+/// assert_eq!(after_tax(&cash_flows, &rates), vec![-100.0, 6.0, 122.0]);
+/// ```
+pub fn after_tax<T>(cash_flows: &LabeledCashFlows<Distribution, T>, rates: &TaxRates<T>) -> Vec<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let mut taxed: LabeledCashFlows<Distribution, T> = LabeledCashFlows::new();
+    for distribution in [
+        Distribution::Contribution,
+        Distribution::Income,
+        Distribution::ReturnOfCapital,
+        Distribution::Gain,
+    ]
+    .iter()
+    {
+        if let Some(series) = cash_flows.get(distribution) {
+            let retained: T = T::one() - rates.rate(*distribution);
+            taxed.insert(
+                *distribution,
+                series
+                    .iter()
+                    .map(|cash_flow| {
+                        if T::zero() < *cash_flow {
+                            *cash_flow * retained
+                        } else {
+                            *cash_flow
+                        }
+                    })
+                    .collect(),
+            );
+        }
+    }
+    taxed.consolidated()
+}
+
+/// Calculates the IRR of the consolidated cash flows before and after tax, in that order.
+///
+/// # Comments
+/// Each IRR is found by searching for initial bounds outwards from `rate_guess` and then applying the bisection method, as in `LabeledCashFlows::irr_by_group`.
+///
+/// # Example with f64
+/// ```
+/// use time_value::labeled_cash_flows::LabeledCashFlows;
+/// use time_value::tax::{pre_and_post_tax_irr, Distribution, TaxRates};
+///
+/// let mut cash_flows: LabeledCashFlows<Distribution, f64> = LabeledCashFlows::new();
+/// cash_flows.insert(Distribution::Contribution, vec![-100.0]);
+/// cash_flows.insert(Distribution::Income, vec![0.0, 10.0, 10.0]);
+/// cash_flows.insert(Distribution::ReturnOfCapital, vec![0.0, 0.0, 100.0]);
+///
+/// let (pre_tax, post_tax) =
+///     pre_and_post_tax_irr(&cash_flows, &TaxRates::new(0.40, 0.20), 0.05, 100);
+/// assert!((pre_tax.irr() - 0.10).abs() < 0.000_1);
+/// assert!((post_tax.irr() - 0.06).abs() < 0.000_1);
+/// ```
+pub fn pre_and_post_tax_irr<T>(
+    cash_flows: &LabeledCashFlows<Distribution, T>,
+    rates: &TaxRates<T>,
+    rate_guess: T,
+    iteration_limit: u32,
+) -> (Irr<T>, Irr<T>)
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    let pre_tax: Vec<T> = cash_flows.consolidated();
+    let post_tax: Vec<T> = after_tax(cash_flows, rates);
+
+    (
+        with_hint(pre_tax.iter(), None, rate_guess, iteration_limit),
+        with_hint(post_tax.iter(), None, rate_guess, iteration_limit),
+    )
+}
+
+#[cfg(test)]
+mod after_tax_tests {
+    use crate::labeled_cash_flows::LabeledCashFlows;
+    use crate::tax::{after_tax, pre_and_post_tax_irr, Distribution, TaxRates};
+
+    #[test]
+    fn it_matches_the_consolidated_cash_flows_without_tax() {
+        let mut cash_flows: LabeledCashFlows<Distribution, f64> = LabeledCashFlows::new();
+        cash_flows.insert(Distribution::Contribution, vec![-100.0, -50.0]);
+        cash_flows.insert(Distribution::Gain, vec![0.0, 0.0, 30.0, -10.0]);
+        cash_flows.insert(Distribution::Income, vec![0.0, 5.0, 5.0, 5.0]);
+
+        assert_eq!(
+            after_tax(&cash_flows, &TaxRates::new(0.0, 0.0)),
+            cash_flows.consolidated()
+        );
+    }
+
+    #[test]
+    fn it_does_not_credit_losses() {
+        let mut cash_flows: LabeledCashFlows<Distribution, f32> = LabeledCashFlows::new();
+        cash_flows.insert(Distribution::Gain, vec![50.0, -20.0]);
+
+        assert_eq!(
+            after_tax(&cash_flows, &TaxRates::new(0.0, 0.5)),
+            vec![25.0, -20.0]
+        );
+    }
+
+    #[test]
+    fn it_lowers_the_irr_after_tax() {
+        let mut cash_flows: LabeledCashFlows<Distribution, f64> = LabeledCashFlows::new();
+        cash_flows.insert(Distribution::Contribution, vec![-100.0]);
+        cash_flows.insert(Distribution::ReturnOfCapital, vec![0.0, 50.0, 50.0]);
+        cash_flows.insert(Distribution::Gain, vec![0.0, 10.0, 20.0]);
+
+        let (pre_tax, post_tax) =
+            pre_and_post_tax_irr(&cash_flows, &TaxRates::new(0.40, 0.20), 0.05, 100);
+        assert!(pre_tax.is_valid() && post_tax.is_valid());
+        assert!(post_tax.irr() < pre_tax.irr());
+    }
+}
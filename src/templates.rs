@@ -0,0 +1,289 @@
+//! Functions for generating the cash flows of common deal structures.
+//!
+//! Every series is from the point of view of the investor: the outlay in period 0 is negative and the cash flows received afterwards are positive.
+
+use num::Float;
+use std::slice::Iter;
+
+use crate::power::powi;
+
+/// Generates the cash flows of a loan repaid with level payments, from the point of view of the lender.
+///
+/// # Example with f64
+/// ```
+/// use time_value::templates::level_payment_loan;
+/// use time_value::present_value::from_cash_flows_and_discount_rate as npv;
+///
+/// let cash_flows: Vec<f64> = level_payment_loan(1_000.0, 0.05, 3);
+/// assert_eq!(cash_flows[0], -1_000.0);
+/// assert!((cash_flows[1] - 367.21).abs() < 0.01);
+/// assert!(npv(cash_flows.iter(), 0.05).abs() < 0.000_001);
+/// ```
+pub fn level_payment_loan<T>(principal: T, rate: T, periods: usize) -> Vec<T>
+where
+    T: Float,
+{
+    let payment: T = if rate.is_zero() {
+        principal / T::from(periods).unwrap()
+    } else {
+        principal * rate / (T::one() - powi(T::one() + rate, -(periods as i32)))
+    };
+
+    let mut cash_flows: Vec<T> = vec![-principal];
+    cash_flows.extend(vec![payment; periods]);
+    cash_flows
+}
+
+/// Generates the cash flows of a bond bought at `price` that pays a fixed coupon every period and its face value at maturity.
+///
+/// # Example with f32
+/// ```
+/// use time_value::templates::bullet_bond;
+///
+/// let cash_flows: Vec<f32> = bullet_bond(100.0, 0.05, 3, 98.0);
+/// assert_eq!(cash_flows, vec![-98.0, 5.0, 5.0, 105.0]);
+/// ```
+pub fn bullet_bond<T>(face_value: T, coupon_rate: T, periods: usize, price: T) -> Vec<T>
+where
+    T: Float,
+{
+    let mut cash_flows: Vec<T> = vec![-price];
+    cash_flows.extend(vec![face_value * coupon_rate; periods]);
+    if 0 < periods {
+        cash_flows[periods] = cash_flows[periods] + face_value;
+    }
+    cash_flows
+}
+
+/// Generates the cash flows of a share bought at `price` that pays a dividend growing at a constant rate, for a finite number of periods.
+///
+/// # Example with f64
+/// ```
+/// use time_value::templates::growing_dividend_stream;
+///
+/// let cash_flows: Vec<f64> = growing_dividend_stream(50.0, 2.0, 0.10, 3);
+/// assert_eq!(cash_flows.len(), 4);
+/// assert_eq!(cash_flows[0], -50.0);
+/// assert!((cash_flows[3] - 2.42).abs() < 0.000_001);
+/// ```
+pub fn growing_dividend_stream<T>(price: T, first_dividend: T, growth: T, periods: usize) -> Vec<T>
+where
+    T: Float,
+{
+    let mut cash_flows: Vec<T> = vec![-price];
+    let mut dividend: T = first_dividend;
+    for _ in 0..periods {
+        cash_flows.push(dividend);
+        dividend = dividend * (T::one() + growth);
+    }
+    cash_flows
+}
+
+/// Generates the cash flows of an investment whose returns ramp up linearly to a steady state, as is typical of subscription businesses.
+///
+/// # Comments
+/// In ramp period `k` of `ramp_periods` the cash flow is `steady_state * k / ramp_periods`, after which `steady_state` is received for `steady_periods` periods.
+///
+/// # Example with f32
+/// ```
+/// use time_value::templates::ramp_then_steady_state;
+///
+/// let cash_flows: Vec<f32> = ramp_then_steady_state(100.0, 40.0, 4, 2);
+/// assert_eq!(cash_flows, vec![-100.0, 10.0, 20.0, 30.0, 40.0, 40.0, 40.0]);
+/// ```
+pub fn ramp_then_steady_state<T>(
+    investment: T,
+    steady_state: T,
+    ramp_periods: usize,
+    steady_periods: usize,
+) -> Vec<T>
+where
+    T: Float,
+{
+    let mut cash_flows: Vec<T> = vec![-investment];
+    for period in 1..=ramp_periods {
+        cash_flows.push(steady_state * T::from(period).unwrap() / T::from(ramp_periods).unwrap());
+    }
+    cash_flows.extend(vec![steady_state; steady_periods]);
+    cash_flows
+}
+
+/// Generates the real and nominal cash flows of an annuity whose payments are indexed to a consumer price index (CPI), bought at `price`.
+///
+/// # Comments
+/// `cpi` holds the index level for periods `0..=periods`, so `cpi[0]` is the base level at purchase.
+/// The payment in period `t` is `real_payment` in period 0 money, i.e. `real_payment * cpi[t] / cpi[0]` nominally.
+/// The real series suits discounting at a real rate and the nominal one at a nominal rate.
+///
+/// # Example with f64
+/// ```
+/// use time_value::templates::inflation_indexed_annuity;
+///
+/// let cpi: Vec<f64> = vec![100.0, 102.0, 104.04];
+/// let (real, nominal): (Vec<f64>, Vec<f64>) = inflation_indexed_annuity(1_000.0, cpi.iter(), 1_900.0);
+/// assert_eq!(real, vec![-1_900.0, 1_000.0, 1_000.0]);
+/// assert!((nominal[2] - 1_040.4).abs() < 0.000_001);
+/// ```
+pub fn inflation_indexed_annuity<T>(real_payment: T, cpi: Iter<T>, price: T) -> (Vec<T>, Vec<T>)
+where
+    T: Float,
+{
+    let index_ratios: Vec<T> = index_ratios(cpi);
+    let mut real: Vec<T> = vec![-price];
+    let mut nominal: Vec<T> = vec![-price];
+    for index_ratio in index_ratios.iter().skip(1) {
+        real.push(real_payment);
+        nominal.push(real_payment * *index_ratio);
+    }
+    (real, nominal)
+}
+
+/// Generates the real and nominal cash flows of an inflation-indexed bond, in the style of US TIPS, bought at `price`.
+///
+/// # Comments
+/// `cpi` holds the index level for periods `0..=periods`, so `cpi[0]` is the base level at purchase.
+/// The principal is indexed by `cpi[t] / cpi[0]` and pays `real_coupon_rate` of the indexed principal every period.
+/// At maturity the indexed principal is repaid, but never less than `face_value`, so cumulative deflation does not reduce the principal.
+///
+/// # Example with f64
+/// ```
+/// use time_value::templates::inflation_indexed_bond;
+///
+/// let cpi: Vec<f64> = vec![100.0, 103.0, 106.09];
+/// let (real, nominal): (Vec<f64>, Vec<f64>) =
+///     inflation_indexed_bond(100.0, 0.01, cpi.iter(), 100.0);
+/// assert!((real[2] - 101.0).abs() < 0.000_001);
+/// assert!((nominal[1] - 1.03).abs() < 0.000_001);
+/// assert!((nominal[2] - 107.150_9).abs() < 0.000_001);
+/// ```
+pub fn inflation_indexed_bond<T>(
+    face_value: T,
+    real_coupon_rate: T,
+    cpi: Iter<T>,
+    price: T,
+) -> (Vec<T>, Vec<T>)
+where
+    T: Float,
+{
+    let index_ratios: Vec<T> = index_ratios(cpi);
+    let maturity: usize = index_ratios.len().saturating_sub(1);
+    let mut real: Vec<T> = vec![-price];
+    let mut nominal: Vec<T> = vec![-price];
+    for (period, index_ratio) in index_ratios.iter().enumerate().skip(1) {
+        let mut payment: T = face_value * *index_ratio * real_coupon_rate;
+        if period == maturity {
+            payment = payment + face_value * index_ratio.max(T::one());
+        }
+        nominal.push(payment);
+        real.push(payment / *index_ratio);
+    }
+    (real, nominal)
+}
+
+/// The ratio of each CPI level to the first.
+fn index_ratios<T>(cpi: Iter<T>) -> Vec<T>
+where
+    T: Float,
+{
+    let base: T = cpi.as_slice().first().copied().unwrap_or_else(T::one);
+    cpi.map(|level| *level / base).collect()
+}
+
+#[cfg(test)]
+mod level_payment_loan_tests {
+    use crate::templates::level_payment_loan;
+
+    #[test]
+    fn it_works_at_a_zero_rate() {
+        let cash_flows: Vec<f64> = level_payment_loan(1_200.0, 0.0, 12);
+        assert_eq!(cash_flows.len(), 13);
+        assert!(cash_flows[1..].iter().all(|payment| *payment == 100.0));
+    }
+
+    #[test]
+    fn it_repays_the_principal() {
+        let cash_flows: Vec<f64> = level_payment_loan(250_000.0, 0.004, 360);
+        let mut balance: f64 = 250_000.0;
+        for payment in cash_flows[1..].iter() {
+            balance = balance * 1.004 - payment;
+        }
+        assert!(balance.abs() < 0.000_01);
+    }
+}
+
+#[cfg(test)]
+mod bullet_bond_tests {
+    use crate::irr::bisection::functions::irr::bisection as irr;
+    use crate::templates::bullet_bond;
+
+    #[test]
+    fn it_works_with_no_periods() {
+        let cash_flows: Vec<f64> = bullet_bond(100.0, 0.05, 0, 100.0);
+        assert_eq!(cash_flows, vec![-100.0]);
+    }
+
+    #[test]
+    fn it_yields_the_coupon_at_par() {
+        let cash_flows: Vec<f64> = bullet_bond(100.0, 0.06, 10, 100.0);
+        let calculated_irr = irr(cash_flows.iter(), 0.01, 0.20, 100);
+        assert!((calculated_irr.irr() - 0.06).abs() < 0.000_001);
+    }
+}
+
+#[cfg(test)]
+mod ramp_then_steady_state_tests {
+    use crate::templates::ramp_then_steady_state;
+
+    #[test]
+    fn it_works_without_a_ramp() {
+        let cash_flows: Vec<f32> = ramp_then_steady_state(100.0, 40.0, 0, 3);
+        assert_eq!(cash_flows, vec![-100.0, 40.0, 40.0, 40.0]);
+    }
+}
+
+#[cfg(test)]
+mod inflation_indexed_annuity_tests {
+    use crate::present_value::from_cash_flows_and_discount_rate as npv;
+    use crate::templates::inflation_indexed_annuity;
+
+    #[test]
+    fn it_works_without_a_cpi() {
+        let cpi: Vec<f64> = vec![];
+        let (real, nominal): (Vec<f64>, Vec<f64>) =
+            inflation_indexed_annuity(10.0, cpi.iter(), 50.0);
+        assert_eq!(real, vec![-50.0]);
+        assert_eq!(nominal, vec![-50.0]);
+    }
+
+    #[test]
+    fn it_values_the_same_in_real_and_nominal_terms() {
+        // with 3% inflation, a 2% real rate is a 5.06% nominal rate
+        let cpi: Vec<f64> = (0..=10).map(|t| 100.0 * 1.03_f64.powi(t)).collect();
+        let (real, nominal): (Vec<f64>, Vec<f64>) =
+            inflation_indexed_annuity(100.0, cpi.iter(), 900.0);
+        assert!((npv(real.iter(), 0.02) - npv(nominal.iter(), 0.0506)).abs() < 0.000_001);
+    }
+}
+
+#[cfg(test)]
+mod inflation_indexed_bond_tests {
+    use crate::templates::{bullet_bond, inflation_indexed_bond};
+
+    #[test]
+    fn it_is_a_bullet_bond_without_inflation() {
+        let cpi: Vec<f64> = vec![100.0; 4];
+        let (real, nominal): (Vec<f64>, Vec<f64>) =
+            inflation_indexed_bond(100.0, 0.02, cpi.iter(), 99.0);
+        assert_eq!(real, bullet_bond(100.0, 0.02, 3, 99.0));
+        assert_eq!(nominal, real);
+    }
+
+    #[test]
+    fn it_floors_the_principal_after_deflation() {
+        let cpi: Vec<f64> = vec![100.0, 99.0, 98.0];
+        let (_, nominal): (Vec<f64>, Vec<f64>) =
+            inflation_indexed_bond(100.0, 0.01, cpi.iter(), 100.0);
+        assert!((nominal[1] - 0.99).abs() < 0.000_001);
+        assert!((nominal[2] - 100.98).abs() < 0.000_001);
+    }
+}
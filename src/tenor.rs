@@ -0,0 +1,378 @@
+//! Lengths of time in days, weeks, months or years, e.g. 45D, 3M or 10Y, and their year fractions under a day-count convention.
+//!
+//! A tenor is held as a number of months and a number of days, since neither converts exactly into the other without a start date: a week is 7 days and a year is 12 months.
+//! With the `parse` feature tenors can be read from strings such as `"3M"` or `"1Y6M"`.
+
+use num::Float;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(feature = "parse")]
+use std::error::Error;
+#[cfg(feature = "parse")]
+use std::str::FromStr;
+
+/// The convention by which a tenor is converted into a fraction of a year.
+///
+/// # Comments
+/// Without a start date a month cannot be counted in actual days, so the actual conventions count a month as 365 / 12 days, i.e. a twelfth of a 365-day year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayCount {
+    /// Actual days over a year of 365 days.
+    Actual365Fixed,
+    /// Actual days over a year of 360 days, so a year of 12 months is 365 / 360 of a year.
+    Actual360,
+    /// Months of 30 days over a year of 360 days.
+    Thirty360,
+}
+
+impl DayCount {
+    /// The number of days this convention counts in a month.
+    fn days_per_month<T>(&self) -> T
+    where
+        T: Float,
+    {
+        match self {
+            DayCount::Actual365Fixed | DayCount::Actual360 => {
+                T::from(365.0).unwrap() / T::from(12.0).unwrap()
+            }
+            DayCount::Thirty360 => T::from(30.0).unwrap(),
+        }
+    }
+
+    /// The number of days this convention counts in a year.
+    fn days_per_year<T>(&self) -> T
+    where
+        T: Float,
+    {
+        match self {
+            DayCount::Actual365Fixed => T::from(365.0).unwrap(),
+            DayCount::Actual360 | DayCount::Thirty360 => T::from(360.0).unwrap(),
+        }
+    }
+}
+
+/// A length of time of a number of months and a number of days, either of which can be negative.
+///
+/// # Example
+/// ```
+/// use time_value::tenor::{DayCount, Tenor};
+///
+/// let tenor: Tenor = Tenor::from_years(1) + Tenor::from_months(6);
+/// assert_eq!(tenor, Tenor::from_months(18));
+/// assert_eq!(format!("{}", tenor), "1Y6M");
+/// assert_eq!(format!("{}", Tenor::from_weeks(2) * 3), "42D");
+///
+/// let year_fraction: f64 = tenor.year_fraction(DayCount::Thirty360);
+/// assert_eq!(year_fraction, 1.5);
+/// let year_fraction: f64 = Tenor::from_days(45).year_fraction(DayCount::Actual360);
+/// assert_eq!(year_fraction, 0.125);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tenor {
+    months: i32,
+    days: i32,
+}
+
+impl Tenor {
+    pub fn new(months: i32, days: i32) -> Tenor {
+        Tenor { months, days }
+    }
+
+    pub fn from_days(days: i32) -> Tenor {
+        Tenor::new(0, days)
+    }
+
+    pub fn from_weeks(weeks: i32) -> Tenor {
+        Tenor::new(0, 7 * weeks)
+    }
+
+    pub fn from_months(months: i32) -> Tenor {
+        Tenor::new(months, 0)
+    }
+
+    pub fn from_years(years: i32) -> Tenor {
+        Tenor::new(12 * years, 0)
+    }
+
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.months == 0 && self.days == 0
+    }
+
+    /// The fraction of a year this tenor spans under `day_count`.
+    pub fn year_fraction<T>(&self, day_count: DayCount) -> T
+    where
+        T: Float,
+    {
+        let days: T = T::from(self.months).unwrap() * day_count.days_per_month()
+            + T::from(self.days).unwrap();
+        days / day_count.days_per_year()
+    }
+}
+
+impl Add for Tenor {
+    type Output = Tenor;
+
+    fn add(self, other: Tenor) -> Tenor {
+        Tenor::new(self.months + other.months, self.days + other.days)
+    }
+}
+
+impl Sub for Tenor {
+    type Output = Tenor;
+
+    fn sub(self, other: Tenor) -> Tenor {
+        Tenor::new(self.months - other.months, self.days - other.days)
+    }
+}
+
+impl Neg for Tenor {
+    type Output = Tenor;
+
+    fn neg(self) -> Tenor {
+        Tenor::new(-self.months, -self.days)
+    }
+}
+
+impl Mul<i32> for Tenor {
+    type Output = Tenor;
+
+    fn mul(self, multiple: i32) -> Tenor {
+        Tenor::new(self.months * multiple, self.days * multiple)
+    }
+}
+
+/// Whole years, the remaining months and then days, omitting zero components, e.g. `1Y6M`, `45D` or `-3M`; a zero tenor is `0D`.
+///
+/// A tenor whose months and days have opposite signs has a sign on each component, e.g. `1M-5D`, and cannot be parsed back.
+impl Display for Tenor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.is_zero() {
+            return write!(f, "0D");
+        }
+        let tenor: Tenor = if self.months <= 0 && self.days <= 0 {
+            write!(f, "-")?;
+            -*self
+        } else {
+            *self
+        };
+        let (years, months): (i32, i32) = (tenor.months / 12, tenor.months % 12);
+        if years != 0 {
+            write!(f, "{}Y", years)?;
+        }
+        if months != 0 {
+            write!(f, "{}M", months)?;
+        }
+        if tenor.days != 0 {
+            write!(f, "{}D", tenor.days)?;
+        }
+        Ok(())
+    }
+}
+
+/// The reason a string is not a tenor.
+#[cfg(feature = "parse")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseTenorError {
+    /// The string is empty or only whitespace.
+    Empty,
+    /// A unit is not preceded by a number.
+    MissingNumber,
+    /// A number is not followed by a unit.
+    MissingUnit,
+    /// A unit is not one of `D`, `W`, `M` or `Y`.
+    UnknownUnit(char),
+    /// A number does not fit in an `i32`.
+    Overflow,
+}
+
+#[cfg(feature = "parse")]
+impl Display for ParseTenorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ParseTenorError::Empty => write!(f, "the tenor is empty"),
+            ParseTenorError::MissingNumber => write!(f, "expected a number before each unit"),
+            ParseTenorError::MissingUnit => {
+                write!(f, "expected a unit of D, W, M or Y after each number")
+            }
+            ParseTenorError::UnknownUnit(unit) => {
+                write!(f, "unknown unit '{}', expected D, W, M or Y", unit)
+            }
+            ParseTenorError::Overflow => write!(f, "the tenor is too long"),
+        }
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Error for ParseTenorError {}
+
+/// Parses one or more numbers each followed by a unit of `D`, `W`, `M` or `Y`, in either case and with an optional leading sign that applies to the whole tenor, e.g. `"3M"`, `"1y6m"` or `"-2W"`.
+///
+/// # Comments
+/// Whitespace is allowed between a number and its unit and between components, e.g. `"3 M"` or `"1Y 6M"`, but not within a number.
+///
+/// # Example
+/// ```
+/// use time_value::tenor::{ParseTenorError, Tenor};
+///
+/// assert_eq!("18M".parse(), Ok(Tenor::from_months(18)));
+/// assert_eq!(" 1y6m ".parse(), Ok(Tenor::from_months(18)));
+/// assert_eq!("1 Y 6 M".parse(), Ok(Tenor::from_months(18)));
+/// assert_eq!("-2W".parse(), Ok(Tenor::from_days(-14)));
+/// assert_eq!("3Q".parse::<Tenor>(), Err(ParseTenorError::UnknownUnit('Q')));
+/// ```
+#[cfg(feature = "parse")]
+impl FromStr for Tenor {
+    type Err = ParseTenorError;
+
+    fn from_str(s: &str) -> Result<Tenor, ParseTenorError> {
+        let trimmed: &str = s.trim();
+        let (sign, body): (i32, &str) = match trimmed.chars().next() {
+            Some('-') => (-1, &trimmed[1..]),
+            Some('+') => (1, &trimmed[1..]),
+            _ => (1, trimmed),
+        };
+        if body.is_empty() {
+            return Err(ParseTenorError::Empty);
+        }
+
+        let mut tenor: Tenor = Tenor::default();
+        let mut number: Option<i32> = None;
+        let mut awaiting_unit: bool = false;
+        for character in body.chars() {
+            if character.is_whitespace() {
+                // a number followed by whitespace must be followed by its unit, not more digits
+                awaiting_unit = number.is_some();
+                continue;
+            }
+            if let Some(digit) = character.to_digit(10) {
+                if awaiting_unit {
+                    return Err(ParseTenorError::MissingUnit);
+                }
+                number = Some(
+                    number
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|number| number.checked_add(digit as i32))
+                        .ok_or(ParseTenorError::Overflow)?,
+                );
+                continue;
+            }
+            awaiting_unit = false;
+            let count: i32 = number.take().ok_or(ParseTenorError::MissingNumber)?;
+            let (months, days): (i32, i32) = match character.to_ascii_uppercase() {
+                'D' => (0, count),
+                'W' => (0, count.checked_mul(7).ok_or(ParseTenorError::Overflow)?),
+                'M' => (count, 0),
+                'Y' => (count.checked_mul(12).ok_or(ParseTenorError::Overflow)?, 0),
+                _ => return Err(ParseTenorError::UnknownUnit(character)),
+            };
+            tenor = Tenor::new(
+                tenor
+                    .months
+                    .checked_add(months)
+                    .ok_or(ParseTenorError::Overflow)?,
+                tenor
+                    .days
+                    .checked_add(days)
+                    .ok_or(ParseTenorError::Overflow)?,
+            );
+        }
+        if number.is_some() {
+            return Err(ParseTenorError::MissingUnit);
+        }
+        Ok(tenor * sign)
+    }
+}
+
+#[cfg(test)]
+mod tenor_tests {
+    use crate::tenor::{DayCount, Tenor};
+
+    #[test]
+    fn it_does_arithmetic_by_component() {
+        let tenor: Tenor = Tenor::from_months(3) + Tenor::from_days(45) - Tenor::from_weeks(1);
+        assert_eq!(tenor, Tenor::new(3, 38));
+        assert_eq!(-tenor, Tenor::new(-3, -38));
+        assert_eq!(tenor * 2, Tenor::new(6, 76));
+        assert!((tenor - tenor).is_zero());
+    }
+
+    #[test]
+    fn it_converts_to_year_fractions() {
+        let tenor: Tenor = Tenor::from_years(10);
+        assert_eq!(tenor.year_fraction::<f64>(DayCount::Actual365Fixed), 10.0);
+        assert_eq!(tenor.year_fraction::<f64>(DayCount::Thirty360), 10.0);
+        assert!((tenor.year_fraction::<f64>(DayCount::Actual360) - 3_650.0 / 360.0).abs() < 1e-12);
+
+        let tenor: Tenor = Tenor::from_days(73);
+        assert_eq!(tenor.year_fraction::<f32>(DayCount::Actual365Fixed), 0.2);
+        assert_eq!(
+            tenor.year_fraction::<f32>(DayCount::Thirty360),
+            73.0 / 360.0
+        );
+    }
+
+    #[test]
+    fn it_displays_years_months_and_days() {
+        assert_eq!(format!("{}", Tenor::from_months(3)), "3M");
+        assert_eq!(format!("{}", Tenor::from_months(24)), "2Y");
+        assert_eq!(format!("{}", Tenor::new(14, 10)), "1Y2M10D");
+        assert_eq!(format!("{}", -Tenor::from_months(18)), "-1Y6M");
+        assert_eq!(format!("{}", Tenor::default()), "0D");
+    }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod parse_tests {
+    use crate::tenor::{ParseTenorError, Tenor};
+
+    #[test]
+    fn it_round_trips_through_display() {
+        let tenors: Vec<Tenor> = vec![
+            Tenor::from_months(3),
+            Tenor::from_months(18),
+            Tenor::from_years(10),
+            Tenor::from_days(45),
+            Tenor::new(14, 10),
+            -Tenor::from_months(18),
+        ];
+        for tenor in tenors {
+            assert_eq!(format!("{}", tenor).parse(), Ok(tenor));
+        }
+    }
+
+    #[test]
+    fn it_skips_whitespace_between_numbers_and_units() {
+        assert_eq!("3 M".parse(), Ok(Tenor::from_months(3)));
+        assert_eq!("-1Y\t6 m".parse(), Ok(Tenor::from_months(-18)));
+        assert_eq!("2 W 1 D".parse(), Ok(Tenor::from_days(15)));
+    }
+
+    #[test]
+    fn it_rejects_malformed_tenors() {
+        assert_eq!("".parse::<Tenor>(), Err(ParseTenorError::Empty));
+        assert_eq!(" - ".parse::<Tenor>(), Err(ParseTenorError::Empty));
+        assert_eq!("M".parse::<Tenor>(), Err(ParseTenorError::MissingNumber));
+        assert_eq!("3M6".parse::<Tenor>(), Err(ParseTenorError::MissingUnit));
+        assert_eq!("3 6M".parse::<Tenor>(), Err(ParseTenorError::MissingUnit));
+        assert_eq!(
+            "3 _".parse::<Tenor>(),
+            Err(ParseTenorError::UnknownUnit('_'))
+        );
+        assert_eq!(
+            "99999999999D".parse::<Tenor>(),
+            Err(ParseTenorError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Canonical test vectors for NPV, IRR and PMT, and a runner to check any implementation against them, e.g. a port of this crate to another language.
+//!
+//! The vectors are published as JSON in `test_vectors.json` at the root of the repository, which the tests check against `vectors` with the `serde` feature.
+//! To regenerate it after changing `vectors`, run `TIME_VALUE_BLESS=1 cargo test --features serde test_vectors`.
+//!
+//! Amounts are `f64` and the conventions are those of this crate:
+//! - NPVs are valued at the period of the first cash flow; see `present_value::from_cash_flows_and_discount_rate`
+//! - payments follow the sign conventions of a spreadsheet; see `annuity::pmt`
+
+use crate::annuity::pmt;
+use crate::present_value::from_cash_flows_and_discount_rate as pv;
+use crate::spreadsheet::irr;
+
+/// The function a test vector exercises, and its inputs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "function", rename_all = "snake_case"))]
+pub enum Case {
+    Npv {
+        cash_flows: Vec<f64>,
+        rate: f64,
+    },
+    Irr {
+        cash_flows: Vec<f64>,
+    },
+    Pmt {
+        rate: f64,
+        nper: u32,
+        pv: f64,
+        fv: f64,
+        due: bool,
+    },
+}
+
+/// A named case and the value expected from it, to within an absolute tolerance.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestVector {
+    name: String,
+    case: Case,
+    expected: f64,
+    tolerance: f64,
+}
+
+impl TestVector {
+    pub fn new(name: &str, case: Case, expected: f64, tolerance: f64) -> TestVector {
+        TestVector {
+            name: name.to_string(),
+            case,
+            expected,
+            tolerance,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn case(&self) -> &Case {
+        &self.case
+    }
+
+    pub fn expected(&self) -> f64 {
+        self.expected
+    }
+
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Whether `actual` is within the tolerance of the expected value.
+    pub fn accepts(&self, actual: f64) -> bool {
+        (actual - self.expected).abs() <= self.tolerance
+    }
+}
+
+/// The functions a set of test vectors is run against.
+pub trait Implementation {
+    fn npv(&self, cash_flows: &[f64], rate: f64) -> f64;
+
+    fn irr(&self, cash_flows: &[f64]) -> f64;
+
+    fn pmt(&self, rate: f64, nper: u32, pv: f64, fv: f64, due: bool) -> f64;
+}
+
+/// The implementation in this crate, against which the vectors are validated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reference;
+
+impl Implementation for Reference {
+    fn npv(&self, cash_flows: &[f64], rate: f64) -> f64 {
+        pv(cash_flows.iter(), rate)
+    }
+
+    /// The IRR from `spreadsheet::irr` with a guess of 10%.
+    fn irr(&self, cash_flows: &[f64]) -> f64 {
+        irr(cash_flows, 0.10)
+    }
+
+    fn pmt(&self, rate: f64, nper: u32, pv: f64, fv: f64, due: bool) -> f64 {
+        pmt(rate, nper, pv, fv, due)
+    }
+}
+
+/// The result of running an implementation on a test vector.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome<'a> {
+    test_vector: &'a TestVector,
+    actual: f64,
+}
+
+impl<'a> Outcome<'a> {
+    pub fn test_vector(&self) -> &'a TestVector {
+        self.test_vector
+    }
+
+    pub fn actual(&self) -> f64 {
+        self.actual
+    }
+
+    pub fn is_pass(&self) -> bool {
+        self.test_vector.accepts(self.actual)
+    }
+}
+
+/// The canonical test vectors.
+///
+/// # Comments
+/// The expected values were calculated independently in 40-digit arithmetic and rounded to 17 significant digits.
+pub fn vectors() -> Vec<TestVector> {
+    let npv = |name: &str, cash_flows: &[f64], rate: f64, expected: f64| {
+        TestVector::new(
+            name,
+            Case::Npv {
+                cash_flows: cash_flows.to_vec(),
+                rate,
+            },
+            expected,
+            1e-9,
+        )
+    };
+    let irr = |name: &str, cash_flows: &[f64], expected: f64| {
+        TestVector::new(
+            name,
+            Case::Irr {
+                cash_flows: cash_flows.to_vec(),
+            },
+            expected,
+            1e-9,
+        )
+    };
+    let pmt = |name: &str, rate: f64, nper: u32, pv: f64, fv: f64, due: bool, expected: f64| {
+        TestVector::new(
+            name,
+            Case::Pmt {
+                rate,
+                nper,
+                pv,
+                fv,
+                due,
+            },
+            expected,
+            1e-9,
+        )
+    };
+    let bond: Vec<f64> = vec![
+        -1_000.0, 50.0, 50.0, 50.0, 50.0, 50.0, 50.0, 50.0, 50.0, 50.0, 1_050.0,
+    ];
+
+    vec![
+        npv(
+            "npv_two_payments",
+            &[-100.0, 60.0, 60.0],
+            0.10,
+            4.132_231_404_958_677,
+        ),
+        npv(
+            "npv_uneven_payments",
+            &[-10_000.0, 3_000.0, 4_200.0, 6_800.0],
+            0.10,
+            1_307.287_753_568_745_2,
+        ),
+        npv("npv_zero_rate", &[-100.0, 60.0, 60.0], 0.0, 20.0),
+        npv(
+            "npv_negative_rate",
+            &[-100.0, 60.0, 60.0],
+            -0.05,
+            29.639_889_196_675_9,
+        ),
+        npv("npv_bond_below_coupon", &bond, 0.04, 81.108_957_793_550_28),
+        npv(
+            "npv_leading_zeros_at_irr",
+            &[0.0, 0.0, -100.0, 110.0],
+            0.10,
+            0.0,
+        ),
+        irr(
+            "irr_two_payments",
+            &[-100.0, 60.0, 60.0],
+            0.130_662_386_291_807_5,
+        ),
+        irr(
+            "irr_uneven_payments",
+            &[-70_000.0, 12_000.0, 15_000.0, 18_000.0, 21_000.0, 26_000.0],
+            0.086_630_948_036_531_61,
+        ),
+        irr(
+            "irr_single_inflow",
+            &[-100.0, 0.0, 0.0, 121.0],
+            0.065_602_236_766_610_71,
+        ),
+        irr(
+            "irr_negative",
+            &[-100.0, 50.0, 40.0],
+            -0.069_926_474_563_227_83,
+        ),
+        irr("irr_borrowing", &[100.0, -125.0], 0.25),
+        irr("irr_bond_at_par", &bond, 0.05),
+        irr(
+            "irr_long_wait",
+            &[-100.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 300.0],
+            0.116_123_174_033_904_43,
+        ),
+        pmt(
+            "pmt_mortgage",
+            0.005,
+            360,
+            200_000.0,
+            0.0,
+            false,
+            -1_199.101_050_305_504_8,
+        ),
+        pmt(
+            "pmt_monthly_from_annual",
+            0.08 / 12.0,
+            10,
+            10_000.0,
+            0.0,
+            false,
+            -1_037.032_089_359_152_2,
+        ),
+        pmt(
+            "pmt_savings_in_advance",
+            0.05,
+            10,
+            0.0,
+            10_000.0,
+            true,
+            -757.186_428_242_444_7,
+        ),
+        pmt("pmt_zero_rate", 0.0, 10, 1_000.0, 0.0, false, -100.0),
+        pmt(
+            "pmt_balloon_in_advance",
+            0.01,
+            12,
+            1_000.0,
+            -500.0,
+            true,
+            -48.935_043_900_169_16,
+        ),
+    ]
+}
+
+/// Runs an implementation on each of `test_vectors`, e.g. from `vectors` or parsed from `test_vectors.json`.
+///
+/// # Example
+/// ```
+/// use time_value::test_vectors::{run, vectors, Implementation, Outcome, TestVector};
+///
+/// // an implementation that forgets to discount
+/// struct Undiscounted;
+///
+/// impl Implementation for Undiscounted {
+///     fn npv(&self, cash_flows: &[f64], _rate: f64) -> f64 {
+///         cash_flows.iter().sum()
+///     }
+///
+///     fn irr(&self, _cash_flows: &[f64]) -> f64 {
+///         0.0
+///     }
+///
+///     fn pmt(&self, _rate: f64, nper: u32, pv: f64, fv: f64, _due: bool) -> f64 {
+///         -(pv + fv) / nper as f64
+///     }
+/// }
+///
+/// let test_vectors: Vec<TestVector> = vectors();
+/// let outcomes: Vec<Outcome> = run(&Undiscounted, &test_vectors);
+/// let passed: Vec<&str> = outcomes
+///     .iter()
+///     .filter(|outcome| outcome.is_pass())
+///     .map(|outcome| outcome.test_vector().name())
+///     .collect();
+/// assert_eq!(passed, vec!["npv_zero_rate", "pmt_zero_rate"]);
+/// ```
+pub fn run<'a, I>(implementation: &I, test_vectors: &'a [TestVector]) -> Vec<Outcome<'a>>
+where
+    I: Implementation,
+{
+    test_vectors
+        .iter()
+        .map(|test_vector| {
+            let actual: f64 = match test_vector.case() {
+                Case::Npv { cash_flows, rate } => implementation.npv(cash_flows, *rate),
+                Case::Irr { cash_flows } => implementation.irr(cash_flows),
+                Case::Pmt {
+                    rate,
+                    nper,
+                    pv,
+                    fv,
+                    due,
+                } => implementation.pmt(*rate, *nper, *pv, *fv, *due),
+            };
+            Outcome {
+                test_vector,
+                actual,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_vectors_tests {
+    use crate::test_vectors::{run, vectors, Reference, TestVector};
+    use std::collections::HashSet;
+
+    #[test]
+    fn it_passes_the_reference_implementation() {
+        let test_vectors: Vec<TestVector> = vectors();
+        for outcome in run(&Reference, &test_vectors) {
+            assert!(
+                outcome.is_pass(),
+                "{} gave {}",
+                outcome.test_vector().name(),
+                outcome.actual()
+            );
+        }
+    }
+
+    #[test]
+    fn it_names_every_vector_uniquely() {
+        let test_vectors: Vec<TestVector> = vectors();
+        let names: HashSet<&str> = test_vectors
+            .iter()
+            .map(|test_vector| test_vector.name())
+            .collect();
+        assert_eq!(names.len(), test_vectors.len());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use crate::test_vectors::{vectors, TestVector};
+
+    #[test]
+    fn it_matches_the_published_file() {
+        let path: String = format!("{}/test_vectors.json", env!("CARGO_MANIFEST_DIR"));
+        let generated: String = serde_json::to_string_pretty(&vectors()).unwrap() + "\n";
+        if std::env::var_os("TIME_VALUE_BLESS").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        let published: String = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(published, generated);
+
+        let parsed: Vec<TestVector> = serde_json::from_str(&published).unwrap();
+        assert_eq!(parsed, vectors());
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers for recording values in spans and events, behind the `tracing` feature.
+
+use num::Float;
+
+/// Converts a value to `f64` for recording, as `T` need not implement `Display` or `Debug`.
+pub(crate) fn value<T>(value: T) -> f64
+where
+    T: Float,
+{
+    value.to_f64().unwrap_or(f64::NAN)
+}
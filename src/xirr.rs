@@ -0,0 +1,265 @@
+//! The NPV and IRR of cash flows on irregular dates, with the semantics of a spreadsheet's `XNPV` and `XIRR`.
+//!
+//! Dates are day numbers, e.g. the serial day numbers of a spreadsheet or the days since any other epoch, or, with the `chrono` feature, `chrono::NaiveDate`s.
+//! Each cash flow is discounted over the years of 365 days from the date of the first cash flow, i.e. actual/365 fixed; see `tenor::DayCount::Actual365Fixed`.
+
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
+use num::{Float, Signed};
+use std::iter::{Product, Sum};
+
+use crate::cash_flows::scale;
+#[cfg(feature = "chrono")]
+use crate::dated::day_numbers;
+use crate::irr::bisection::structs::failure_reason::FailureReason;
+use crate::irr::bisection::structs::irr::Irr;
+use crate::irr::root_finder::solve_from_guess;
+use crate::power::powf;
+
+/// The number of days per year over which cash flows are discounted.
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// The value at the date of the first cash flow of `(day, amount)` pairs, discounted at the annual `rate`.
+///
+/// # Comments
+/// The cash flows need not be in order of date, but the first is the one valued at; one dated before it is compounded to its date.
+///
+/// # Example with f64
+/// ```
+/// use time_value::xirr::xnpv;
+///
+/// // 2008-01-01, 2008-03-01, 2008-10-30, 2009-02-15 and 2009-04-01
+/// let cash_flows: Vec<(i32, f64)> = vec![
+///     (39_448, -10_000.0),
+///     (39_508, 2_750.0),
+///     (39_751, 4_250.0),
+///     (39_859, 3_250.0),
+///     (39_904, 2_750.0),
+/// ];
+/// assert!((xnpv(&cash_flows, 0.09) - 2_086.65).abs() < 0.005);
+/// ```
+pub fn xnpv<T>(cash_flows: &[(i32, T)], rate: T) -> T
+where
+    T: Float + Sum<T>,
+{
+    npv_from_days(&days_and_amounts(cash_flows), rate)
+}
+
+/// The annual IRR of `(day, amount)` pairs, i.e. the rate at which `xnpv` is zero, searching outwards from `guess`.
+///
+/// # Comments
+/// The rate is found with Brent's method to within an NPV tolerance relative to the largest cash flow, as by `spreadsheet::xirr`.
+/// There is no IRR without cash flows, or unless there is at least one positive and one negative cash flow, in which case the result is not valid.
+///
+/// # Example with f64
+/// ```
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::xirr::xirr;
+///
+/// // 2008-01-01, 2008-03-01, 2008-10-30, 2009-02-15 and 2009-04-01
+/// let cash_flows: Vec<(i32, f64)> = vec![
+///     (39_448, -10_000.0),
+///     (39_508, 2_750.0),
+///     (39_751, 4_250.0),
+///     (39_859, 3_250.0),
+///     (39_904, 2_750.0),
+/// ];
+/// let calculated_irr: Irr<f64> = xirr(&cash_flows, 0.10);
+/// assert!(calculated_irr.is_valid());
+/// assert!((calculated_irr.irr() - 0.373_362_5).abs() < 0.000_001);
+/// ```
+pub fn xirr<T>(cash_flows: &[(i32, T)], guess: T) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    xirr_from_days(&days_and_amounts(cash_flows), guess)
+}
+
+/// `xnpv` for cash flows on dates, behind the `chrono` feature.
+///
+/// # Example with f64
+/// ```
+/// use chrono::NaiveDate;
+/// use time_value::xirr::xnpv_from_dates;
+///
+/// let date = |year: i32, month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+/// let cash_flows: Vec<(NaiveDate, f64)> = vec![
+///     (date(2008, 1, 1), -10_000.0),
+///     (date(2008, 3, 1), 2_750.0),
+///     (date(2008, 10, 30), 4_250.0),
+///     (date(2009, 2, 15), 3_250.0),
+///     (date(2009, 4, 1), 2_750.0),
+/// ];
+/// assert!((xnpv_from_dates(&cash_flows, 0.09) - 2_086.65).abs() < 0.005);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn xnpv_from_dates<T>(cash_flows: &[(NaiveDate, T)], rate: T) -> T
+where
+    T: Float + Sum<T>,
+{
+    xnpv(&day_numbers(cash_flows), rate)
+}
+
+/// `xirr` for cash flows on dates, behind the `chrono` feature.
+///
+/// # Example with f64
+/// ```
+/// use chrono::NaiveDate;
+/// use time_value::irr::bisection::structs::irr::Irr;
+/// use time_value::xirr::xirr_from_dates;
+///
+/// let date = |year: i32, month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+/// let cash_flows: Vec<(NaiveDate, f64)> = vec![
+///     (date(2008, 1, 1), -10_000.0),
+///     (date(2008, 3, 1), 2_750.0),
+///     (date(2008, 10, 30), 4_250.0),
+///     (date(2009, 2, 15), 3_250.0),
+///     (date(2009, 4, 1), 2_750.0),
+/// ];
+/// let calculated_irr: Irr<f64> = xirr_from_dates(&cash_flows, 0.10);
+/// assert!((calculated_irr.irr() - 0.373_362_5).abs() < 0.000_001);
+/// ```
+#[cfg(feature = "chrono")]
+pub fn xirr_from_dates<T>(cash_flows: &[(NaiveDate, T)], guess: T) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    xirr(&day_numbers(cash_flows), guess)
+}
+
+/// `xirr` for days as `T`, which need not be whole, e.g. for `spreadsheet::xirr`.
+pub(crate) fn xirr_from_days<T>(cash_flows: &[(T, T)], guess: T) -> Irr<T>
+where
+    T: Float + Product<T> + Sum<T> + Signed,
+{
+    if cash_flows.is_empty() {
+        return Irr::new(
+            guess,
+            T::nan(),
+            guess,
+            T::nan(),
+            0,
+            0,
+            T::nan(),
+            T::nan(),
+            false,
+        )
+        .with_failure_reason(FailureReason::BracketNotFound);
+    }
+    let amounts: Vec<T> = cash_flows.iter().map(|(_, amount)| *amount).collect();
+    solve_from_guess(
+        |rate| npv_from_days(cash_flows, rate),
+        guess,
+        100,
+        scale(amounts.iter()),
+    )
+}
+
+fn days_and_amounts<T>(cash_flows: &[(i32, T)]) -> Vec<(T, T)>
+where
+    T: Float,
+{
+    cash_flows
+        .iter()
+        .map(|(day, amount)| (T::from(*day).unwrap(), *amount))
+        .collect()
+}
+
+fn npv_from_days<T>(cash_flows: &[(T, T)], rate: T) -> T
+where
+    T: Float + Sum<T>,
+{
+    let first_day: T = match cash_flows.first() {
+        Some((day, _)) => *day,
+        None => return T::zero(),
+    };
+    let days_per_year: T = T::from(DAYS_PER_YEAR).unwrap();
+    cash_flows
+        .iter()
+        .map(|(day, amount)| *amount / powf(T::one() + rate, (*day - first_day) / days_per_year))
+        .sum()
+}
+
+#[cfg(test)]
+mod xirr_tests {
+    use crate::irr::bisection::structs::failure_reason::FailureReason;
+    use crate::irr::bisection::structs::irr::Irr;
+    use crate::present_value::from_cash_flows_and_discount_rate as pv;
+    use crate::spreadsheet;
+    use crate::xirr::{xirr, xnpv};
+
+    #[test]
+    fn it_matches_the_irr_on_whole_years() {
+        let cash_flows: Vec<(i32, f64)> = vec![(0, -100.0), (365, 60.0), (730, 60.0)];
+        let calculated_irr: Irr<f64> = xirr(&cash_flows, 0.10);
+
+        assert!(calculated_irr.is_valid());
+        assert!((calculated_irr.irr() - 0.130_662_386_291_807_5).abs() < 0.000_000_001);
+        assert!((xnpv(&cash_flows, 0.10) - pv([-100.0, 60.0, 60.0].iter(), 0.10)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn it_agrees_with_the_spreadsheet() {
+        let cash_flows: Vec<(i32, f32)> =
+            vec![(100, -1_000.0), (190, 300.0), (400, 450.0), (700, 500.0)];
+        let values: Vec<f32> = cash_flows.iter().map(|(_, amount)| *amount).collect();
+        let dates: Vec<f32> = cash_flows.iter().map(|(day, _)| *day as f32).collect();
+
+        assert_eq!(
+            xirr(&cash_flows, 0.10).irr(),
+            spreadsheet::xirr(&values, &dates, 0.10)
+        );
+    }
+
+    #[test]
+    fn it_values_at_the_first_date() {
+        // the first cash flow is not the earliest
+        let cash_flows: Vec<(i32, f64)> = vec![(365, 110.0), (0, -100.0)];
+
+        assert!(xnpv(&cash_flows, 0.10).abs() < 1e-12);
+        assert!((xirr(&cash_flows, 0.05).irr() - 0.10).abs() < 0.000_000_001);
+    }
+
+    #[test]
+    fn it_is_invalid_without_a_change_of_sign() {
+        let cash_flows: Vec<(i32, f64)> = vec![(0, 100.0), (31, 60.0)];
+        let calculated_irr: Irr<f64> = xirr(&cash_flows, 0.10);
+
+        assert!(!calculated_irr.is_valid());
+        assert!(calculated_irr.irr().is_nan());
+        assert!(!xirr(&[] as &[(i32, f64)], 0.10).is_valid());
+        assert_eq!(
+            xirr(&[] as &[(i32, f64)], 0.10).failure_reason(),
+            Some(FailureReason::BracketNotFound)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod xirr_from_dates_tests {
+    use chrono::NaiveDate;
+
+    use crate::xirr::{xirr, xirr_from_dates, xnpv, xnpv_from_dates};
+
+    #[test]
+    fn it_matches_the_day_numbers() {
+        let date =
+            |year: i32, month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let dated: Vec<(NaiveDate, f64)> = vec![
+            (date(2019, 12, 31), -1_000.0),
+            (date(2020, 2, 29), 300.0),
+            (date(2021, 3, 1), 450.0),
+            (date(2022, 1, 15), 500.0),
+        ];
+        // spreadsheet serial day numbers
+        let numbered: Vec<(i32, f64)> = vec![
+            (43_830, -1_000.0),
+            (43_890, 300.0),
+            (44_256, 450.0),
+            (44_576, 500.0),
+        ];
+
+        assert_eq!(xnpv_from_dates(&dated, 0.07), xnpv(&numbered, 0.07));
+        assert_eq!(xirr_from_dates(&dated, 0.10), xirr(&numbered, 0.10));
+    }
+}